@@ -4,4 +4,32 @@ import type { AttachmentType } from "./AttachmentType";
 /**
  * @description 定义附件的核心数据结构。
  */
-export type Attachment = { id: bigint, task_id: bigint, attachment_type: AttachmentType, payload: string, };
+export type Attachment = { id: bigint, task_id: bigint, attachment_type: AttachmentType, payload: string, 
+/**
+ * 显示标题，由用户填写或从网页获取
+ */
+title: string | null, 
+/**
+ * 文件大小（字节）
+ */
+file_size: bigint | null, 
+/**
+ * 文件的 MIME 类型
+ */
+mime_type: string | null, 
+/**
+ * 文件被添加时的原始文件名
+ */
+original_name: string | null, 
+/**
+ * 本地路径在最近一次检查时是否存在，`None` 表示尚未检查过
+ */
+exists: boolean | null, 
+/**
+ * 最近一次健康检查的时间
+ */
+last_checked_at: string | null, 
+/**
+ * 文件最近一次修改的时间
+ */
+modified_at: string | null, };
@@ -3,4 +3,4 @@
 /**
  * @description 定义附件的类型。
  */
-export type AttachmentType = "Url" | "LocalPath";
+export type AttachmentType = "Url" | "LocalPath" | "VaultFile" | "Note" | "Email" | "CodeRef";
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BulkTaskResult } from "./BulkTaskResult";
+
+/**
+ * 批量操作的结果。所有任务在同一个事务中处理，失败的任务不会影响其他任务。
+ */
+export type BulkTaskOutcome = { 
+/**
+ * 每个任务的结果，顺序与传入的 ID 相同
+ */
+results: Array<BulkTaskResult>, succeeded_count: bigint, failed_count: bigint, 
+/**
+ * 因此改变的所有任务，包括联动改变的后代任务、祖先任务和解除阻塞的任务
+ */
+changed_task_ids: Array<bigint>, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 批量操作中单个任务的结果。
+ */
+export type BulkTaskResult = { id: bigint, success: boolean, 
+/**
+ * 失败时已经本地化的错误信息
+ */
+error: string | null, };
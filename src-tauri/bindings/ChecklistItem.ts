@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 任务内的一个检查项。
+ */
+export type ChecklistItem = { id: bigint, task_id: bigint, text: string, is_checked: boolean, 
+/**
+ * 检查项在任务内的排列顺序，从 0 开始
+ */
+position: bigint, created_at: string, updated_at: string, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 任务中检查项的完成进度。
+ */
+export type ChecklistProgress = { 
+/**
+ * 检查项的总数
+ */
+total: bigint, 
+/**
+ * 已勾选的检查项数量
+ */
+checked: bigint, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 代码引用附件的内容：某个文件中的一行或一段连续的行。
+ */
+export type CodeRefPayload = { 
+/**
+ * 源文件的路径
+ */
+path: string, 
+/**
+ * 起始行号，从 1 开始
+ */
+line: number, 
+/**
+ * 结束行号（包含），为空时只引用起始行
+ */
+end_line: number | null, };
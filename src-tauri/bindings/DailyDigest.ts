@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DigestProjectGroup } from "./DigestProjectGroup";
+
+/**
+ * 每日概览：按项目汇总今天到期、已逾期和高优先级的未完成任务。
+ */
+export type DailyDigest = { 
+/**
+ * 概览对应的本地日期
+ */
+date: string, due_today_count: bigint, overdue_count: bigint, high_priority_count: bigint, 
+/**
+ * 按项目分组的明细，收件箱中的任务归入 `project_id` 为 `None` 的分组
+ */
+projects: Array<DigestProjectGroup>, };
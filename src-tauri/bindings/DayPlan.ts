@@ -0,0 +1,32 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EstimateUnit } from "./EstimateUnit";
+import type { Task } from "./Task";
+
+/**
+ * @description “规划我的一天”的结果：在每日容量内安排的任务，以及放不下的任务。
+ */
+export type DayPlan = { 
+/**
+ * 计划对应的本地日期
+ */
+date: string, 
+/**
+ * 估算值的单位
+ */
+unit: EstimateUnit, 
+/**
+ * 当天可以安排的工作量
+ */
+capacity: bigint, 
+/**
+ * 已安排任务的工作量之和，尚未估算的任务按默认估算计算
+ */
+planned_estimate: bigint, 
+/**
+ * 安排在当天的任务，按优先级和截止日期排列
+ */
+planned: Array<Task>, 
+/**
+ * 超出容量、没有安排进来的任务，顺序与 `planned` 相同
+ */
+overflow: Array<Task>, };
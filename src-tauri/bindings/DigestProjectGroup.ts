@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Task } from "./Task";
+
+/**
+ * 每日概览中单个项目的分组。
+ * 每个任务只会出现在一个列表中：逾期优先于今天到期，二者又优先于高优先级。
+ */
+export type DigestProjectGroup = { project_id: bigint | null, project_name: string | null, due_today: Array<Task>, overdue: Array<Task>, high_priority: Array<Task>, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 每日概览（晨间摘要）的设置，持久化在 `settings.json` 中。
+ */
+export type DigestSettings = { 
+/**
+ * 是否启用每日概览
+ */
+enabled: boolean, 
+/**
+ * 每天发送概览的本地时间
+ */
+time: string, 
+/**
+ * 休息日，不发送概览。取值为 ISO 星期序号：1 = 周一 … 7 = 周日
+ */
+days_off: Array<number>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 复制任务时，副本放在哪里。
+ */
+export type DuplicateTarget = "Original" | { "Parent": bigint } | { "Project": bigint | null };
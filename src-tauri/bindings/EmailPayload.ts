@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 邮件附件的内容，解析自 `.eml` 文件的邮件头。
+ */
+export type EmailPayload = { 
+/**
+ * `.eml` 文件的路径
+ */
+path: string, subject: string | null, from: string | null, date: string | null, };
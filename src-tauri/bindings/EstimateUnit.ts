@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 工作量估算的单位。
+ */
+export type EstimateUnit = "Minutes" | "Points";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 后端支持的界面语言。
+ */
+export type Language = "en" | "zh-CN";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 完成一个任务时，如何处理它尚未完成的后代任务。
+ */
+export type OpenDescendantsPolicy = "Ignore" | "Complete" | "Warn";
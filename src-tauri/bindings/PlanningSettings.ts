@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EstimateUnit } from "./EstimateUnit";
+
+/**
+ * 工作量估算和每日计划的设置，持久化在 `settings.json` 中。
+ */
+export type PlanningSettings = { 
+/**
+ * 所有估算值使用的单位
+ */
+estimate_unit: EstimateUnit, 
+/**
+ * 每天可以安排的工作量，单位与估算相同
+ */
+daily_capacity: number, 
+/**
+ * 制定计划时，尚未估算的任务按这个工作量计算
+ */
+default_estimate: number, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 一个已完成的专注时段
+ */
+export type Pomodoro = { id: bigint, task_id: bigint | null, started_at: string, ended_at: string, 
+/**
+ * 实际专注的时长（秒），不包含暂停的时间
+ */
+duration_seconds: bigint, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 番茄钟的阶段
+ */
+export type PomodoroPhase = "Work" | "ShortBreak" | "LongBreak";
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 番茄钟设置，持久化在 `settings.json` 中。
+ */
+export type PomodoroSettings = { 
+/**
+ * 专注时段的时长（分钟）
+ */
+work_minutes: number, 
+/**
+ * 短休息的时长（分钟）
+ */
+short_break_minutes: number, 
+/**
+ * 长休息的时长（分钟）
+ */
+long_break_minutes: number, 
+/**
+ * 每完成多少个专注时段后进入一次长休息
+ */
+long_break_interval: number, 
+/**
+ * 休息结束后是否自动开始下一个专注时段；否则休息结束时番茄钟停止
+ */
+auto_start_work: boolean, };
@@ -0,0 +1,31 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PomodoroPhase } from "./PomodoroPhase";
+
+/**
+ * @description 正在进行的番茄钟的状态，由后端维护并通过 `pomodoro_tick` 事件推送给前端。
+ */
+export type PomodoroState = { 
+/**
+ * 关联的任务，为空时表示未关联任务
+ */
+task_id: bigint | null, phase: PomodoroPhase, 
+/**
+ * 当前阶段开始的时间
+ */
+phase_started_at: string, 
+/**
+ * 当前阶段预计结束的时间；暂停期间不会前进
+ */
+phase_ends_at: string, 
+/**
+ * 当前阶段剩余的秒数
+ */
+remaining_seconds: bigint, 
+/**
+ * 当前阶段的总时长（秒）
+ */
+phase_seconds: bigint, is_paused: boolean, 
+/**
+ * 本轮已完成的专注时段数量，用于决定何时进入长休息
+ */
+completed_work_sessions: number, };
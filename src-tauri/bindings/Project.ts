@@ -1,6 +1,48 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectStats } from "./ProjectStats";
+import type { ProjectStatus } from "./ProjectStatus";
 
 /**
  * 项目的领域模型，定义了一个“项目”或“分类”的核心属性。
  */
-export type Project = { id: bigint, name: string, created_at: string, updated_at: string, };
+export type Project = { id: bigint, name: string, 
+/**
+ * 颜色，格式为 `#RRGGBB`
+ */
+color: string | null, 
+/**
+ * 图标，可以是 emoji 或前端图标的名称
+ */
+icon: string | null, description: string | null, status: ProjectStatus, 
+/**
+ * 父项目的 ID，为空时是顶层项目（区域）
+ */
+parent_id: bigint | null, 
+/**
+ * 直接属于该项目的任务数量（包含子任务）
+ */
+task_count: bigint, 
+/**
+ * 直接属于该项目的未完成任务数量
+ */
+open_task_count: bigint, 
+/**
+ * 该项目及其所有后代项目中的任务数量
+ */
+subtree_task_count: bigint, 
+/**
+ * 该项目及其所有后代项目中的未完成任务数量
+ */
+subtree_open_task_count: bigint, 
+/**
+ * 该项目及其所有后代项目中所有任务的估算之和
+ */
+subtree_estimate: bigint, 
+/**
+ * 该项目及其所有后代项目中尚未完成的任务的估算之和
+ */
+subtree_remaining_estimate: bigint, 
+/**
+ * 项目的统计数据，只有在获取项目时要求包含统计数据才会填充
+ */
+stats: ProjectStats | null, created_at: string, updated_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 删除项目时，如何处理项目中的任务。
+ */
+export type ProjectDeletionStrategy = "MoveToInbox" | { "MoveToProject": bigint } | "DeleteTasks";
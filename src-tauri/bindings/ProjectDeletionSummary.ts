@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 删除项目后的结果汇总。
+ */
+export type ProjectDeletionSummary = { project_id: bigint, 
+/**
+ * 被移动的任务数量（包含子任务）
+ */
+moved_task_count: bigint, 
+/**
+ * 被删除的任务数量（包含子任务）
+ */
+deleted_task_count: bigint, 
+/**
+ * 被移动到上一级的子项目数量
+ */
+reparented_project_count: bigint, };
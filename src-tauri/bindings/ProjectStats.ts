@@ -0,0 +1,39 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WeeklyCompletion } from "./WeeklyCompletion";
+
+/**
+ * @description 项目的进度与统计数据，统计范围包含项目中任务的各级子任务。
+ */
+export type ProjectStats = { project_id: bigint, total_count: bigint, completed_count: bigint, open_count: bigint, 
+/**
+ * 已过截止日期但尚未完成的任务数量
+ */
+overdue_count: bigint, 
+/**
+ * 完成百分比（0 - 100），没有任务时为 0
+ */
+completion_percentage: number, 
+/**
+ * 最近几周每周完成的任务数量，按时间升序排列
+ */
+completed_per_week: Array<WeeklyCompletion>, 
+/**
+ * 从创建到完成的平均时长（小时），没有已完成的任务时为空
+ */
+average_completion_hours: number | null, 
+/**
+ * 为项目中的任务完成的番茄钟数量
+ */
+pomodoro_count: bigint, 
+/**
+ * 项目中各级任务记录的总时长（秒）
+ */
+tracked_seconds: bigint, 
+/**
+ * 项目中所有任务的估算之和，不含子项目；包含子项目的汇总见 `Project::subtree_estimate`
+ */
+total_estimate: bigint, 
+/**
+ * 项目中尚未完成的任务的估算之和，不含子项目
+ */
+remaining_estimate: bigint, };
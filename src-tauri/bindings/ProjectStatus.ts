@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 定义项目的状态。
+ * 已归档的项目及其子项目、任务不会出现在默认的列表中，但不会被删除。
+ */
+export type ProjectStatus = "Active" | "OnHold" | "Completed" | "Archived";
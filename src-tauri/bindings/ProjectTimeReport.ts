@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskTrackedTime } from "./TaskTrackedTime";
+
+/**
+ * @description 项目在一段时间内的工时汇总，用于按小时结算。
+ */
+export type ProjectTimeReport = { project_id: bigint, 
+/**
+ * 统计范围的开始时间，为空时不限制
+ */
+from: string | null, 
+/**
+ * 统计范围的结束时间，为空时不限制
+ */
+to: string | null, total_seconds: bigint, 
+/**
+ * 按任务汇总的工时，只包含有记录的任务，按时长降序排列
+ */
+tasks: Array<TaskTrackedTime>, };
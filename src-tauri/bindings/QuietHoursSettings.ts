@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 免打扰设置：每天固定的安静时段，以及手动开启的“专注/勿扰直到某时”。
+ * 处于这些时段内的提醒会被推迟到时段结束后再发送。
+ */
+export type QuietHoursSettings = { 
+/**
+ * 是否启用每天的安静时段
+ */
+enabled: boolean, 
+/**
+ * 安静时段开始的本地时间
+ */
+start: string, 
+/**
+ * 安静时段结束的本地时间。早于 `start` 时表示跨越午夜（如 22:00 - 07:00）
+ */
+end: string, 
+/**
+ * 是否允许高优先级任务的提醒无视免打扰立即发送
+ */
+allow_high_priority: boolean, 
+/**
+ * 手动开启的勿扰模式的结束时间，`None` 表示未开启
+ */
+do_not_disturb_until: string | null, };
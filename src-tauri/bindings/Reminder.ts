@@ -11,4 +11,33 @@ remind_at: string,
 /**
  * 是否已发送
  */
-is_sent: boolean, };
+is_sent: boolean, 
+/**
+ * 相对提醒：在截止日期前多少分钟提醒。
+ * 为 `None` 时表示这是一个绝对时间的提醒。
+ */
+offset_minutes: bigint | null, 
+/**
+ * 提醒实际发出的时间
+ */
+sent_at: string | null, 
+/**
+ * 用户是否已在收件箱中处理了该提醒
+ */
+is_dismissed: boolean, 
+/**
+ * 被推迟（snooze）的次数
+ */
+snooze_count: bigint, 
+/**
+ * 是否因应用关闭而错过（错过的提醒不会再单独发送）
+ */
+is_missed: boolean, 
+/**
+ * 因免打扰被推迟到的时间，在此之前不会发送
+ */
+deferred_until: string | null, 
+/**
+ * 被推迟（snooze）到的时间。再次发出之前，截止日期的变化不会覆盖它
+ */
+snoozed_until: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 复制任务时如何处理提醒。
+ */
+export type ReminderCopyMode = "Shift" | "Drop";
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 项目内的分组，用于将一个项目中的任务划分为有序的几个部分。
+ */
+export type Section = { id: bigint, project_id: bigint, name: string, 
+/**
+ * 分组在项目内的排列顺序，从 0 开始
+ */
+position: bigint, created_at: string, updated_at: string, };
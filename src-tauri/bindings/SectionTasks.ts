@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Section } from "./Section";
+import type { Task } from "./Task";
+
+/**
+ * @description 按分组归类后的一组任务。
+ */
+export type SectionTasks = { 
+/**
+ * 为空时表示不属于任何分组的任务
+ */
+section: Section | null, tasks: Array<Task>, };
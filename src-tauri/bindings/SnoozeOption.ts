@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 稍后提醒的选项。
+ */
+export type SnoozeOption = "TenMinutes" | "OneHour" | "TomorrowMorning" | { "Custom": string };
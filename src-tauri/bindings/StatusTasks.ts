@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Task } from "./Task";
+import type { WorkflowStatus } from "./WorkflowStatus";
+
+/**
+ * @description 按工作流状态归类后的一组任务，用于看板视图中的一列。
+ */
+export type StatusTasks = { status: WorkflowStatus, tasks: Array<Task>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 任务的标签。标签在所有项目之间共享，名称不区分大小写。
+ */
+export type Tag = { id: bigint, name: string, created_at: string, updated_at: string, };
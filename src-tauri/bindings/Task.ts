@@ -1,5 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChecklistProgress } from "./ChecklistProgress";
 import type { Priority } from "./Priority";
+import type { WorkflowStatus } from "./WorkflowStatus";
 
 /**
  * 核心业务模型：任务
@@ -22,6 +24,10 @@ description: string | null,
  * 任务是否已完成。
  */
 is_completed: boolean, 
+/**
+ * 任务当前所处的工作流状态。
+ */
+status: WorkflowStatus, 
 /**
  * 任务所属项目的 ID。
  */
@@ -29,7 +35,51 @@ project_id: bigint | null,
 /**
  * 父任务的ID。
  */
-parent_id: bigint | null, subtask_count: bigint, 
+parent_id: bigint | null, 
+/**
+ * 任务在项目内所属分组的 ID，为空时不属于任何分组。
+ */
+section_id: bigint | null, subtask_count: bigint, 
+/**
+ * 所有后代任务（子任务、子任务的子任务……）的数量。
+ */
+descendant_count: bigint, 
+/**
+ * 所有后代任务中已完成的数量。
+ */
+completed_descendant_count: bigint, 
+/**
+ * 任务是否被尚未完成的依赖任务阻塞。
+ */
+is_blocked: boolean, 
+/**
+ * 阻塞该任务的、尚未完成的依赖任务的 ID。
+ */
+blocked_by: Array<bigint>, 
+/**
+ * 任务记录的总时长（秒），不包含子任务。
+ */
+tracked_seconds: bigint, 
+/**
+ * 为该任务完成的番茄钟（专注时段）数量。
+ */
+pomodoro_count: bigint, 
+/**
+ * 任务中检查项的完成进度。
+ */
+checklist_progress: ChecklistProgress, 
+/**
+ * 任务本身的工作量估算，单位由计划设置决定（分钟或故事点）；为空时表示尚未估算。
+ */
+estimate: bigint | null, 
+/**
+ * 任务及其所有后代任务的估算之和。
+ */
+rolled_up_estimate: bigint, 
+/**
+ * 任务及其所有后代任务中，尚未完成部分的估算之和。
+ */
+remaining_estimate: bigint, 
 /**
  * 优先级
  */
@@ -46,4 +96,8 @@ next_reminder_at: string | null,
  * 使用 `chrono` 的 `DateTime<Utc>` 来确保所有时间戳都使用统一的世界协调时（UTC）。
  * 这是处理时间的最佳实践，可以避免因用户本地时区不同而导致的数据混乱。
  */
-created_at: string, updated_at: string, };
+created_at: string, updated_at: string, 
+/**
+ * 任务的完成时间，未完成时为空。
+ */
+completed_at: string | null, };
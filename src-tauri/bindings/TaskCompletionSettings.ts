@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OpenDescendantsPolicy } from "./OpenDescendantsPolicy";
+
+/**
+ * 任务完成状态联动的设置，持久化在 `settings.json` 中。
+ */
+export type TaskCompletionSettings = { 
+/**
+ * 所有子任务都完成后，自动完成父任务；重新打开子任务时，也会重新打开已完成的父任务
+ */
+auto_complete_parent: boolean, 
+/**
+ * 完成任务时如何处理尚未完成的后代任务
+ */
+open_descendants: OpenDescendantsPolicy, };
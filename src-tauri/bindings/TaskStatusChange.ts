@@ -0,0 +1,31 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Task } from "./Task";
+
+/**
+ * 更新任务完成状态的结果，包含因联动而一并改变的其他任务。
+ */
+export type TaskStatusChange = { 
+/**
+ * 更新后的任务
+ */
+task: Task, 
+/**
+ * 状态是否已经更新；在“提醒”策略下，存在未完成的后代任务时不会更新
+ */
+applied: boolean, 
+/**
+ * 完成任务时，尚未完成的后代任务数量
+ */
+open_descendant_count: bigint, 
+/**
+ * 随之被一并完成的后代任务
+ */
+completed_descendant_ids: Array<bigint>, 
+/**
+ * 随之被自动完成（或重新打开）的祖先任务，由近及远排列
+ */
+updated_ancestor_ids: Array<bigint>, 
+/**
+ * 因最后一个阻塞任务完成而解除阻塞的任务
+ */
+unblocked_task_ids: Array<bigint>, };
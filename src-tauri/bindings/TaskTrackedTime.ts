@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 单个任务在统计范围内的工时。
+ */
+export type TaskTrackedTime = { task_id: bigint, title: string, total_seconds: bigint, };
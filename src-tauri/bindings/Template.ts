@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TemplateKind } from "./TemplateKind";
+
+/**
+ * @description 从已有的任务或项目中保存下来的模板。
+ */
+export type Template = { id: bigint, name: string, kind: TemplateKind, 
+/**
+ * 项目模板创建的项目名称，可以包含 `{{变量}}`；任务模板为空
+ */
+project_name: string | null, project_color: string | null, project_icon: string | null, project_description: string | null, 
+/**
+ * 模板中的任务数量（包含子任务）
+ */
+task_count: bigint, 
+/**
+ * 模板中用到的变量名，去重后按名称排序
+ */
+variables: Array<string>, created_at: string, updated_at: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AttachmentType } from "./AttachmentType";
+
+/**
+ * @description 模板任务的附件，字段含义与 `Attachment` 相同。
+ */
+export type TemplateAttachment = { id: bigint, template_task_id: bigint, attachment_type: AttachmentType, payload: string, title: string | null, file_size: bigint | null, mime_type: string | null, original_name: string | null, };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Project } from "./Project";
+import type { Task } from "./Task";
+
+/**
+ * @description 使用模板创建的结果。
+ */
+export type TemplateInstance = { template_id: bigint, 
+/**
+ * 项目模板创建的项目，任务模板为空
+ */
+project: Project | null, 
+/**
+ * 创建的顶层任务
+ */
+tasks: Array<Task>, 
+/**
+ * 创建的任务总数（包含子任务）
+ */
+created_task_count: bigint, };
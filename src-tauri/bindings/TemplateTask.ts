@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Priority } from "./Priority";
+import type { TemplateAttachment } from "./TemplateAttachment";
+
+/**
+ * @description 模板中的一个任务。
+ */
+export type TemplateTask = { id: bigint, template_id: bigint, 
+/**
+ * 模板中父任务的 ID，为空时是模板的顶层任务
+ */
+parent_id: bigint | null, 
+/**
+ * 在兄弟任务中的顺序，从 0 开始
+ */
+position: bigint, title: string, description: string | null, priority: Priority, estimate: bigint | null, 
+/**
+ * 截止日期相对于锚点日期（本地时间零点）的偏移量（分钟），为空时没有截止日期
+ */
+due_offset_minutes: bigint | null, 
+/**
+ * 相对提醒：在截止日期前多少分钟提醒，按从远到近排序
+ */
+reminder_offsets: Array<bigint>, attachments: Array<TemplateAttachment>, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 一条时间记录，由计时器产生或手动录入。
+ */
+export type TimeEntry = { id: bigint, task_id: bigint, started_at: string, 
+/**
+ * 为空时表示计时器正在运行
+ */
+ended_at: string | null, note: string | null, 
+/**
+ * 记录的时长（秒），正在运行的计时器计算到当前时间
+ */
+duration_seconds: bigint, created_at: string, updated_at: string, };
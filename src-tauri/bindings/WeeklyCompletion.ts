@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 某一周内完成的任务数量。
+ */
+export type WeeklyCompletion = { 
+/**
+ * 该周的第一天（周一）
+ */
+week_start: string, completed_count: bigint, };
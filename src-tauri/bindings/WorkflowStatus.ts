@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * @description 任务的工作流状态，例如“待办 / 进行中 / 已完成”。
+ * 项目可以定义自己的一组状态，没有定义的项目使用全局状态。
+ */
+export type WorkflowStatus = { id: bigint, 
+/**
+ * 所属的项目，为空时是全局状态
+ */
+project_id: bigint | null, name: string, 
+/**
+ * 状态在看板中的排列顺序，从 0 开始
+ */
+position: bigint, 
+/**
+ * 处于该状态的任务是否视为已完成
+ */
+counts_as_done: boolean, };
@@ -0,0 +1,84 @@
+# Momentum 后端英文文案
+# 格式为 Fluent 的简单消息子集：每行一条 `key = value`，变量写作 `{ $name }`
+
+## 托盘
+tray-show = Show Window
+tray-exit = Quit
+tray-tooltip = Momentum is running
+tray-tooltip-timer = Momentum: tracking "{ $task }" ({ $elapsed })
+
+## 通知
+notification-reminder-title = Momentum Reminder
+notification-reminder-body = Task '{ $title }' is due soon!
+notification-missed-title = Momentum: Missed Reminders
+notification-missed-body = { $count } reminder(s) were missed while Momentum was closed
+notification-missed-more = …and { $count } more
+notification-digest-title = Momentum: Today's Overview
+notification-digest-body = Due today: { $due_today }, overdue: { $overdue }, high priority: { $high_priority }
+notification-pomodoro-break-title = Focus session complete
+notification-pomodoro-break-body = Nice work! Take a { $minutes }-minute break.
+notification-pomodoro-work-title = Break is over
+notification-pomodoro-work-body = Your next { $minutes }-minute focus session has started.
+notification-pomodoro-finished-title = Break is over
+notification-pomodoro-finished-body = Start a new focus session when you are ready.
+
+## 错误
+error-not-found = The requested item does not exist.
+error-already-exists = An item with the same name already exists.
+error-database = Database error: { $detail }
+error-io = File system error: { $detail }
+error-settings = Failed to read or save settings: { $detail }
+error-internal = Internal error: { $detail }
+error-negative-reminder-offset = Reminder offsets cannot be negative.
+error-relative-reminder-without-due-date = The task has no due date, so a relative reminder cannot be created.
+error-snooze-in-past = The snooze time must be in the future.
+error-invalid-day-off = Days off must be numbers from 1 (Monday) to 7 (Sunday).
+error-do-not-disturb-in-past = The do-not-disturb end time must be in the future.
+error-vault-not-a-file = Only files (not folders) can be copied into the vault.
+error-note-empty = A note cannot be empty.
+error-attachment-not-a-note = This attachment is not a note.
+error-attachment-has-no-file = This attachment has no file to open.
+error-email-not-eml = Only .eml email files can be attached as emails.
+error-code-ref-invalid-line = Line numbers start at 1, and the end line cannot come before the start line.
+error-code-ref-not-a-file = The referenced source file does not exist.
+error-code-ref-line-out-of-range = The referenced lines are outside the file, which has { $line_count } line(s).
+error-url-invalid = This is not a valid web address.
+error-url-unsupported-scheme = Links using "{ $scheme }:" are not supported; use http or https.
+error-url-duplicate = This link is already attached to the task.
+error-url-fetch-failed = Failed to fetch the page title: { $detail }
+error-project-move-cycle = A project cannot be moved into itself or one of its sub-projects.
+error-invalid-color = Colors must be written as #RRGGBB.
+error-section-other-project = A task can only be moved to a section in its own project.
+error-delete-project-target-self = Tasks cannot be moved into the project that is being deleted.
+error-dependency-cycle = A task cannot depend on itself or on a task that already depends on it.
+error-status-other-project = This status belongs to a different project.
+error-status-name-duplicate = A status with this name already exists.
+error-status-set-incomplete = A workflow needs at least one open status and one status that counts as done.
+error-status-replacement-invalid = Tasks can only be moved to another status of the same workflow.
+error-timer-already-running = A timer is already running for "{ $task }". Stop it first.
+error-timer-not-running = No timer is running.
+error-time-entry-invalid-range = The end time must be after the start time.
+error-time-entry-end-required = A finished time entry needs an end time.
+error-pomodoro-already-running = A focus session is already running.
+error-pomodoro-not-running = No focus session is running.
+error-pomodoro-invalid-duration = Focus and break lengths must be at least one minute.
+error-estimate-negative = An estimate cannot be negative.
+error-planning-invalid-capacity = The daily capacity must be greater than zero.
+error-template-missing-variable = Please provide a value for the template variable "{ $name }".
+error-template-project-under-task = A project template cannot be created under a task.
+error-task-open-descendants = This task still has { $count } open subtasks.
+error-tag-name-empty = A tag name cannot be empty.
+error-tag-name-duplicate = A tag with this name already exists.
+error-checklist-item-empty = A checklist item cannot be empty.
+error-unknown-attachment-type = Unknown attachment type: { $value }
+error-unknown-project-status = Unknown project status: { $value }
+error-unknown-template-kind = Unknown template kind: { $value }
+status-todo = Todo
+status-in-progress = In Progress
+status-waiting = Waiting
+status-done = Done
+status-cancelled = Cancelled
+error-time-entry-overlap = This time overlaps an existing entry for "{ $task }".
+error-reorder-ids-mismatch = The new order must list every item exactly once.
+error-project-name-duplicate = A project with this name already exists here.
+error-delete-project-child-name-clash = The sub-project "{ $name }" cannot be moved up because a project with the same name already exists there.
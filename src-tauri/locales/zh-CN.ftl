@@ -0,0 +1,84 @@
+# Momentum 后端中文文案
+# 格式为 Fluent 的简单消息子集：每行一条 `key = value`，变量写作 `{ $name }`
+
+## 托盘
+tray-show = 显示窗口
+tray-exit = 退出
+tray-tooltip = Momentum 正在运行
+tray-tooltip-timer = Momentum：正在为“{ $task }”计时（{ $elapsed }）
+
+## 通知
+notification-reminder-title = Momentum 任务提醒
+notification-reminder-body = 任务 '{ $title }' 即将到期！
+notification-missed-title = Momentum 错过的提醒
+notification-missed-body = 应用关闭期间错过了 { $count } 个提醒
+notification-missed-more = …等另外 { $count } 项
+notification-digest-title = Momentum 今日概览
+notification-digest-body = 今天到期 { $due_today } 项，已逾期 { $overdue } 项，高优先级 { $high_priority } 项
+notification-pomodoro-break-title = 专注时段已完成
+notification-pomodoro-break-body = 干得好！休息 { $minutes } 分钟吧。
+notification-pomodoro-work-title = 休息结束
+notification-pomodoro-work-body = 下一个 { $minutes } 分钟的专注时段已经开始。
+notification-pomodoro-finished-title = 休息结束
+notification-pomodoro-finished-body = 准备好后，开始新的专注时段吧。
+
+## 错误
+error-not-found = 请求的数据不存在。
+error-already-exists = 一个同名的项目已经存在。
+error-database = 数据库错误：{ $detail }
+error-io = 文件系统错误：{ $detail }
+error-settings = 读取或保存设置失败：{ $detail }
+error-internal = 内部错误：{ $detail }
+error-negative-reminder-offset = 提醒偏移量不能为负数。
+error-relative-reminder-without-due-date = 任务尚未设置截止日期，无法创建相对提醒。
+error-snooze-in-past = 稍后提醒的时间必须晚于当前时间。
+error-invalid-day-off = 休息日必须是 1（周一）到 7（周日）之间的数字。
+error-do-not-disturb-in-past = 勿扰模式的结束时间必须晚于当前时间。
+error-vault-not-a-file = 只有文件（而非文件夹）才能复制到附件保管库中。
+error-note-empty = 笔记内容不能为空。
+error-attachment-not-a-note = 该附件不是笔记。
+error-attachment-has-no-file = 该附件没有可以打开的文件。
+error-email-not-eml = 只能将 .eml 邮件文件添加为邮件附件。
+error-code-ref-invalid-line = 行号从 1 开始，且结束行不能早于起始行。
+error-code-ref-not-a-file = 引用的源文件不存在。
+error-code-ref-line-out-of-range = 引用的行超出了文件范围，该文件共有 { $line_count } 行。
+error-url-invalid = 这不是一个有效的网址。
+error-url-unsupported-scheme = 不支持“{ $scheme }:”类型的链接，请使用 http 或 https。
+error-url-duplicate = 该任务已经添加过这个链接。
+error-url-fetch-failed = 获取网页标题失败：{ $detail }
+error-project-move-cycle = 不能将项目移动到它自身或它的子项目下。
+error-invalid-color = 颜色的格式必须为 #RRGGBB。
+error-section-other-project = 任务只能移动到它所在项目中的分组。
+error-delete-project-target-self = 不能将任务移动到正在被删除的项目中。
+error-dependency-cycle = 任务不能依赖于它自身，或依赖于已经依赖它的任务。
+error-status-other-project = 该状态属于其他项目。
+error-status-name-duplicate = 已经存在同名的状态。
+error-status-set-incomplete = 工作流中至少需要一个未完成状态和一个视为已完成的状态。
+error-status-replacement-invalid = 任务只能切换到同一工作流中的其他状态。
+error-timer-already-running = “{ $task }”的计时器正在运行，请先停止它。
+error-timer-not-running = 当前没有正在运行的计时器。
+error-time-entry-invalid-range = 结束时间必须晚于开始时间。
+error-time-entry-end-required = 已结束的时间记录必须有结束时间。
+error-pomodoro-already-running = 已经有一个番茄钟在进行中。
+error-pomodoro-not-running = 当前没有进行中的番茄钟。
+error-pomodoro-invalid-duration = 专注和休息的时长至少为 1 分钟。
+error-estimate-negative = 估算不能为负数。
+error-planning-invalid-capacity = 每日容量必须大于 0。
+error-template-missing-variable = 请为模板变量“{ $name }”提供一个值。
+error-template-project-under-task = 项目模板不能创建在任务下。
+error-task-open-descendants = 该任务还有 { $count } 个未完成的子任务。
+error-tag-name-empty = 标签名称不能为空。
+error-tag-name-duplicate = 已经存在同名的标签。
+error-checklist-item-empty = 检查项的内容不能为空。
+error-unknown-attachment-type = 未知的附件类型：{ $value }
+error-unknown-project-status = 未知的项目状态：{ $value }
+error-unknown-template-kind = 未知的模板类型：{ $value }
+status-todo = 待办
+status-in-progress = 进行中
+status-waiting = 等待
+status-done = 已完成
+status-cancelled = 已取消
+error-time-entry-overlap = 该时间段与“{ $task }”已有的时间记录重叠。
+error-reorder-ids-mismatch = 新的顺序必须恰好包含每一项一次。
+error-project-name-duplicate = 同一位置已经存在同名的项目。
+error-delete-project-child-name-clash = 子项目“{ $name }”无法移动到上一级，因为那里已经存在同名的项目。
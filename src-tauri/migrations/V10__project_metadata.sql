@@ -0,0 +1,7 @@
+-- 版本 10: 项目的颜色、图标、描述和状态
+
+ALTER TABLE projects ADD COLUMN color TEXT;
+ALTER TABLE projects ADD COLUMN icon TEXT;
+ALTER TABLE projects ADD COLUMN description TEXT;
+-- 项目状态：Active / OnHold / Completed / Archived
+ALTER TABLE projects ADD COLUMN status TEXT NOT NULL DEFAULT 'Active';
@@ -0,0 +1,17 @@
+-- 版本 11: 项目内的分组（Section）
+
+CREATE TABLE IF NOT EXISTS sections (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id      INTEGER NOT NULL,
+    name            TEXT NOT NULL,
+    -- 分组在项目内的排列顺序，从 0 开始
+    position        INTEGER NOT NULL DEFAULT 0,
+    created_at      TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    updated_at      TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_sections_project_id ON sections (project_id);
+
+-- 任务所属的分组，为 NULL 时任务不属于任何分组
+ALTER TABLE tasks ADD COLUMN section_id INTEGER REFERENCES sections(id) ON DELETE SET NULL;
@@ -0,0 +1,6 @@
+-- 版本 12: 记录任务的完成时间，用于项目统计
+
+ALTER TABLE tasks ADD COLUMN completed_at TEXT;
+
+-- 已完成的旧任务没有记录完成时间，以最后一次更新的时间作为近似值
+UPDATE tasks SET completed_at = updated_at WHERE is_completed = 1;
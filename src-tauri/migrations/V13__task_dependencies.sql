@@ -0,0 +1,15 @@
+-- 版本 13: 任务之间的依赖关系（“完成 Y 之前不能开始 X”）
+
+CREATE TABLE IF NOT EXISTS task_dependencies (
+    -- 被阻塞的任务
+    task_id             INTEGER NOT NULL,
+    -- 需要先完成的任务
+    depends_on_task_id  INTEGER NOT NULL,
+    created_at          TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    PRIMARY KEY (task_id, depends_on_task_id),
+    CHECK (task_id <> depends_on_task_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (depends_on_task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_dependencies_depends_on ON task_dependencies (depends_on_task_id);
@@ -0,0 +1,33 @@
+-- 版本 14: 自定义工作流状态（例如 待办 / 进行中 / 等待 / 已完成 / 已取消）
+
+CREATE TABLE IF NOT EXISTS workflow_statuses (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- 状态所属的项目，为 NULL 时是全局状态；没有自定义状态的项目使用全局状态
+    project_id      INTEGER,
+    name            TEXT NOT NULL,
+    -- 状态在看板中的排列顺序，从 0 开始
+    position        INTEGER NOT NULL DEFAULT 0,
+    -- 处于该状态的任务是否视为已完成
+    counts_as_done  INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflow_statuses_name ON workflow_statuses (IFNULL(project_id, 0), name);
+
+INSERT INTO workflow_statuses (project_id, name, position, counts_as_done) VALUES
+    (NULL, 'Todo', 0, 0),
+    (NULL, 'In Progress', 1, 0),
+    (NULL, 'Waiting', 2, 0),
+    (NULL, 'Done', 3, 1),
+    (NULL, 'Cancelled', 4, 1);
+
+-- 任务的状态取代原来的完成标记。
+-- `is_completed` 列保留，并始终与状态的 `counts_as_done` 保持一致，供现有的查询和索引使用。
+ALTER TABLE tasks ADD COLUMN status_id INTEGER REFERENCES workflow_statuses(id);
+
+UPDATE tasks SET status_id = (
+    SELECT id FROM workflow_statuses
+    WHERE project_id IS NULL AND name = CASE WHEN tasks.is_completed = 1 THEN 'Done' ELSE 'Todo' END
+);
+
+CREATE INDEX IF NOT EXISTS idx_tasks_status_id ON tasks (status_id);
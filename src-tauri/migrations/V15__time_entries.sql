@@ -0,0 +1,18 @@
+-- 版本 15: 任务的时间记录（计时器和手动录入）
+
+CREATE TABLE IF NOT EXISTS time_entries (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id     INTEGER NOT NULL,
+    -- 开始和结束时间均为 UTC；结束时间为 NULL 表示计时器正在运行
+    started_at  TEXT NOT NULL,
+    ended_at    TEXT,
+    note        TEXT,
+    created_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    updated_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_entries_task_id ON time_entries (task_id);
+
+-- 同一时间最多只有一个正在运行的计时器：所有运行中的记录在该索引上的值都相同
+CREATE UNIQUE INDEX IF NOT EXISTS idx_time_entries_running ON time_entries ((ended_at IS NULL)) WHERE ended_at IS NULL;
@@ -0,0 +1,15 @@
+-- 版本 16: 已完成的番茄钟（专注时段）记录
+
+CREATE TABLE IF NOT EXISTS pomodoros (
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- 关联的任务，为 NULL 时表示未关联任务（或任务已被删除）
+    task_id           INTEGER,
+    -- 开始和结束时间均为 UTC
+    started_at        TEXT NOT NULL,
+    ended_at          TEXT NOT NULL,
+    -- 实际专注的时长（秒），不包含暂停的时间
+    duration_seconds  INTEGER NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_pomodoros_task_id ON pomodoros (task_id);
@@ -0,0 +1,4 @@
+-- 版本 17: 任务的工作量估算，用于汇总和每日计划
+
+-- 估算值的单位（分钟或故事点）由全局的计划设置决定，为 NULL 时表示尚未估算
+ALTER TABLE tasks ADD COLUMN estimate INTEGER CHECK (estimate IS NULL OR estimate >= 0);
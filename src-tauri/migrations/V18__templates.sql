@@ -0,0 +1,56 @@
+-- 版本 18: 任务模板和项目模板
+
+CREATE TABLE IF NOT EXISTS templates (
+    id                   INTEGER PRIMARY KEY AUTOINCREMENT,
+    name                 TEXT NOT NULL,
+    -- 模板类型：Task（任务及其子任务）/ Project（项目及其任务）
+    kind                 TEXT NOT NULL CHECK (kind IN ('Task', 'Project')),
+    -- 以下字段只用于项目模板
+    project_name         TEXT,
+    project_color        TEXT,
+    project_icon         TEXT,
+    project_description  TEXT,
+    created_at           TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    updated_at           TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime'))
+);
+
+-- 模板中的任务，通过 parent_id 组成与原任务相同的层级
+CREATE TABLE IF NOT EXISTS template_tasks (
+    id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+    template_id         INTEGER NOT NULL,
+    parent_id           INTEGER,
+    position            INTEGER NOT NULL DEFAULT 0,
+    title               TEXT NOT NULL,
+    description         TEXT,
+    priority            INTEGER NOT NULL DEFAULT 0,
+    estimate            INTEGER,
+    -- 截止日期相对于锚点日期（本地时间零点）的偏移量（分钟），为 NULL 时没有截止日期
+    due_offset_minutes  INTEGER,
+    FOREIGN KEY (template_id) REFERENCES templates(id) ON DELETE CASCADE,
+    FOREIGN KEY (parent_id) REFERENCES template_tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_template_tasks_template_id ON template_tasks (template_id);
+
+-- 模板任务的相对提醒：在截止日期前多少分钟提醒
+CREATE TABLE IF NOT EXISTS template_task_reminders (
+    template_task_id  INTEGER NOT NULL,
+    offset_minutes    INTEGER NOT NULL,
+    PRIMARY KEY (template_task_id, offset_minutes),
+    FOREIGN KEY (template_task_id) REFERENCES template_tasks(id) ON DELETE CASCADE
+);
+
+-- 模板任务的附件，字段含义与 attachments 表相同
+CREATE TABLE IF NOT EXISTS template_attachments (
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    template_task_id  INTEGER NOT NULL,
+    type              TEXT NOT NULL,
+    payload           TEXT NOT NULL,
+    title             TEXT,
+    file_size         INTEGER,
+    mime_type         TEXT,
+    original_name     TEXT,
+    FOREIGN KEY (template_task_id) REFERENCES template_tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_template_attachments_task_id ON template_attachments (template_task_id);
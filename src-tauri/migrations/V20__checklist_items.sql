@@ -0,0 +1,15 @@
+-- 版本 20: 任务内的检查项（轻量的步骤，没有自己的提醒和附件）
+
+CREATE TABLE IF NOT EXISTS checklist_items (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id     INTEGER NOT NULL,
+    text        TEXT NOT NULL,
+    is_checked  INTEGER NOT NULL DEFAULT 0,
+    -- 检查项在任务内的排列顺序，从 0 开始
+    position    INTEGER NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    updated_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_checklist_items_task_id ON checklist_items (task_id);
@@ -0,0 +1,4 @@
+-- 版本 21: 记录提醒被推迟到的时间
+
+-- 被推迟（snooze）且尚未再次发出的提醒，在截止日期变化时保留用户选择的时间
+ALTER TABLE reminders ADD COLUMN snoozed_until TEXT;
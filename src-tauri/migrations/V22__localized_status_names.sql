@@ -0,0 +1,9 @@
+-- 版本 22: 内置工作流状态改为存储本地化消息 ID，读取时按当前语言显示名称
+
+-- 版本 14 以英文名称写入了内置状态；复制到项目中的同名状态一并转换，
+-- 保证按名称在全局状态与项目状态之间对应任务时仍然一致。
+UPDATE workflow_statuses SET name = 'status-todo' WHERE name = 'Todo';
+UPDATE workflow_statuses SET name = 'status-in-progress' WHERE name = 'In Progress';
+UPDATE workflow_statuses SET name = 'status-waiting' WHERE name = 'Waiting';
+UPDATE workflow_statuses SET name = 'status-done' WHERE name = 'Done';
+UPDATE workflow_statuses SET name = 'status-cancelled' WHERE name = 'Cancelled';
@@ -0,0 +1,11 @@
+-- 版本 23: 任务是否完成改为由其状态的“视为已完成”标记决定，不再单独存储
+
+-- 版本 14 之前创建、但没有状态的任务按原来的完成标记补上默认的全局状态
+UPDATE tasks SET status_id = (
+    SELECT id FROM workflow_statuses
+    WHERE project_id IS NULL AND counts_as_done = tasks.is_completed
+    ORDER BY position ASC, id ASC LIMIT 1
+)
+WHERE status_id IS NULL;
+
+ALTER TABLE tasks DROP COLUMN is_completed;
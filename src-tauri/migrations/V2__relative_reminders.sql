@@ -0,0 +1,11 @@
+-- 版本 2: 支持相对于截止日期的提醒，以及项目级的默认提醒偏移量
+
+-- offset_minutes 为 NULL 表示绝对提醒；非 NULL 表示“截止日期前 N 分钟”的相对提醒
+ALTER TABLE reminders ADD COLUMN offset_minutes INTEGER;
+
+CREATE TABLE IF NOT EXISTS project_reminder_defaults (
+    project_id      INTEGER NOT NULL,
+    offset_minutes  INTEGER NOT NULL,
+    PRIMARY KEY (project_id, offset_minutes),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+-- 版本 3: 提醒收件箱与稍后提醒 (snooze)
+
+-- 提醒实际发出的时间
+ALTER TABLE reminders ADD COLUMN sent_at TEXT;
+-- 用户是否已在收件箱中处理（关闭/完成/打开）了该提醒
+ALTER TABLE reminders ADD COLUMN is_dismissed INTEGER NOT NULL DEFAULT 0;
+-- 该提醒被推迟的次数
+ALTER TABLE reminders ADD COLUMN snooze_count INTEGER NOT NULL DEFAULT 0;
+
+-- 历史上已发送的提醒视为已处理，避免升级后涌入收件箱
+UPDATE reminders SET is_dismissed = 1 WHERE is_sent = 1;
@@ -0,0 +1,4 @@
+-- 版本 4: 记录应用关闭期间错过的提醒
+
+-- 启动时已经过期的提醒不再逐条发送，而是标记为“错过”并汇总通知
+ALTER TABLE reminders ADD COLUMN is_missed INTEGER NOT NULL DEFAULT 0;
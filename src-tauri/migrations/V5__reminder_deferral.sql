@@ -0,0 +1,4 @@
+-- 版本 5: 免打扰时段内的提醒推迟
+
+-- 提醒因免打扰被推迟到的时间；在此之前不会发送，remind_at 保持原值不变
+ALTER TABLE reminders ADD COLUMN deferred_until TEXT;
@@ -0,0 +1,6 @@
+-- 版本 6: 附件保管库（将文件按内容哈希复制到数据目录中管理）
+
+-- 文件的元数据：保管库附件必定有值，其他类型的附件可能为空
+ALTER TABLE attachments ADD COLUMN file_size INTEGER;
+ALTER TABLE attachments ADD COLUMN mime_type TEXT;
+ALTER TABLE attachments ADD COLUMN original_name TEXT;
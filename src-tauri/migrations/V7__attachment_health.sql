@@ -0,0 +1,8 @@
+-- 版本 7: 本地路径附件的健康检查
+
+-- 路径在最近一次检查时是否存在，NULL 表示尚未检查过
+ALTER TABLE attachments ADD COLUMN path_exists INTEGER;
+-- 最近一次检查的时间 (UTC)
+ALTER TABLE attachments ADD COLUMN last_checked_at TEXT;
+-- 文件最近一次修改的时间 (UTC)
+ALTER TABLE attachments ADD COLUMN modified_at TEXT;
@@ -0,0 +1,3 @@
+-- 版本 8: 附件的显示标题（用户填写或从网页获取）
+
+ALTER TABLE attachments ADD COLUMN title TEXT;
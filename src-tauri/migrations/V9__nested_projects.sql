@@ -0,0 +1,22 @@
+-- 版本 9: 项目层级（区域 / 项目 / 子项目）
+
+-- 项目名称不再全局唯一，而是在同一个父项目下唯一。
+-- SQLite 无法删除列上的 UNIQUE 约束，因此需要重建 projects 表。
+CREATE TABLE projects_new (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    name            TEXT NOT NULL,
+    parent_id       INTEGER,
+    created_at      TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    updated_at      TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')),
+    FOREIGN KEY (parent_id) REFERENCES projects(id)
+);
+
+INSERT INTO projects_new (id, name, created_at, updated_at)
+SELECT id, name, created_at, updated_at FROM projects;
+
+DROP TABLE projects;
+ALTER TABLE projects_new RENAME TO projects;
+
+-- 顶层项目的 parent_id 为 NULL，而 NULL 在唯一索引中互不相等，因此用 0 代替
+CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_sibling_name ON projects (IFNULL(parent_id, 0), name);
+CREATE INDEX IF NOT EXISTS idx_projects_parent_id ON projects (parent_id);
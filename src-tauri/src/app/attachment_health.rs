@@ -0,0 +1,287 @@
+use crate::app::state::AppState;
+use crate::db::queries::attachment_queries;
+use crate::domain::attachment::{Attachment, AttachmentType};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use log::{debug, error, info, warn};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::{thread, time::Duration};
+use tauri::{Emitter, Manager};
+
+/// 后台健康检查的间隔：6 小时
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 启动后台附件健康检查服务：启动时检查一次，之后定期检查
+pub fn start(app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        info!("[AttachmentHealth] 附件健康检查服务已启动。");
+        loop {
+            match check_local_attachments(&app_handle) {
+                Ok(attachments) => {
+                    let broken_ids: Vec<i64> = attachments
+                        .iter()
+                        .filter(|a| a.exists == Some(false))
+                        .map(|a| a.id)
+                        .collect();
+                    if !broken_ids.is_empty() {
+                        warn!(
+                            "[AttachmentHealth] 发现 {} 个路径失效的附件。",
+                            broken_ids.len()
+                        );
+                    }
+                    // 通知前端刷新附件的状态
+                    app_handle
+                        .emit("attachments_checked", broken_ids)
+                        .unwrap_or_else(|e| {
+                            error!("[AttachmentHealth] 发送附件检查事件失败: {}", e);
+                        });
+                }
+                Err(e) => error!("[AttachmentHealth] 检查附件时出错: {}", e),
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+/// 检查所有本地路径附件，记录路径是否存在以及文件的元数据，返回检查后的附件列表。
+/// 访问文件系统期间不持有数据库锁，避免网络路径等较慢的检查阻塞其他操作。
+pub fn check_local_attachments(app_handle: &tauri::AppHandle) -> Result<Vec<Attachment>> {
+    let state = app_handle.state::<AppState>();
+    let attachments = {
+        let conn = state.db.lock().unwrap();
+        attachment_queries::get_attachments_by_type(&conn, AttachmentType::LocalPath)?
+    };
+    debug!(
+        "[AttachmentHealth] 开始检查 {} 个本地路径附件",
+        attachments.len()
+    );
+
+    let results: Vec<(&Attachment, Option<PathHealth>)> = attachments
+        .iter()
+        .filter_map(|a| inspect_path(Path::new(&a.payload)).map(|health| (a, health)))
+        .collect();
+
+    let conn = state.db.lock().unwrap();
+    for (attachment, health) in &results {
+        let (id, path) = (attachment.id, attachment.payload.as_str());
+        match health {
+            Some(h) => attachment_queries::update_attachment_health(
+                &conn,
+                id,
+                path,
+                true,
+                h.size,
+                h.mime_type.as_deref(),
+                h.modified_at,
+            )?,
+            None => attachment_queries::update_attachment_health(
+                &conn, id, path, false, None, None, None,
+            )?,
+        };
+    }
+    Ok(attachment_queries::get_attachments_by_type(
+        &conn,
+        AttachmentType::LocalPath,
+    )?)
+}
+
+/// 在指定的根目录下查找失效附件被移动后的新位置。
+/// 按文件名匹配，如果记录过文件大小则大小也必须一致；只有唯一匹配的附件才会被重新关联。
+/// 返回 附件ID -> 新路径 的映射。
+pub fn find_moved_files(root: &Path, broken: &[Attachment]) -> HashMap<i64, PathBuf> {
+    // 只为需要查找的文件名收集候选项
+    let mut candidates: HashMap<OsString, Vec<(PathBuf, Option<i64>)>> = broken
+        .iter()
+        .filter_map(|a| Path::new(&a.payload).file_name())
+        .map(|name| (name.to_os_string(), Vec::new()))
+        .collect();
+    if candidates.is_empty() {
+        return HashMap::new();
+    }
+
+    // 使用显式栈遍历目录，不跟随符号链接，避免循环
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                debug!("[AttachmentHealth] 无法读取目录 {}: {}", dir.display(), e);
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_symlink() {
+                continue;
+            }
+            let path = entry.path();
+            if let Some(found) = candidates.get_mut(&entry.file_name()) {
+                let size = if file_type.is_file() {
+                    entry.metadata().ok().map(|m| m.len() as i64)
+                } else {
+                    None
+                };
+                found.push((path.clone(), size));
+            }
+            if file_type.is_dir() {
+                stack.push(path);
+            }
+        }
+    }
+
+    broken
+        .iter()
+        .filter_map(|a| {
+            let name = Path::new(&a.payload).file_name()?;
+            let mut matches = candidates[name]
+                .iter()
+                .filter(|(_, size)| a.file_size.is_none() || *size == a.file_size);
+            match (matches.next(), matches.next()) {
+                (Some((path, _)), None) => Some((a.id, path.clone())),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// 一个存在的路径的元数据
+pub struct PathHealth {
+    /// 文件大小，目录为 `None`
+    pub size: Option<i64>,
+    pub mime_type: Option<String>,
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+/// 获取路径的状态。
+/// 返回 `Some(Some(..))` 表示存在，`Some(None)` 表示不存在，
+/// `None` 表示无法确定（例如没有访问权限），此时不更新记录。
+pub fn inspect_path(path: &Path) -> Option<Option<PathHealth>> {
+    match fs::metadata(path) {
+        Ok(meta) => {
+            let is_file = meta.is_file();
+            Some(Some(PathHealth {
+                size: is_file.then_some(meta.len() as i64),
+                mime_type: is_file.then(|| {
+                    mime_guess::from_path(path)
+                        .first_or_octet_stream()
+                        .essence_str()
+                        .to_string()
+                }),
+                modified_at: meta.modified().ok().map(DateTime::<Utc>::from),
+            }))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Some(None),
+        Err(e) => {
+            warn!("[AttachmentHealth] 无法检查路径 {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在系统临时目录下创建一个独立的测试目录，测试结束时删除
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+            let dir = std::env::temp_dir().join(format!(
+                "attachment-health-{}-{}-{}",
+                name,
+                std::process::id(),
+                nanos
+            ));
+            fs::create_dir_all(&dir).unwrap();
+            TestDir(dir)
+        }
+
+        fn write(&self, relative: &str, contents: &[u8]) -> PathBuf {
+            let path = self.0.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, contents).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn broken_attachment(id: i64, payload: &str, file_size: Option<i64>) -> Attachment {
+        Attachment {
+            id,
+            task_id: 1,
+            attachment_type: AttachmentType::LocalPath,
+            payload: payload.to_string(),
+            title: None,
+            file_size,
+            mime_type: None,
+            original_name: None,
+            exists: Some(false),
+            last_checked_at: None,
+            modified_at: None,
+        }
+    }
+
+    #[test]
+    fn inspect_path_reports_files_directories_and_missing_paths() {
+        let dir = TestDir::new("inspect");
+        let file = dir.write("notes.txt", b"hello");
+
+        let health = inspect_path(&file).unwrap().unwrap();
+        assert_eq!(health.size, Some(5));
+        assert_eq!(health.mime_type.as_deref(), Some("text/plain"));
+        assert!(health.modified_at.is_some());
+
+        let health = inspect_path(&dir.0).unwrap().unwrap();
+        assert_eq!(health.size, None);
+        assert_eq!(health.mime_type, None);
+
+        assert!(inspect_path(&dir.0.join("missing.txt")).unwrap().is_none());
+    }
+
+    #[test]
+    fn find_moved_files_relinks_unique_matches_in_subdirectories() {
+        let dir = TestDir::new("moved");
+        let report = dir.write("archive/2024/report.pdf", b"pdf");
+        dir.write("other/unrelated.pdf", b"pdf");
+        let broken = [broken_attachment(1, "/old/place/report.pdf", Some(3))];
+
+        let moved = find_moved_files(&dir.0, &broken);
+
+        assert_eq!(moved, HashMap::from([(1, report)]));
+    }
+
+    #[test]
+    fn find_moved_files_skips_ambiguous_names_unless_the_size_decides() {
+        let dir = TestDir::new("ambiguous");
+        dir.write("a/photo.jpg", b"small");
+        let large = dir.write("b/photo.jpg", b"much larger");
+        dir.write("a/data.csv", b"1,2");
+        dir.write("b/data.csv", b"3,4");
+        let broken = [
+            // 两个同名文件，按记录的大小只有一个匹配
+            broken_attachment(1, "/old/photo.jpg", Some(11)),
+            // 两个同名且大小相同的文件，无法确定
+            broken_attachment(2, "/old/data.csv", Some(3)),
+            // 没有记录大小时，同名文件不唯一也无法确定
+            broken_attachment(3, "/old/photo.jpg", None),
+            broken_attachment(4, "/old/absent.txt", None),
+        ];
+
+        let moved = find_moved_files(&dir.0, &broken);
+
+        assert_eq!(moved, HashMap::from([(1, large)]));
+    }
+}
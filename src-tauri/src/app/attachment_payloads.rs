@@ -0,0 +1,255 @@
+use crate::domain::attachment::{CodeRefPayload, EmailPayload};
+use crate::error::{Error, Result};
+use crate::t;
+use chrono::{DateTime, Utc};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// 校验笔记内容，返回用于存储的正文
+pub fn validate_note(content: &str) -> Result<String> {
+    if content.trim().is_empty() {
+        return Err(Error::Validation(t!("error-note-empty")));
+    }
+    Ok(content.to_string())
+}
+
+/// 校验代码引用：行号从 1 开始，且引用的行必须在文件范围内
+pub fn validate_code_ref(payload: &CodeRefPayload) -> Result<()> {
+    let last_line = payload.end_line.unwrap_or(payload.line);
+    if payload.line == 0 || last_line < payload.line {
+        return Err(Error::Validation(t!("error-code-ref-invalid-line")));
+    }
+    let path = Path::new(&payload.path);
+    if !path.is_file() {
+        return Err(Error::Validation(t!("error-code-ref-not-a-file")));
+    }
+    let line_count = BufReader::new(File::open(path)?).split(b'\n').count();
+    if last_line as usize > line_count {
+        return Err(Error::Validation(t!(
+            "error-code-ref-line-out-of-range",
+            line_count = line_count
+        )));
+    }
+    Ok(())
+}
+
+/// 解析 `.eml` 文件的邮件头，提取主题、发件人和日期。
+/// 只读取邮件头部分，不会把整封邮件（可能包含很大的附件）读入内存。
+pub fn parse_eml(path: &Path) -> Result<EmailPayload> {
+    let is_eml = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("eml"));
+    if !is_eml || !path.is_file() {
+        return Err(Error::Validation(t!("error-email-not-eml")));
+    }
+
+    let headers = read_headers(BufReader::new(File::open(path)?))?;
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| decode_encoded_words(value))
+    };
+
+    Ok(EmailPayload {
+        path: path.to_string_lossy().into_owned(),
+        subject: header("Subject"),
+        from: header("From"),
+        date: header("Date")
+            .and_then(|date| DateTime::parse_from_rfc2822(&date).ok())
+            .map(|date| date.with_timezone(&Utc)),
+    })
+}
+
+/// 读取邮件头（直到第一个空行为止），并展开折叠的多行邮件头
+fn read_headers(mut reader: impl BufRead) -> io::Result<Vec<(String, String)>> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        if line.starts_with([' ', '\t']) {
+            // 以空白开头的行是上一个邮件头的延续
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    Ok(headers)
+}
+
+/// 解码邮件头中的 RFC 2047 编码字（如 `=?UTF-8?B?...?=`）。
+/// 支持 UTF-8、US-ASCII 和 ISO-8859-1 字符集；其他字符集（如 GBK）或格式错误的编码字保持原文。
+fn decode_encoded_words(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut last_was_encoded = false;
+    while let Some(start) = rest.find("=?") {
+        let (before, candidate) = rest.split_at(start);
+        match decode_encoded_word(candidate) {
+            Some((decoded, consumed)) => {
+                // 相邻编码字之间的空白不属于内容
+                if !(last_was_encoded && before.trim().is_empty()) {
+                    out.push_str(before);
+                }
+                out.push_str(&decoded);
+                rest = &candidate[consumed..];
+                last_was_encoded = true;
+            }
+            None => {
+                out.push_str(before);
+                out.push_str("=?");
+                rest = &candidate[2..];
+                last_was_encoded = false;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 解码一个以 `=?` 开头的编码字，返回解码结果和消耗的字节数
+fn decode_encoded_word(s: &str) -> Option<(String, usize)> {
+    let mut parts = s[2..].splitn(3, '?');
+    let charset = parts.next()?;
+    let encoding = parts.next()?;
+    let remainder = parts.next()?;
+    let text = &remainder[..remainder.find("?=")?];
+    if charset.is_empty()
+        || [charset, text]
+            .iter()
+            .any(|p| p.contains(char::is_whitespace))
+    {
+        return None;
+    }
+    let bytes = match encoding {
+        "B" | "b" => decode_base64(text)?,
+        "Q" | "q" => decode_quoted_printable(text)?,
+        _ => return None,
+    };
+    let consumed = 2 + charset.len() + 1 + encoding.len() + 1 + text.len() + 2;
+    Some((decode_charset(charset, bytes)?, consumed))
+}
+
+/// 按编码字声明的字符集将字节转换为文本，不支持的字符集或无效的字节序列返回 `None`
+fn decode_charset(charset: &str, bytes: Vec<u8>) -> Option<String> {
+    // RFC 2231 允许在字符集后附加语言，如 `UTF-8*en`
+    let charset = charset.split('*').next()?.to_ascii_lowercase();
+    match charset.as_str() {
+        "utf-8" | "utf8" => String::from_utf8(bytes).ok(),
+        "us-ascii" | "ascii" if bytes.is_ascii() => String::from_utf8(bytes).ok(),
+        // ISO-8859-1 的每个字节恰好对应同值的 Unicode 码位
+        "iso-8859-1" | "latin1" | "latin-1" => Some(bytes.into_iter().map(char::from).collect()),
+        _ => None,
+    }
+}
+
+/// 解码标准字母表的 Base64
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for c in text.bytes().filter(|&c| c != b'=') {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        acc = ((acc << 6) | value as u32) & 0xFF_FFFF;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// 解码编码字中的 Q 编码：`_` 表示空格，`=XX` 表示一个十六进制字节
+fn decode_quoted_printable(text: &str) -> Option<Vec<u8>> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'_' => out.push(b' '),
+            b'=' => {
+                out.push(u8::from_str_radix(text.get(i + 1..i + 3)?, 16).ok()?);
+                i += 2;
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_base64_and_q_encoded_words() {
+        assert_eq!(decode_encoded_words("=?UTF-8?B?5L2g5aW9?="), "你好");
+        assert_eq!(
+            decode_encoded_words("=?utf-8?Q?Caf=C3=A9_au_lait?="),
+            "Café au lait"
+        );
+        assert_eq!(decode_encoded_words("=?ISO-8859-1?Q?Caf=E9?="), "Café");
+        assert_eq!(decode_encoded_words("=?UTF-8*en?B?aGk=?="), "hi");
+    }
+
+    #[test]
+    fn joins_adjacent_encoded_words_and_keeps_surrounding_text() {
+        assert_eq!(
+            decode_encoded_words("=?UTF-8?B?5L2g?= =?UTF-8?B?5aW9?="),
+            "你好"
+        );
+        assert_eq!(
+            decode_encoded_words("Re: =?UTF-8?Q?caf=C3=A9?= tomorrow"),
+            "Re: café tomorrow"
+        );
+    }
+
+    #[test]
+    fn keeps_malformed_or_unsupported_words_as_is() {
+        for raw in [
+            "=?UTF-8?B?not base64?=",
+            "=?UTF-8?X?abc?=",
+            "=?UTF-8?Q?bad=Z?=",
+            "=?UTF-8?B?5L2g",
+            "=?GBK?B?xOO6ww==?=",
+            "=?UTF-8?B?/w==?=",
+            "plain =? text",
+        ] {
+            assert_eq!(decode_encoded_words(raw), raw);
+        }
+    }
+
+    #[test]
+    fn unfolds_headers_until_the_blank_line() {
+        let eml = "Subject: first\r\n second\r\nFrom: a@example.com\r\n\r\nSubject: body\r\n";
+        let headers = read_headers(eml.as_bytes()).unwrap();
+        assert_eq!(
+            headers,
+            vec![
+                ("Subject".to_string(), "first second".to_string()),
+                ("From".to_string(), "a@example.com".to_string()),
+            ]
+        );
+    }
+}
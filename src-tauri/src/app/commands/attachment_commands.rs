@@ -1,22 +1,44 @@
 use crate::app::state::AppState;
+use crate::app::url_attachments::{self, TitleFetcher};
+use crate::app::{attachment_health, attachment_payloads, settings, vault};
 use crate::db::queries::attachment_queries;
-use crate::domain::attachment::Attachment;
-use crate::error::Result;
-use log::{debug, info};
+use crate::domain::attachment::{Attachment, AttachmentType, CodeRefPayload, EmailPayload};
+use crate::error::{Error, Result};
+use crate::t;
+use log::{debug, info, warn};
+use std::path::{Path, PathBuf};
 
 /// Tauri 指令：创建一个新的 URL 附件
+/// URL 会先被校验和规范化，同一任务下不允许重复的 URL。
+/// 未填写标题且 `fetch_title` 为 true 时，会尝试获取网页标题，获取失败不影响附件的创建。
 #[tauri::command]
 pub async fn create_url_attachment(
     task_id: i64,
     url: String,
+    title: Option<String>,
+    fetch_title: Option<bool>,
     state: tauri::State<'_, AppState>,
 ) -> Result<Attachment> {
     debug!(
-        "[Command] create_url_attachment, task_id: {}, url: {}",
-        task_id, url
+        "[Command] create_url_attachment, task_id: {}, url: {}, title: {:?}, fetch_title: {:?}",
+        task_id, url, title, fetch_title
     );
-    let conn = state.db.lock().unwrap();
-    let attachment = attachment_queries::create_url_attachment(&conn, task_id, &url)?;
+    // 只在需要获取标题时才创建 HTTP 客户端，创建失败不影响附件的创建
+    let fetcher = if fetch_title.unwrap_or(false) {
+        url_attachments::HttpTitleFetcher::new()
+            .inspect_err(|e| warn!("[Command] 创建网页标题获取器失败: {}", e))
+            .ok()
+    } else {
+        None
+    };
+    let attachment = url_attachments::create_url_attachment(
+        &state.db,
+        fetcher.as_ref().map(|f| f as &dyn TitleFetcher),
+        task_id,
+        &url,
+        title,
+    )
+    .await?;
     info!(
         "[Command] 成功为任务 {} 添加URL附件, ID: {}",
         task_id, attachment.id
@@ -24,6 +46,26 @@ pub async fn create_url_attachment(
     Ok(attachment)
 }
 
+/// Tauri 指令：修改附件的显示标题，传入空标题则清除
+#[tauri::command]
+pub async fn update_attachment_title(
+    id: i64,
+    title: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Attachment> {
+    debug!(
+        "[Command] update_attachment_title, id: {}, title: {:?}",
+        id, title
+    );
+    let title = title
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+    let conn = state.db.lock().unwrap();
+    attachment_queries::update_attachment_title(&conn, id, title.as_deref())?;
+    info!("[Command] 成功更新附件标题, ID: {}", id);
+    Ok(attachment_queries::get_attachment_by_id(&conn, id)?)
+}
+
 /// Tauri 指令：获取指定任务的所有附件
 #[tauri::command]
 pub async fn get_attachments_for_task(
@@ -37,26 +79,59 @@ pub async fn get_attachments_for_task(
 }
 
 /// Tauri 指令：删除一个附件
+/// 如果是保管库附件，且对应的文件不再被其他附件引用，会一并删除该文件
 #[tauri::command]
-pub async fn delete_attachment(id: i64, state: tauri::State<'_, AppState>) -> Result<()> {
+pub async fn delete_attachment(
+    id: i64,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
     debug!("[Command] delete_attachment, id: {}", id);
     let conn = state.db.lock().unwrap();
+    let attachment = attachment_queries::get_attachment_by_id(&conn, id)?;
     attachment_queries::delete_attachment(&conn, id)?;
+    if attachment.attachment_type == AttachmentType::VaultFile {
+        let vault_dir = vault::vault_dir(&settings::data_dir(&app_handle)?);
+        vault::remove_if_unreferenced(&conn, &vault_dir, &attachment.payload)?;
+    }
     info!("[Command] 成功删除附件, ID: {}", id);
     Ok(())
 }
 
 /// Tauri 指令，创建一个新的本地路径附件
+/// `copy_to_vault` 为 true 时，文件会被复制进附件保管库，之后不再依赖原始路径
 #[tauri::command]
 pub async fn create_local_path_attachment(
     task_id: i64,
     path: String,
+    copy_to_vault: Option<bool>,
+    app_handle: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
 ) -> Result<Attachment> {
     debug!(
-        "[Command] create_local_path_attachment, task_id: {}, path: {}",
-        task_id, path
+        "[Command] create_local_path_attachment, task_id: {}, path: {}, copy_to_vault: {:?}",
+        task_id, path, copy_to_vault
     );
+    if copy_to_vault.unwrap_or(false) {
+        let vault_dir = vault::vault_dir(&settings::data_dir(&app_handle)?);
+        // 在数据库锁内存入文件并创建附件，避免垃圾回收在两者之间删除这个尚未被引用的文件
+        let conn = state.db.lock().unwrap();
+        let stored = vault::store_file(&vault_dir, Path::new(&path))?;
+        let attachment = attachment_queries::create_vault_attachment(
+            &conn,
+            task_id,
+            &stored.hash,
+            stored.size,
+            &stored.mime_type,
+            &stored.original_name,
+        )?;
+        info!(
+            "[Command] 成功为任务 {} 添加保管库附件, ID: {}",
+            task_id, attachment.id
+        );
+        return Ok(attachment);
+    }
+
     let conn = state.db.lock().unwrap();
     let attachment = attachment_queries::create_local_path_attachment(&conn, task_id, &path)?;
     info!(
@@ -65,3 +140,186 @@ pub async fn create_local_path_attachment(
     );
     Ok(attachment)
 }
+
+/// Tauri 指令：为指定任务创建一个 Markdown 笔记附件
+#[tauri::command]
+pub async fn create_note_attachment(
+    task_id: i64,
+    content: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Attachment> {
+    debug!("[Command] create_note_attachment, task_id: {}", task_id);
+    let content = attachment_payloads::validate_note(&content)?;
+    let conn = state.db.lock().unwrap();
+    let attachment =
+        attachment_queries::create_attachment(&conn, task_id, AttachmentType::Note, &content)?;
+    info!(
+        "[Command] 成功为任务 {} 添加笔记附件, ID: {}",
+        task_id, attachment.id
+    );
+    Ok(attachment)
+}
+
+/// Tauri 指令：更新一个笔记附件的内容
+#[tauri::command]
+pub async fn update_note_attachment(
+    id: i64,
+    content: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Attachment> {
+    debug!("[Command] update_note_attachment, id: {}", id);
+    let content = attachment_payloads::validate_note(&content)?;
+    let conn = state.db.lock().unwrap();
+    let attachment = attachment_queries::get_attachment_by_id(&conn, id)?;
+    if attachment.attachment_type != AttachmentType::Note {
+        return Err(Error::Validation(t!("error-attachment-not-a-note")));
+    }
+    attachment_queries::update_attachment_payload(&conn, id, &content)?;
+    info!("[Command] 成功更新笔记附件, ID: {}", id);
+    Ok(attachment_queries::get_attachment_by_id(&conn, id)?)
+}
+
+/// Tauri 指令：为指定任务添加一个 `.eml` 邮件附件，并解析邮件的主题、发件人和日期
+#[tauri::command]
+pub async fn create_email_attachment(
+    task_id: i64,
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Attachment> {
+    debug!(
+        "[Command] create_email_attachment, task_id: {}, path: {}",
+        task_id, path
+    );
+    let email = attachment_payloads::parse_eml(Path::new(&path))?;
+    let payload = serde_json::to_string(&email)?;
+    let conn = state.db.lock().unwrap();
+    let attachment =
+        attachment_queries::create_attachment(&conn, task_id, AttachmentType::Email, &payload)?;
+    info!(
+        "[Command] 成功为任务 {} 添加邮件附件, ID: {}",
+        task_id, attachment.id
+    );
+    Ok(attachment)
+}
+
+/// Tauri 指令：为指定任务添加一个代码位置引用（文件:行）
+#[tauri::command]
+pub async fn create_code_ref_attachment(
+    task_id: i64,
+    code_ref: CodeRefPayload,
+    state: tauri::State<'_, AppState>,
+) -> Result<Attachment> {
+    debug!(
+        "[Command] create_code_ref_attachment, task_id: {}, code_ref: {:?}",
+        task_id, code_ref
+    );
+    attachment_payloads::validate_code_ref(&code_ref)?;
+    let payload = serde_json::to_string(&code_ref)?;
+    let conn = state.db.lock().unwrap();
+    let attachment =
+        attachment_queries::create_attachment(&conn, task_id, AttachmentType::CodeRef, &payload)?;
+    info!(
+        "[Command] 成功为任务 {} 添加代码引用附件, ID: {}",
+        task_id, attachment.id
+    );
+    Ok(attachment)
+}
+
+/// Tauri 指令：获取附件对应文件在磁盘上的实际路径，供前端打开
+/// 保管库附件会被解析为保管库中的文件路径
+#[tauri::command]
+pub async fn get_attachment_file_path(
+    id: i64,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String> {
+    debug!("[Command] get_attachment_file_path, id: {}", id);
+    let attachment = {
+        let conn = state.db.lock().unwrap();
+        attachment_queries::get_attachment_by_id(&conn, id)?
+    };
+    let path = match attachment.attachment_type {
+        AttachmentType::VaultFile => {
+            let vault_dir = vault::vault_dir(&settings::data_dir(&app_handle)?);
+            vault::blob_path(&vault_dir, &attachment.payload)
+                .to_string_lossy()
+                .into_owned()
+        }
+        AttachmentType::Email => serde_json::from_str::<EmailPayload>(&attachment.payload)?.path,
+        AttachmentType::CodeRef => {
+            serde_json::from_str::<CodeRefPayload>(&attachment.payload)?.path
+        }
+        AttachmentType::Note => {
+            return Err(Error::Validation(t!("error-attachment-has-no-file")));
+        }
+        AttachmentType::Url | AttachmentType::LocalPath => attachment.payload,
+    };
+    Ok(path)
+}
+
+/// Tauri 指令：清理附件保管库中不再被任何附件引用的文件，返回删除的文件数量
+#[tauri::command]
+pub async fn collect_vault_garbage(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<usize> {
+    debug!("[Command] collect_vault_garbage");
+    let vault_dir = vault::vault_dir(&settings::data_dir(&app_handle)?);
+    let conn = state.db.lock().unwrap();
+    vault::collect_garbage(&conn, &vault_dir)
+}
+
+/// Tauri 指令：立即检查所有本地路径附件是否仍然存在，返回检查后的本地路径附件列表
+#[tauri::command]
+pub async fn check_attachments(app_handle: tauri::AppHandle) -> Result<Vec<Attachment>> {
+    debug!("[Command] check_attachments");
+    let attachments = attachment_health::check_local_attachments(&app_handle)?;
+    info!("[Command] 成功检查 {} 个本地路径附件", attachments.len());
+    Ok(attachments)
+}
+
+/// Tauri 指令：在用户选择的根目录下查找已失效的本地路径附件，
+/// 将按文件名和大小唯一匹配到的附件重新关联到新路径，返回被重新关联的附件
+#[tauri::command]
+pub async fn relink_attachments(
+    root_dir: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Attachment>> {
+    debug!("[Command] relink_attachments, root_dir: {}", root_dir);
+    let broken: Vec<Attachment> = {
+        let conn = state.db.lock().unwrap();
+        attachment_queries::get_attachments_by_type(&conn, AttachmentType::LocalPath)?
+            .into_iter()
+            .filter(|a| a.exists == Some(false))
+            .collect()
+    };
+    // 遍历目录可能较慢，期间不持有数据库锁
+    let moved = attachment_health::find_moved_files(&PathBuf::from(&root_dir), &broken);
+
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let mut relinked = Vec::new();
+    for (id, new_path) in moved {
+        let new_path_str = new_path.to_string_lossy();
+        attachment_queries::relink_attachment(&tx, id, &new_path_str)?;
+        if let Some(Some(health)) = attachment_health::inspect_path(&new_path) {
+            attachment_queries::update_attachment_health(
+                &tx,
+                id,
+                &new_path_str,
+                true,
+                health.size,
+                health.mime_type.as_deref(),
+                health.modified_at,
+            )?;
+        }
+        relinked.push(attachment_queries::get_attachment_by_id(&tx, id)?);
+    }
+    tx.commit()?;
+    info!(
+        "[Command] 成功重新关联 {} / {} 个失效附件",
+        relinked.len(),
+        broken.len()
+    );
+    Ok(relinked)
+}
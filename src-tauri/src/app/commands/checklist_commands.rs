@@ -0,0 +1,201 @@
+use crate::app::commands::ensure_same_ids;
+use crate::app::settings;
+use crate::app::state::AppState;
+use crate::app::task_completion::{self, TASK_COMPLETION_SETTINGS_KEY};
+use crate::db::queries::{checklist_queries, task_queries};
+use crate::domain::checklist::ChecklistItem;
+use crate::domain::settings::TaskCompletionSettings;
+use crate::domain::task::Task;
+use crate::error::{Error, Result};
+use crate::t;
+use log::{debug, info};
+use rusqlite::Connection;
+use tauri::Emitter;
+
+/// Tauri 指令：在任务的检查项列表末尾添加一个新检查项
+#[tauri::command]
+pub async fn create_checklist_item(
+    task_id: i64,
+    text: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<ChecklistItem> {
+    debug!(
+        "[Command] create_checklist_item, task_id: {}, text: '{}'",
+        task_id, text
+    );
+    let text = validate_text(&text)?;
+    let conn = state.db.lock().unwrap();
+    let item = checklist_queries::create_checklist_item(&conn, task_id, text)?;
+    info!(
+        "[Command] 成功为任务 {} 添加检查项, ID: {}",
+        task_id, item.id
+    );
+    Ok(item)
+}
+
+/// Tauri 指令：获取任务的所有检查项
+#[tauri::command]
+pub async fn get_checklist_items(
+    task_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ChecklistItem>> {
+    debug!("[Command] get_checklist_items, task_id: {}", task_id);
+    let conn = state.db.lock().unwrap();
+    Ok(checklist_queries::get_checklist_items(&conn, task_id)?)
+}
+
+/// Tauri 指令：修改检查项的文本
+#[tauri::command]
+pub async fn update_checklist_item(
+    id: i64,
+    text: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<ChecklistItem> {
+    debug!(
+        "[Command] update_checklist_item, id: {}, text: '{}'",
+        id, text
+    );
+    let text = validate_text(&text)?;
+    let conn = state.db.lock().unwrap();
+    let item = checklist_queries::update_checklist_item_text(&conn, id, text)?;
+    info!("[Command] 成功修改检查项, ID: {}", id);
+    Ok(item)
+}
+
+/// Tauri 指令：勾选或取消勾选一个检查项
+#[tauri::command]
+pub async fn set_checklist_item_checked(
+    id: i64,
+    is_checked: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<ChecklistItem> {
+    debug!(
+        "[Command] set_checklist_item_checked, id: {}, is_checked: {}",
+        id, is_checked
+    );
+    let conn = state.db.lock().unwrap();
+    let item = checklist_queries::set_checklist_item_checked(&conn, id, is_checked)?;
+    info!("[Command] 成功更新检查项的勾选状态, ID: {}", id);
+    Ok(item)
+}
+
+/// Tauri 指令：按给定的 ID 顺序重新排列任务内的检查项。
+/// `item_ids` 必须恰好包含任务的每个检查项一次。
+#[tauri::command]
+pub async fn reorder_checklist_items(
+    task_id: i64,
+    item_ids: Vec<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ChecklistItem>> {
+    debug!(
+        "[Command] reorder_checklist_items, task_id: {}, item_ids: {:?}",
+        task_id, item_ids
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let current = checklist_queries::get_checklist_items(&tx, task_id)?;
+    ensure_same_ids(current.iter().map(|item| item.id).collect(), &item_ids)?;
+    checklist_queries::reorder_checklist_items(&tx, task_id, &item_ids)?;
+    let items = checklist_queries::get_checklist_items(&tx, task_id)?;
+    tx.commit()?;
+    info!("[Command] 成功调整任务 {} 的检查项顺序", task_id);
+    Ok(items)
+}
+
+/// Tauri 指令：删除一个检查项
+#[tauri::command]
+pub async fn delete_checklist_item(id: i64, state: tauri::State<'_, AppState>) -> Result<()> {
+    debug!("[Command] delete_checklist_item, id: {}", id);
+    let conn = state.db.lock().unwrap();
+    checklist_queries::delete_checklist_item(&conn, id)?;
+    info!("[Command] 成功删除检查项, ID: {}", id);
+    Ok(())
+}
+
+/// Tauri 指令：将检查项转换为所属任务的子任务，返回新的子任务。
+/// 已勾选的检查项会转换为已完成的子任务，联动规则与 `update_task_status` 相同；转换后检查项被删除。
+#[tauri::command]
+pub async fn promote_checklist_item(
+    id: i64,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Task> {
+    debug!("[Command] promote_checklist_item, id: {}", id);
+    let completion_settings: TaskCompletionSettings =
+        settings::load(&app_handle, TASK_COMPLETION_SETTINGS_KEY)?;
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let (subtask, unblocked_task_ids) = promote_item(&tx, id, &completion_settings)?;
+    tx.commit()?;
+
+    if !unblocked_task_ids.is_empty() {
+        app_handle.emit("tasks_unblocked", &unblocked_task_ids)?;
+    }
+    info!(
+        "[Command] 成功将检查项 {} 转换为任务 {:?} 的子任务, 新任务ID: {}",
+        id, subtask.parent_id, subtask.id
+    );
+    Ok(subtask)
+}
+
+/// 内部辅助函数：将检查项转换为子任务并删除检查项，返回新的子任务和因此解除阻塞的任务
+fn promote_item(
+    conn: &Connection,
+    id: i64,
+    completion_settings: &TaskCompletionSettings,
+) -> Result<(Task, Vec<i64>)> {
+    let item = checklist_queries::get_checklist_item_by_id(conn, id)?;
+    let parent = task_queries::get_task_by_id(conn, item.task_id)?;
+    let subtask = task_queries::create_task(conn, &item.text, parent.project_id, Some(parent.id))?;
+    let mut unblocked_task_ids = Vec::new();
+    if item.is_checked {
+        let change =
+            task_completion::set_task_completion(conn, subtask.id, true, completion_settings)?;
+        unblocked_task_ids = change.unblocked_task_ids;
+    }
+    checklist_queries::delete_checklist_item(conn, id)?;
+    let subtask = task_queries::get_task_by_id(conn, subtask.id)?;
+    Ok((subtask, unblocked_task_ids))
+}
+
+/// 内部辅助函数：检查项的文本去掉首尾空白后不能为空
+fn validate_text(text: &str) -> Result<&str> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(Error::Validation(t!("error-checklist-item-empty")));
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::open_test_db;
+
+    #[test]
+    fn promoting_a_checked_item_completes_the_subtask_through_task_completion() {
+        let conn = open_test_db();
+        let parent = task_queries::create_task(&conn, "parent", None, None).unwrap();
+        let item = checklist_queries::create_checklist_item(&conn, parent.id, "step").unwrap();
+        checklist_queries::set_checklist_item_checked(&conn, item.id, true).unwrap();
+        let settings = TaskCompletionSettings {
+            auto_complete_parent: true,
+            ..Default::default()
+        };
+
+        let (subtask, _) = promote_item(&conn, item.id, &settings).unwrap();
+
+        assert_eq!(subtask.parent_id, Some(parent.id));
+        assert!(subtask.is_completed);
+        assert!(subtask.completed_at.is_some());
+        // 联动规则生效：唯一的子任务完成后，父任务也自动完成
+        assert!(
+            task_queries::get_task_by_id(&conn, parent.id)
+                .unwrap()
+                .is_completed
+        );
+        assert!(checklist_queries::get_checklist_items(&conn, parent.id)
+            .unwrap()
+            .is_empty());
+    }
+}
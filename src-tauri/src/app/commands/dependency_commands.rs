@@ -0,0 +1,72 @@
+use crate::app::state::AppState;
+use crate::db::queries::{dependency_queries, task_queries};
+use crate::domain::task::Task;
+use crate::error::{Error, Result};
+use crate::t;
+use log::{debug, info};
+
+/// Tauri 指令：添加一条依赖，`task_id` 需要在 `depends_on_task_id` 完成后才能开始。
+/// 返回更新后的任务（包含最新的阻塞状态）。
+#[tauri::command]
+pub async fn add_task_dependency(
+    task_id: i64,
+    depends_on_task_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Task> {
+    debug!(
+        "[Command] add_task_dependency, task_id: {}, depends_on_task_id: {}",
+        task_id, depends_on_task_id
+    );
+    let conn = state.db.lock().unwrap();
+    if dependency_queries::would_create_cycle(&conn, task_id, depends_on_task_id)? {
+        return Err(Error::Validation(t!("error-dependency-cycle")));
+    }
+    dependency_queries::add_dependency(&conn, task_id, depends_on_task_id)?;
+    info!(
+        "[Command] 成功添加依赖, 任务 {} 依赖于任务 {}",
+        task_id, depends_on_task_id
+    );
+    Ok(task_queries::get_task_by_id(&conn, task_id)?)
+}
+
+/// Tauri 指令：删除一条依赖，返回更新后的任务
+#[tauri::command]
+pub async fn remove_task_dependency(
+    task_id: i64,
+    depends_on_task_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Task> {
+    debug!(
+        "[Command] remove_task_dependency, task_id: {}, depends_on_task_id: {}",
+        task_id, depends_on_task_id
+    );
+    let conn = state.db.lock().unwrap();
+    dependency_queries::remove_dependency(&conn, task_id, depends_on_task_id)?;
+    info!(
+        "[Command] 成功删除依赖, 任务 {} 不再依赖于任务 {}",
+        task_id, depends_on_task_id
+    );
+    Ok(task_queries::get_task_by_id(&conn, task_id)?)
+}
+
+/// Tauri 指令：获取指定任务依赖的所有任务（包括已完成的）
+#[tauri::command]
+pub async fn get_task_dependencies(
+    task_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Task>> {
+    debug!("[Command] get_task_dependencies, task_id: {}", task_id);
+    let conn = state.db.lock().unwrap();
+    Ok(dependency_queries::get_dependencies(&conn, task_id)?)
+}
+
+/// Tauri 指令：获取依赖于指定任务的所有任务
+#[tauri::command]
+pub async fn get_task_dependents(
+    task_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Task>> {
+    debug!("[Command] get_task_dependents, task_id: {}", task_id);
+    let conn = state.db.lock().unwrap();
+    Ok(dependency_queries::get_dependents(&conn, task_id)?)
+}
@@ -1,5 +1,44 @@
 pub mod attachment_commands;
+pub mod checklist_commands;
+pub mod dependency_commands;
+pub mod pomodoro_commands;
 pub mod project_commands;
 pub mod reminder_commands;
+pub mod section_commands;
 pub mod settings_commands;
+pub mod tag_commands;
 pub mod task_commands;
+pub mod template_commands;
+pub mod time_entry_commands;
+pub mod workflow_status_commands;
+
+use crate::error::{Error, Result};
+use crate::t;
+
+/// 确保调整顺序时给出的 `given` 恰好包含 `expected` 中的每个 ID 一次，
+/// 不能遗漏、重复，也不能包含其他集合中的 ID
+pub(crate) fn ensure_same_ids(mut expected: Vec<i64>, given: &[i64]) -> Result<()> {
+    let mut given = given.to_vec();
+    expected.sort_unstable();
+    given.sort_unstable();
+    if expected != given {
+        return Err(Error::Validation(t!("error-reorder-ids-mismatch")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_same_ids_requires_each_id_exactly_once() {
+        assert!(ensure_same_ids(vec![1, 2, 3], &[3, 1, 2]).is_ok());
+        assert!(ensure_same_ids(vec![], &[]).is_ok());
+        // 遗漏、重复或包含其他集合中的 ID
+        assert!(ensure_same_ids(vec![1, 2, 3], &[1, 2]).is_err());
+        assert!(ensure_same_ids(vec![1, 2, 3], &[1, 2, 2, 3]).is_err());
+        assert!(ensure_same_ids(vec![1, 2], &[1, 2, 9]).is_err());
+        assert!(ensure_same_ids(vec![1, 2], &[1, 9]).is_err());
+    }
+}
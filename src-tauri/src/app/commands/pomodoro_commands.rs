@@ -0,0 +1,128 @@
+use crate::app::pomodoro::{self, POMODORO_SETTINGS_KEY};
+use crate::app::settings;
+use crate::app::state::AppState;
+use crate::db::queries::{pomodoro_queries, task_queries};
+use crate::domain::pomodoro::{Pomodoro, PomodoroState};
+use crate::domain::settings::PomodoroSettings;
+use crate::error::{Error, Result};
+use crate::t;
+use chrono::Utc;
+use log::{debug, info};
+use tauri::Emitter;
+
+/// Tauri 指令：开始一轮番茄钟，`task_id` 为空时不关联任务。
+/// 同一时间只能有一个番茄钟在进行。
+#[tauri::command]
+pub async fn start_pomodoro(
+    task_id: Option<i64>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<PomodoroState> {
+    debug!("[Command] start_pomodoro, task_id: {:?}", task_id);
+    if let Some(task_id) = task_id {
+        // 确认任务存在
+        let conn = state.db.lock().unwrap();
+        task_queries::get_task_by_id(&conn, task_id)?;
+    }
+    let pomodoro_settings: PomodoroSettings = settings::load(&app_handle, POMODORO_SETTINGS_KEY)?;
+
+    let session = {
+        let mut guard = state.pomodoro.lock().unwrap();
+        if guard.is_some() {
+            return Err(Error::Validation(t!("error-pomodoro-already-running")));
+        }
+        let session = pomodoro::start_session(task_id, &pomodoro_settings, Utc::now());
+        *guard = Some(session.clone());
+        session
+    };
+    info!(
+        "[Command] 番茄钟已开始, 任务: {:?}, 将于 {} 结束",
+        task_id, session.phase_ends_at
+    );
+    app_handle.emit("pomodoro_phase_changed", Some(&session))?;
+    Ok(session)
+}
+
+/// Tauri 指令：获取正在进行的番茄钟（如果有的话）
+#[tauri::command]
+pub async fn get_pomodoro_state(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<PomodoroState>> {
+    let mut guard = state.pomodoro.lock().unwrap();
+    if let Some(session) = guard.as_mut() {
+        pomodoro::tick(session, Utc::now());
+    }
+    Ok(guard.clone())
+}
+
+/// Tauri 指令：暂停正在进行的番茄钟
+#[tauri::command]
+pub async fn pause_pomodoro(state: tauri::State<'_, AppState>) -> Result<PomodoroState> {
+    debug!("[Command] pause_pomodoro");
+    let mut guard = state.pomodoro.lock().unwrap();
+    let session = guard
+        .as_mut()
+        .ok_or_else(|| Error::Validation(t!("error-pomodoro-not-running")))?;
+    pomodoro::pause(session, Utc::now());
+    Ok(session.clone())
+}
+
+/// Tauri 指令：继续已暂停的番茄钟
+#[tauri::command]
+pub async fn resume_pomodoro(state: tauri::State<'_, AppState>) -> Result<PomodoroState> {
+    debug!("[Command] resume_pomodoro");
+    let mut guard = state.pomodoro.lock().unwrap();
+    let session = guard
+        .as_mut()
+        .ok_or_else(|| Error::Validation(t!("error-pomodoro-not-running")))?;
+    pomodoro::resume(session, Utc::now());
+    Ok(session.clone())
+}
+
+/// Tauri 指令：跳过当前阶段，直接进入下一阶段。
+/// 被跳过的专注时段不会被记录，也不计入长休息的间隔。
+#[tauri::command]
+pub async fn skip_pomodoro_phase(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<PomodoroState>> {
+    debug!("[Command] skip_pomodoro_phase");
+    let pomodoro_settings: PomodoroSettings = settings::load(&app_handle, POMODORO_SETTINGS_KEY)?;
+    let now = Utc::now();
+    let (finished, next) = {
+        let mut guard = state.pomodoro.lock().unwrap();
+        let finished = guard
+            .clone()
+            .ok_or_else(|| Error::Validation(t!("error-pomodoro-not-running")))?;
+        *guard = pomodoro::next_phase(&finished, &pomodoro_settings, now, false);
+        (finished, guard.clone())
+    };
+    pomodoro::on_phase_finished(&app_handle, &finished, next.as_ref(), false, now);
+    Ok(next)
+}
+
+/// Tauri 指令：停止番茄钟。未完成的专注时段不会被记录。
+#[tauri::command]
+pub async fn stop_pomodoro(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    debug!("[Command] stop_pomodoro");
+    let stopped = state.pomodoro.lock().unwrap().take();
+    if stopped.is_some() {
+        info!("[Command] 番茄钟已停止");
+        app_handle.emit("pomodoro_phase_changed", None::<PomodoroState>)?;
+    }
+    Ok(())
+}
+
+/// Tauri 指令：获取任务的所有专注记录，最近的记录在前
+#[tauri::command]
+pub async fn get_pomodoros_for_task(
+    task_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Pomodoro>> {
+    debug!("[Command] get_pomodoros_for_task, task_id: {}", task_id);
+    let conn = state.db.lock().unwrap();
+    Ok(pomodoro_queries::get_pomodoros_for_task(&conn, task_id)?)
+}
@@ -1,17 +1,33 @@
+use crate::app::commands::time_entry_commands;
 use crate::app::state::AppState;
-use crate::db::queries::project_queries;
-use crate::domain::project::Project;
-use crate::error::Result;
+use crate::app::{project_stats, settings, vault};
+use crate::db::queries::{project_queries, time_entry_queries};
+use crate::domain::project::{
+    Project, ProjectDeletionStrategy, ProjectDeletionSummary, ProjectStats, ProjectStatus,
+};
+use crate::error::{Error, Result};
+use crate::t;
+use chrono::Utc;
 use log::{debug, info};
+use rusqlite::Connection;
 
 /// Tauri 指令：创建一个新项目
+/// `parent_id` 为空时创建顶层项目（区域），否则创建为指定项目的子项目
 #[tauri::command]
-pub async fn create_project(name: String, state: tauri::State<'_, AppState>) -> Result<Project> {
-    debug!("[Command] create_project, name: '{}'", name);
+pub async fn create_project(
+    name: String,
+    parent_id: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Project> {
+    debug!(
+        "[Command] create_project, name: '{}', parent_id: {:?}",
+        name, parent_id
+    );
     // 从共享状态中获取数据库连接
     let conn = state.db.lock().unwrap();
+    ensure_unique_sibling_name(&conn, parent_id, &name, None)?;
     // 调用底层的数据库查询函数
-    let new_project = project_queries::create_project(&conn, &name)?;
+    let new_project = project_queries::create_project(&conn, &name, parent_id)?;
     info!(
         "[Command] 成功创建新项目, ID: {}, 名称: '{}'",
         new_project.id, new_project.name
@@ -21,11 +37,26 @@ pub async fn create_project(name: String, state: tauri::State<'_, AppState>) ->
 }
 
 /// Tauri 指令：获取所有项目
+/// 默认不包含已归档的项目，`include_archived` 为 true 时一并返回；
+/// `include_stats` 为 true 时为每个项目附带统计数据
 #[tauri::command]
-pub async fn get_all_projects(state: tauri::State<'_, AppState>) -> Result<Vec<Project>> {
-    debug!("[Command] get_all_projects");
+pub async fn get_all_projects(
+    include_archived: Option<bool>,
+    include_stats: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Project>> {
+    debug!(
+        "[Command] get_all_projects, include_archived: {:?}, include_stats: {:?}",
+        include_archived, include_stats
+    );
     let conn = state.db.lock().unwrap();
-    let projects = project_queries::get_all_projects(&conn)?;
+    let mut projects = project_queries::get_all_projects(&conn, include_archived.unwrap_or(false))?;
+    if include_stats.unwrap_or(false) {
+        let now = Utc::now();
+        for project in &mut projects {
+            project.stats = Some(project_stats::get_project_stats(&conn, project.id, now)?);
+        }
+    }
     Ok(projects)
 }
 
@@ -38,6 +69,8 @@ pub async fn update_project(
 ) -> Result<Project> {
     debug!("[Command] update_project, id: {}, name: '{}'", id, name);
     let conn = state.db.lock().unwrap();
+    let project = project_queries::get_project_by_id(&conn, id)?;
+    ensure_unique_sibling_name(&conn, project.parent_id, &name, Some(id))?;
     let updated_project = project_queries::update_project(&conn, id, &name)?;
     info!(
         "[Command] 成功更新项目, ID: {}, 新名称: '{}'",
@@ -46,12 +79,296 @@ pub async fn update_project(
     Ok(updated_project)
 }
 
-/// Tauri 指令：删除一个项目
+/// Tauri 指令：获取项目的统计数据（包含各级子任务）
+#[tauri::command]
+pub async fn get_project_stats(
+    project_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<ProjectStats> {
+    debug!("[Command] get_project_stats, project_id: {}", project_id);
+    let conn = state.db.lock().unwrap();
+    // 确保项目存在
+    project_queries::get_project_by_id(&conn, project_id)?;
+    let stats = project_stats::get_project_stats(&conn, project_id, Utc::now())?;
+    Ok(stats)
+}
+
+/// Tauri 指令：更新项目的颜色、图标和描述，传入空值则清除对应的字段
+#[tauri::command]
+pub async fn update_project_details(
+    id: i64,
+    color: Option<String>,
+    icon: Option<String>,
+    description: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Project> {
+    debug!(
+        "[Command] update_project_details, id: {}, color: {:?}, icon: {:?}",
+        id, color, icon
+    );
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let color = non_empty(color);
+    let is_valid_color = |c: &String| {
+        c.len() == 7 && c.starts_with('#') && c[1..].chars().all(|ch| ch.is_ascii_hexdigit())
+    };
+    if color.as_ref().is_some_and(|c| !is_valid_color(c)) {
+        return Err(Error::Validation(t!("error-invalid-color")));
+    }
+
+    let conn = state.db.lock().unwrap();
+    let project = project_queries::update_project_details(
+        &conn,
+        id,
+        color.as_deref(),
+        non_empty(icon).as_deref(),
+        non_empty(description).as_deref(),
+    )?;
+    info!("[Command] 成功更新项目详情, ID: {}", id);
+    Ok(project)
+}
+
+/// Tauri 指令：更新项目的状态
+#[tauri::command]
+pub async fn set_project_status(
+    id: i64,
+    status: ProjectStatus,
+    state: tauri::State<'_, AppState>,
+) -> Result<Project> {
+    debug!(
+        "[Command] set_project_status, id: {}, status: {:?}",
+        id, status
+    );
+    let conn = state.db.lock().unwrap();
+    let project = project_queries::update_project_status(&conn, id, status)?;
+    info!("[Command] 成功更新项目状态, ID: {}, 状态: {:?}", id, status);
+    Ok(project)
+}
+
+/// Tauri 指令：归档一个项目
+/// 归档后，该项目及其子项目和任务不再出现在默认的列表中，但数据会被保留
+#[tauri::command]
+pub async fn archive_project(id: i64, state: tauri::State<'_, AppState>) -> Result<Project> {
+    debug!("[Command] archive_project, id: {}", id);
+    let conn = state.db.lock().unwrap();
+    let project = project_queries::update_project_status(&conn, id, ProjectStatus::Archived)?;
+    info!("[Command] 成功归档项目, ID: {}", id);
+    Ok(project)
+}
+
+/// Tauri 指令：将项目移动到新的父项目下，`new_parent_id` 为空时移动到顶层
+/// 不允许移动到项目自身或其后代项目下
+#[tauri::command]
+pub async fn move_project(
+    id: i64,
+    new_parent_id: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Project> {
+    debug!(
+        "[Command] move_project, id: {}, new_parent_id: {:?}",
+        id, new_parent_id
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    ensure_can_move(&tx, id, new_parent_id)?;
+    let moved_project = project_queries::move_project(&tx, id, new_parent_id)?;
+    tx.commit()?;
+    info!(
+        "[Command] 成功移动项目, ID: {}, 新的父项目: {:?}",
+        id, new_parent_id
+    );
+    Ok(moved_project)
+}
+
+/// Tauri 指令：删除一个项目，并按 `strategy` 处理其中的任务（默认移动到收件箱）
+/// 它的子项目会被移动到它的父项目下。整个过程在一个事务中完成，返回受影响数据的汇总。
+#[tauri::command]
+pub async fn delete_project(
+    id: i64,
+    strategy: Option<ProjectDeletionStrategy>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<ProjectDeletionSummary> {
+    let strategy = strategy.unwrap_or(ProjectDeletionStrategy::MoveToInbox);
+    debug!(
+        "[Command] delete_project, id: {}, strategy: {:?}",
+        id, strategy
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    if let ProjectDeletionStrategy::MoveToProject(target_id) = strategy {
+        if target_id == id {
+            return Err(Error::Validation(t!("error-delete-project-target-self")));
+        }
+        // 确保目标项目存在
+        project_queries::get_project_by_id(&tx, target_id)?;
+    }
+    ensure_children_can_move_up(&tx, id)?;
+    let had_running_timer = time_entry_queries::get_running_entry(&tx)?.is_some();
+    let summary = project_queries::delete_project(&tx, id, strategy)?;
+    let timer_deleted = had_running_timer && time_entry_queries::get_running_entry(&tx)?.is_none();
+    tx.commit()?;
+
+    // 被删除的任务可能引用了保管库中的文件
+    if summary.deleted_task_count > 0 {
+        let vault_dir = vault::vault_dir(&settings::data_dir(&app_handle)?);
+        vault::collect_garbage(&conn, &vault_dir)?;
+    }
+    drop(conn);
+    if timer_deleted {
+        // 正在运行的计时器随项目中的任务一起被删除了
+        time_entry_commands::notify_timer_changed(&app_handle, None)?;
+    }
+    info!("[Command] 成功删除项目, ID: {}, 汇总: {:?}", id, summary);
+    Ok(summary)
+}
+
+/// Tauri 指令：获取项目的默认提醒偏移量（单位：分钟）
 #[tauri::command]
-pub async fn delete_project(id: i64, state: tauri::State<'_, AppState>) -> Result<()> {
-    debug!("[Command] delete_project, id: {}", id);
+pub async fn get_project_default_reminders(
+    project_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<i64>> {
+    debug!(
+        "[Command] get_project_default_reminders, project_id: {}",
+        project_id
+    );
     let conn = state.db.lock().unwrap();
-    project_queries::delete_project(&conn, id)?;
-    info!("[Command] 成功删除项目, ID: {}", id);
+    let offsets = project_queries::get_default_reminder_offsets(&conn, project_id)?;
+    Ok(offsets)
+}
+
+/// Tauri 指令：设置项目的默认提醒偏移量
+/// 项目内的任务首次设置截止日期时，会按这些偏移量自动创建相对提醒
+#[tauri::command]
+pub async fn set_project_default_reminders(
+    project_id: i64,
+    offsets_minutes: Vec<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    debug!(
+        "[Command] set_project_default_reminders, project_id: {}, offsets: {:?}",
+        project_id, offsets_minutes
+    );
+    if offsets_minutes.iter().any(|offset| *offset < 0) {
+        return Err(Error::Validation(t!("error-negative-reminder-offset")));
+    }
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    project_queries::set_default_reminder_offsets(&tx, project_id, &offsets_minutes)?;
+    tx.commit()?;
+    info!("[Command] 成功更新项目 {} 的默认提醒", project_id);
+    Ok(())
+}
+
+/// 确保同一个父项目下没有其他同名的项目，`exclude_id` 是正在重命名或移动的项目自身
+pub(crate) fn ensure_unique_sibling_name(
+    conn: &Connection,
+    parent_id: Option<i64>,
+    name: &str,
+    exclude_id: Option<i64>,
+) -> Result<()> {
+    match project_queries::get_sibling_id_by_name(conn, parent_id, name)? {
+        Some(existing_id) if Some(existing_id) != exclude_id => {
+            Err(Error::Validation(t!("error-project-name-duplicate")))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 内部辅助函数：确保项目可以移动到 `new_parent_id` 下：
+/// 目标项目存在、不是该项目自身或其后代，且目标位置没有同名的项目
+fn ensure_can_move(conn: &Connection, id: i64, new_parent_id: Option<i64>) -> Result<()> {
+    let project = project_queries::get_project_by_id(conn, id)?;
+    if let Some(new_parent_id) = new_parent_id {
+        // 确保目标项目存在
+        project_queries::get_project_by_id(conn, new_parent_id)?;
+        if new_parent_id == id
+            || project_queries::get_descendant_project_ids(conn, id)?.contains(&new_parent_id)
+        {
+            return Err(Error::Validation(t!("error-project-move-cycle")));
+        }
+    }
+    ensure_unique_sibling_name(conn, new_parent_id, &project.name, Some(id))
+}
+
+/// 内部辅助函数：确保删除项目后，它的子项目移动到它的父项目下时不会与那里已有的项目重名
+fn ensure_children_can_move_up(conn: &Connection, id: i64) -> Result<()> {
+    let project = project_queries::get_project_by_id(conn, id)?;
+    for child in project_queries::get_child_projects(conn, id)? {
+        match project_queries::get_sibling_id_by_name(conn, project.parent_id, &child.name)? {
+            // 与被删除的项目自身同名不算冲突
+            Some(existing_id) if existing_id != id => {
+                return Err(Error::Validation(t!(
+                    "error-delete-project-child-name-clash",
+                    name = child.name
+                )));
+            }
+            _ => {}
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::open_test_db;
+
+    #[test]
+    fn moving_a_project_under_its_own_descendant_is_rejected() {
+        let conn = open_test_db();
+        let area = project_queries::create_project(&conn, "Area", None).unwrap();
+        let child = project_queries::create_project(&conn, "Child", Some(area.id)).unwrap();
+        let grandchild =
+            project_queries::create_project(&conn, "Grandchild", Some(child.id)).unwrap();
+
+        for target in [area.id, grandchild.id] {
+            assert!(matches!(
+                ensure_can_move(&conn, area.id, Some(target)),
+                Err(Error::Validation(_))
+            ));
+        }
+        assert!(ensure_can_move(&conn, grandchild.id, Some(area.id)).is_ok());
+        assert!(ensure_can_move(&conn, child.id, None).is_ok());
+    }
+
+    #[test]
+    fn sibling_names_must_be_unique_under_the_same_parent() {
+        let conn = open_test_db();
+        let area = project_queries::create_project(&conn, "Area", None).unwrap();
+        let other = project_queries::create_project(&conn, "Other", None).unwrap();
+        let child = project_queries::create_project(&conn, "Other", Some(area.id)).unwrap();
+
+        assert!(ensure_unique_sibling_name(&conn, None, "Other", None).is_err());
+        // 重命名为自身当前的名称不算冲突
+        assert!(ensure_unique_sibling_name(&conn, None, "Other", Some(other.id)).is_ok());
+        assert!(ensure_unique_sibling_name(&conn, Some(area.id), "Area", None).is_ok());
+        // 移动到顶层时与同名的顶层项目冲突
+        assert!(matches!(
+            ensure_can_move(&conn, child.id, None),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn deleting_is_rejected_when_a_sub_project_would_clash_with_its_new_siblings() {
+        let conn = open_test_db();
+        let area = project_queries::create_project(&conn, "Area", None).unwrap();
+        let project = project_queries::create_project(&conn, "Project", Some(area.id)).unwrap();
+        project_queries::create_project(&conn, "Docs", Some(area.id)).unwrap();
+        let docs = project_queries::create_project(&conn, "Docs", Some(project.id)).unwrap();
+        project_queries::create_project(&conn, "Project", Some(project.id)).unwrap();
+
+        assert!(matches!(
+            ensure_children_can_move_up(&conn, project.id),
+            Err(Error::Validation(_))
+        ));
+        // 与被删除的项目自身同名的子项目不算冲突
+        project_queries::update_project(&conn, docs.id, "Notes").unwrap();
+        assert!(ensure_children_can_move_up(&conn, project.id).is_ok());
+    }
+}
@@ -1,10 +1,16 @@
+use crate::app::reminder_service;
+use crate::app::settings;
 use crate::app::state::AppState;
-use crate::db::queries::reminder_queries;
-use crate::domain::reminder::Reminder;
-use crate::error::Result;
-use chrono::{DateTime, Utc};
+use crate::app::task_completion::{self, TASK_COMPLETION_SETTINGS_KEY};
+use crate::db::queries::{reminder_queries, task_queries};
+use crate::domain::digest::DailyDigest;
+use crate::domain::reminder::{Reminder, SnoozeOption};
+use crate::domain::settings::{OpenDescendantsPolicy, TaskCompletionSettings};
+use crate::error::{Error, Result};
+use crate::t;
+use chrono::{DateTime, Local, Utc};
 use log::{debug, info};
-use tauri::State;
+use tauri::{Emitter, Manager, State};
 
 #[tauri::command]
 pub async fn create_reminder(
@@ -25,6 +31,37 @@ pub async fn create_reminder(
     Ok(new_reminder)
 }
 
+/// Tauri 指令：创建一个相对于任务截止日期的提醒（例如提前 15 分钟、提前 1 天）
+/// 当任务的截止日期变化时，这类提醒会被自动重新计算
+#[tauri::command]
+pub async fn create_relative_reminder(
+    task_id: i64,
+    offset_minutes: i64,
+    state: State<'_, AppState>,
+) -> Result<Reminder> {
+    debug!(
+        "[Command] create_relative_reminder, task_id: {}, offset_minutes: {}",
+        task_id, offset_minutes
+    );
+    if offset_minutes < 0 {
+        return Err(Error::Validation(t!("error-negative-reminder-offset")));
+    }
+    let conn = state.db.lock().unwrap();
+    let task = task_queries::get_task_by_id(&conn, task_id)?;
+    let Some(due_date) = task.due_date else {
+        return Err(Error::Validation(t!(
+            "error-relative-reminder-without-due-date"
+        )));
+    };
+    let new_reminder =
+        reminder_queries::create_relative_reminder(&conn, task_id, offset_minutes, due_date)?;
+    info!(
+        "[Command] 成功为任务 {} 创建相对提醒, ID: {}",
+        task_id, new_reminder.id
+    );
+    Ok(new_reminder)
+}
+
 #[tauri::command]
 pub async fn get_reminders_for_task(
     task_id: i64,
@@ -44,3 +81,123 @@ pub async fn delete_reminder(id: i64, state: State<'_, AppState>) -> Result<()>
     info!("[Command] 成功删除提醒, ID: {}", id);
     Ok(())
 }
+
+/// Tauri 指令：获取提醒收件箱，即已经发出但尚未处理的提醒
+#[tauri::command]
+pub async fn get_reminder_inbox(state: State<'_, AppState>) -> Result<Vec<Reminder>> {
+    debug!("[Command] get_reminder_inbox");
+    let conn = state.db.lock().unwrap();
+    let reminders = reminder_queries::get_inbox_reminders(&conn)?;
+    Ok(reminders)
+}
+
+/// Tauri 指令：获取在应用关闭期间错过、且尚未处理的提醒
+#[tauri::command]
+pub async fn get_missed_reminders(state: State<'_, AppState>) -> Result<Vec<Reminder>> {
+    debug!("[Command] get_missed_reminders");
+    let conn = state.db.lock().unwrap();
+    let reminders = reminder_queries::get_missed_reminders(&conn)?;
+    Ok(reminders)
+}
+
+/// Tauri 指令：稍后提醒。重新安排已有的提醒记录，而不是新建一条
+#[tauri::command]
+pub async fn snooze_reminder(
+    id: i64,
+    option: SnoozeOption,
+    state: State<'_, AppState>,
+) -> Result<Reminder> {
+    debug!(
+        "[Command] snooze_reminder, id: {}, option: {:?}",
+        id, option
+    );
+    let now = Utc::now();
+    let remind_at = option.resolve(now);
+    if remind_at <= now {
+        return Err(Error::Validation(t!("error-snooze-in-past")));
+    }
+    let conn = state.db.lock().unwrap();
+    let reminder = reminder_queries::snooze_reminder(&conn, id, remind_at)?;
+    info!(
+        "[Command] 成功推迟提醒, ID: {}, 新的提醒时间: {}",
+        id, reminder.remind_at
+    );
+    Ok(reminder)
+}
+
+/// Tauri 指令：关闭收件箱中的一个提醒
+#[tauri::command]
+pub async fn dismiss_reminder(id: i64, state: State<'_, AppState>) -> Result<()> {
+    debug!("[Command] dismiss_reminder, id: {}", id);
+    let conn = state.db.lock().unwrap();
+    reminder_queries::dismiss_reminder(&conn, id)?;
+    info!("[Command] 成功关闭提醒, ID: {}", id);
+    Ok(())
+}
+
+/// Tauri 指令：从提醒直接将关联任务标记为完成，并关闭该提醒
+#[tauri::command]
+pub async fn complete_reminder_task(
+    id: i64,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<()> {
+    debug!("[Command] complete_reminder_task, id: {}", id);
+    let mut completion_settings: TaskCompletionSettings =
+        settings::load(&app_handle, TASK_COMPLETION_SETTINGS_KEY)?;
+    // 从提醒完成任务时没有机会提示用户，因此“提醒”策略按“只完成该任务”处理
+    if completion_settings.open_descendants == OpenDescendantsPolicy::Warn {
+        completion_settings.open_descendants = OpenDescendantsPolicy::Ignore;
+    }
+
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let reminder = reminder_queries::get_reminder_by_id(&tx, id)?;
+    let change =
+        task_completion::set_task_completion(&tx, reminder.task_id, true, &completion_settings)?;
+    reminder_queries::dismiss_reminder(&tx, id)?;
+    tx.commit()?;
+
+    if !change.unblocked_task_ids.is_empty() {
+        app_handle.emit("tasks_unblocked", &change.unblocked_task_ids)?;
+    }
+    info!(
+        "[Command] 已通过提醒 {} 完成任务, 任务ID: {}",
+        id, reminder.task_id
+    );
+    Ok(())
+}
+
+/// Tauri 指令：打开提醒关联的任务。
+/// 会唤出主窗口并广播 `open_task` 事件，由前端负责跳转到任务详情。
+#[tauri::command]
+pub async fn open_reminder_task(
+    id: i64,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<()> {
+    debug!("[Command] open_reminder_task, id: {}", id);
+    let task_id = {
+        let conn = state.db.lock().unwrap();
+        let reminder = reminder_queries::get_reminder_by_id(&conn, id)?;
+        reminder_queries::dismiss_reminder(&conn, id)?;
+        reminder.task_id
+    };
+
+    if let Some(window) = app_handle.get_webview_window("main") {
+        window.show()?;
+        window.set_focus()?;
+    }
+    app_handle.emit("open_task", task_id)?;
+    info!("[Command] 已通过提醒 {} 打开任务, 任务ID: {}", id, task_id);
+    Ok(())
+}
+
+/// Tauri 指令：立即生成今天的每日概览（不会发送通知）
+#[tauri::command]
+pub async fn get_daily_digest(state: State<'_, AppState>) -> Result<DailyDigest> {
+    debug!("[Command] get_daily_digest");
+    let conn = state.db.lock().unwrap();
+    let digest = reminder_service::build_daily_digest(&conn, Local::now().date_naive())?;
+    Ok(digest)
+}
@@ -0,0 +1,159 @@
+use crate::app::commands::ensure_same_ids;
+use crate::app::state::AppState;
+use crate::db::queries::{section_queries, task_queries};
+use crate::domain::section::{Section, SectionTasks};
+use crate::domain::task::Task;
+use crate::error::{Error, Result};
+use crate::t;
+use log::{debug, info};
+
+/// Tauri 指令：在项目的末尾创建一个新分组
+#[tauri::command]
+pub async fn create_section(
+    project_id: i64,
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Section> {
+    debug!(
+        "[Command] create_section, project_id: {}, name: '{}'",
+        project_id, name
+    );
+    let conn = state.db.lock().unwrap();
+    let section = section_queries::create_section(&conn, project_id, &name)?;
+    info!(
+        "[Command] 成功为项目 {} 创建分组, ID: {}",
+        project_id, section.id
+    );
+    Ok(section)
+}
+
+/// Tauri 指令：获取项目下的所有分组
+#[tauri::command]
+pub async fn get_sections_for_project(
+    project_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Section>> {
+    debug!(
+        "[Command] get_sections_for_project, project_id: {}",
+        project_id
+    );
+    let conn = state.db.lock().unwrap();
+    let sections = section_queries::get_sections_for_project(&conn, project_id)?;
+    Ok(sections)
+}
+
+/// Tauri 指令：重命名一个分组
+#[tauri::command]
+pub async fn rename_section(
+    id: i64,
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Section> {
+    debug!("[Command] rename_section, id: {}, name: '{}'", id, name);
+    let conn = state.db.lock().unwrap();
+    let section = section_queries::rename_section(&conn, id, &name)?;
+    info!("[Command] 成功重命名分组, ID: {}", id);
+    Ok(section)
+}
+
+/// Tauri 指令：按给定的 ID 顺序重新排列项目内的分组。
+/// `section_ids` 必须恰好包含项目的每个分组一次。
+#[tauri::command]
+pub async fn reorder_sections(
+    project_id: i64,
+    section_ids: Vec<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Section>> {
+    debug!(
+        "[Command] reorder_sections, project_id: {}, section_ids: {:?}",
+        project_id, section_ids
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let current = section_queries::get_sections_for_project(&tx, project_id)?;
+    ensure_same_ids(
+        current.iter().map(|section| section.id).collect(),
+        &section_ids,
+    )?;
+    section_queries::reorder_sections(&tx, project_id, &section_ids)?;
+    let sections = section_queries::get_sections_for_project(&tx, project_id)?;
+    tx.commit()?;
+    info!("[Command] 成功调整项目 {} 的分组顺序", project_id);
+    Ok(sections)
+}
+
+/// Tauri 指令：删除一个分组，其中的任务会被移出分组而不会被删除
+#[tauri::command]
+pub async fn delete_section(id: i64, state: tauri::State<'_, AppState>) -> Result<()> {
+    debug!("[Command] delete_section, id: {}", id);
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    section_queries::delete_section(&tx, id)?;
+    tx.commit()?;
+    info!("[Command] 成功删除分组, ID: {}", id);
+    Ok(())
+}
+
+/// Tauri 指令：将任务移动到同一项目内的另一个分组，`section_id` 为空时移出分组
+#[tauri::command]
+pub async fn move_task_to_section(
+    task_id: i64,
+    section_id: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Task> {
+    debug!(
+        "[Command] move_task_to_section, task_id: {}, section_id: {:?}",
+        task_id, section_id
+    );
+    let conn = state.db.lock().unwrap();
+    let task = task_queries::get_task_by_id(&conn, task_id)?;
+    if let Some(section_id) = section_id {
+        let section = section_queries::get_section_by_id(&conn, section_id)?;
+        if task.project_id != Some(section.project_id) {
+            return Err(Error::Validation(t!("error-section-other-project")));
+        }
+    }
+    task_queries::update_task_section(&conn, task_id, section_id)?;
+    info!(
+        "[Command] 成功将任务 {} 移动到分组 {:?}",
+        task_id, section_id
+    );
+    Ok(task_queries::get_task_by_id(&conn, task_id)?)
+}
+
+/// Tauri 指令：获取项目下的顶级任务，并按分组归类。
+/// 第一组是不属于任何分组的任务，之后按分组的顺序排列（包括没有任务的空分组）。
+#[tauri::command]
+pub async fn get_tasks_grouped_by_section(
+    project_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<SectionTasks>> {
+    debug!(
+        "[Command] get_tasks_grouped_by_section, project_id: {}",
+        project_id
+    );
+    let conn = state.db.lock().unwrap();
+    let sections = section_queries::get_sections_for_project(&conn, project_id)?;
+    let tasks = task_queries::get_tasks_by_parent(&conn, Some(project_id), None)?;
+
+    let mut groups: Vec<SectionTasks> = std::iter::once(None)
+        .chain(sections.into_iter().map(Some))
+        .map(|section| SectionTasks {
+            section,
+            tasks: Vec::new(),
+        })
+        .collect();
+    for task in tasks {
+        // 找不到对应分组的任务（例如分组已被删除）归入第一组
+        let index = task
+            .section_id
+            .and_then(|id| {
+                groups
+                    .iter()
+                    .position(|group| group.section.as_ref().is_some_and(|s| s.id == id))
+            })
+            .unwrap_or(0);
+        groups[index].tasks.push(task);
+    }
+    Ok(groups)
+}
@@ -1,33 +1,26 @@
 use std::fs;
 use std::path::PathBuf;
-use tauri::Manager;
 use tauri_plugin_store::StoreExt;
 
-use crate::error::Result;
+use crate::app::planning::PLANNING_SETTINGS_KEY;
+use crate::app::pomodoro::POMODORO_SETTINGS_KEY;
+use crate::app::reminder_service::{DIGEST_SETTINGS_KEY, QUIET_HOURS_SETTINGS_KEY};
+use crate::app::settings::{self, LANGUAGE_SETTINGS_KEY};
+use crate::app::task_completion::TASK_COMPLETION_SETTINGS_KEY;
+use crate::app::{tray, vault};
+use crate::domain::settings::{
+    DigestSettings, PlanningSettings, PomodoroSettings, QuietHoursSettings, TaskCompletionSettings,
+};
+use crate::error::{Error, Result};
+use crate::i18n::{self, Language};
+use crate::t;
+use chrono::{DateTime, Utc};
 use log::{error, info};
 
-/// 获取当前数据库文件的父目录路径
-fn get_current_db_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf> {
-    let store = app_handle.store("settings.json")?;
-
-    let custom_path = if let Some(path_value) = store.get("databasePath") {
-        path_value.as_str().map(PathBuf::from)
-    } else {
-        None
-    };
-
-    if let Some(path) = custom_path {
-        Ok(path)
-    } else {
-        let default_path = app_handle.path().app_data_dir()?;
-        Ok(default_path)
-    }
-}
-
 /// Tauri 指令：获取当前数据存储路径
 #[tauri::command]
 pub async fn get_data_path(app_handle: tauri::AppHandle) -> Result<String> {
-    let path = get_current_db_dir(&app_handle)?;
+    let path = settings::data_dir(&app_handle)?;
     Ok(path.to_str().unwrap_or("").to_string())
 }
 
@@ -41,7 +34,7 @@ pub async fn set_data_path(new_path_str: String, app_handle: tauri::AppHandle) -
         fs::create_dir_all(&new_path)?;
     }
 
-    let old_dir = get_current_db_dir(&app_handle)?;
+    let old_dir = settings::data_dir(&app_handle)?;
     let old_db_path = old_dir.join("momentum.db");
     let new_db_path = new_path.join("momentum.db");
 
@@ -63,6 +56,9 @@ pub async fn set_data_path(new_path_str: String, app_handle: tauri::AppHandle) -
         }
     }
 
+    // 附件保管库与数据库放在一起，需要随之移动
+    vault::relocate(&old_dir, &new_path)?;
+
     let store = app_handle.store("settings.json")?;
 
     store.set("databasePath".to_string(), new_path.to_str().unwrap());
@@ -71,3 +67,145 @@ pub async fn set_data_path(new_path_str: String, app_handle: tauri::AppHandle) -
     info!("[Settings] 成功更新数据路径并保存设置。");
     Ok(())
 }
+
+/// Tauri 指令：获取每日概览的设置
+#[tauri::command]
+pub async fn get_digest_settings(app_handle: tauri::AppHandle) -> Result<DigestSettings> {
+    settings::load(&app_handle, DIGEST_SETTINGS_KEY)
+}
+
+/// Tauri 指令：保存每日概览的设置
+#[tauri::command]
+pub async fn set_digest_settings(
+    digest_settings: DigestSettings,
+    app_handle: tauri::AppHandle,
+) -> Result<()> {
+    info!("[Settings] 更新每日概览设置: {:?}", digest_settings);
+    if digest_settings
+        .days_off
+        .iter()
+        .any(|day| !(1..=7).contains(day))
+    {
+        return Err(Error::Validation(t!("error-invalid-day-off")));
+    }
+    settings::save(&app_handle, DIGEST_SETTINGS_KEY, &digest_settings)
+}
+
+/// Tauri 指令：获取免打扰设置
+#[tauri::command]
+pub async fn get_quiet_hours_settings(app_handle: tauri::AppHandle) -> Result<QuietHoursSettings> {
+    settings::load(&app_handle, QUIET_HOURS_SETTINGS_KEY)
+}
+
+/// Tauri 指令：保存免打扰设置
+#[tauri::command]
+pub async fn set_quiet_hours_settings(
+    quiet_hours_settings: QuietHoursSettings,
+    app_handle: tauri::AppHandle,
+) -> Result<()> {
+    info!("[Settings] 更新免打扰设置: {:?}", quiet_hours_settings);
+    settings::save(&app_handle, QUIET_HOURS_SETTINGS_KEY, &quiet_hours_settings)
+}
+
+/// Tauri 指令：开启或关闭“专注/勿扰”模式。
+/// `until` 为勿扰结束的时间，传入 `None` 表示立即关闭勿扰。
+#[tauri::command]
+pub async fn set_do_not_disturb(
+    until: Option<DateTime<Utc>>,
+    app_handle: tauri::AppHandle,
+) -> Result<QuietHoursSettings> {
+    info!("[Settings] 设置勿扰模式直到: {:?}", until);
+    if until.is_some_and(|until| until <= Utc::now()) {
+        return Err(Error::Validation(t!("error-do-not-disturb-in-past")));
+    }
+    let mut quiet_hours_settings: QuietHoursSettings =
+        settings::load(&app_handle, QUIET_HOURS_SETTINGS_KEY)?;
+    quiet_hours_settings.do_not_disturb_until = until;
+    settings::save(&app_handle, QUIET_HOURS_SETTINGS_KEY, &quiet_hours_settings)?;
+    Ok(quiet_hours_settings)
+}
+
+/// Tauri 指令：获取任务完成联动的设置
+#[tauri::command]
+pub async fn get_task_completion_settings(
+    app_handle: tauri::AppHandle,
+) -> Result<TaskCompletionSettings> {
+    settings::load(&app_handle, TASK_COMPLETION_SETTINGS_KEY)
+}
+
+/// Tauri 指令：保存任务完成联动的设置
+#[tauri::command]
+pub async fn set_task_completion_settings(
+    task_completion_settings: TaskCompletionSettings,
+    app_handle: tauri::AppHandle,
+) -> Result<()> {
+    info!(
+        "[Settings] 更新任务完成联动设置: {:?}",
+        task_completion_settings
+    );
+    settings::save(
+        &app_handle,
+        TASK_COMPLETION_SETTINGS_KEY,
+        &task_completion_settings,
+    )
+}
+
+/// Tauri 指令：获取番茄钟的设置
+#[tauri::command]
+pub async fn get_pomodoro_settings(app_handle: tauri::AppHandle) -> Result<PomodoroSettings> {
+    settings::load(&app_handle, POMODORO_SETTINGS_KEY)
+}
+
+/// Tauri 指令：保存番茄钟的设置，对下一个阶段生效
+#[tauri::command]
+pub async fn set_pomodoro_settings(
+    pomodoro_settings: PomodoroSettings,
+    app_handle: tauri::AppHandle,
+) -> Result<()> {
+    info!("[Settings] 更新番茄钟设置: {:?}", pomodoro_settings);
+    let durations = [
+        pomodoro_settings.work_minutes,
+        pomodoro_settings.short_break_minutes,
+        pomodoro_settings.long_break_minutes,
+    ];
+    if durations.contains(&0) {
+        return Err(Error::Validation(t!("error-pomodoro-invalid-duration")));
+    }
+    settings::save(&app_handle, POMODORO_SETTINGS_KEY, &pomodoro_settings)
+}
+
+/// Tauri 指令：获取工作量估算和每日计划的设置
+#[tauri::command]
+pub async fn get_planning_settings(app_handle: tauri::AppHandle) -> Result<PlanningSettings> {
+    settings::load(&app_handle, PLANNING_SETTINGS_KEY)
+}
+
+/// Tauri 指令：保存工作量估算和每日计划的设置
+#[tauri::command]
+pub async fn set_planning_settings(
+    planning_settings: PlanningSettings,
+    app_handle: tauri::AppHandle,
+) -> Result<()> {
+    info!("[Settings] 更新每日计划设置: {:?}", planning_settings);
+    if planning_settings.daily_capacity == 0 {
+        return Err(Error::Validation(t!("error-planning-invalid-capacity")));
+    }
+    settings::save(&app_handle, PLANNING_SETTINGS_KEY, &planning_settings)
+}
+
+/// Tauri 指令：获取当前的界面语言
+#[tauri::command]
+pub async fn get_language() -> Result<Language> {
+    Ok(i18n::current_language())
+}
+
+/// Tauri 指令：切换界面语言。
+/// 会立即作用于之后的通知、错误信息，并刷新托盘菜单。
+#[tauri::command]
+pub async fn set_language(language: Language, app_handle: tauri::AppHandle) -> Result<()> {
+    info!("[Settings] 切换界面语言: {:?}", language);
+    settings::save(&app_handle, LANGUAGE_SETTINGS_KEY, &language)?;
+    i18n::set_language(language);
+    tray::refresh(&app_handle)?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::app::state::AppState;
+use crate::db::queries::{tag_queries, task_queries};
+use crate::domain::tag::Tag;
+use crate::domain::task::Task;
+use crate::error::{Error, Result};
+use crate::t;
+use log::{debug, info};
+
+/// Tauri 指令：获取所有标签
+#[tauri::command]
+pub async fn get_all_tags(state: tauri::State<'_, AppState>) -> Result<Vec<Tag>> {
+    debug!("[Command] get_all_tags");
+    let conn = state.db.lock().unwrap();
+    Ok(tag_queries::get_all_tags(&conn)?)
+}
+
+/// Tauri 指令：获取任务的所有标签
+#[tauri::command]
+pub async fn get_tags_for_task(
+    task_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Tag>> {
+    debug!("[Command] get_tags_for_task, task_id: {}", task_id);
+    let conn = state.db.lock().unwrap();
+    Ok(tag_queries::get_tags_for_task(&conn, task_id)?)
+}
+
+/// Tauri 指令：获取带有指定标签的所有任务
+#[tauri::command]
+pub async fn get_tasks_by_tag(tag_id: i64, state: tauri::State<'_, AppState>) -> Result<Vec<Task>> {
+    debug!("[Command] get_tasks_by_tag, tag_id: {}", tag_id);
+    let conn = state.db.lock().unwrap();
+    let task_ids = tag_queries::get_task_ids_by_tag(&conn, tag_id)?;
+    Ok(task_queries::get_tasks_by_ids(&conn, &task_ids)?)
+}
+
+/// Tauri 指令：按名称为任务添加标签，标签不存在时自动创建，返回添加的标签
+#[tauri::command]
+pub async fn add_tag_to_task(
+    task_id: i64,
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Tag> {
+    debug!(
+        "[Command] add_tag_to_task, task_id: {}, name: '{}'",
+        task_id, name
+    );
+    let name = validate_name(&name)?;
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    // 确保任务存在
+    task_queries::get_task_by_id(&tx, task_id)?;
+    let tag = tag_queries::get_or_create_tag(&tx, name)?;
+    tag_queries::add_tag_to_task(&tx, task_id, tag.id)?;
+    tx.commit()?;
+    info!("[Command] 成功为任务 {} 添加标签, ID: {}", task_id, tag.id);
+    Ok(tag)
+}
+
+/// Tauri 指令：移除任务的一个标签（标签本身保留）
+#[tauri::command]
+pub async fn remove_tag_from_task(
+    task_id: i64,
+    tag_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    debug!(
+        "[Command] remove_tag_from_task, task_id: {}, tag_id: {}",
+        task_id, tag_id
+    );
+    let conn = state.db.lock().unwrap();
+    tag_queries::remove_tag_from_task(&conn, task_id, tag_id)?;
+    info!("[Command] 成功移除任务 {} 的标签 {}", task_id, tag_id);
+    Ok(())
+}
+
+/// Tauri 指令：重命名一个标签，新名称不能与其他标签重复（不区分大小写）
+#[tauri::command]
+pub async fn rename_tag(id: i64, name: String, state: tauri::State<'_, AppState>) -> Result<Tag> {
+    debug!("[Command] rename_tag, id: {}, name: '{}'", id, name);
+    let name = validate_name(&name)?;
+    let conn = state.db.lock().unwrap();
+    if tag_queries::get_tag_by_name(&conn, name)?.is_some_and(|tag| tag.id != id) {
+        return Err(Error::Validation(t!("error-tag-name-duplicate")));
+    }
+    let tag = tag_queries::rename_tag(&conn, id, name)?;
+    info!("[Command] 成功重命名标签, ID: {}", id);
+    Ok(tag)
+}
+
+/// Tauri 指令：删除一个标签，并从所有任务上移除它
+#[tauri::command]
+pub async fn delete_tag(id: i64, state: tauri::State<'_, AppState>) -> Result<()> {
+    debug!("[Command] delete_tag, id: {}", id);
+    let conn = state.db.lock().unwrap();
+    tag_queries::delete_tag(&conn, id)?;
+    info!("[Command] 成功删除标签, ID: {}", id);
+    Ok(())
+}
+
+/// 内部辅助函数：标签名称去掉首尾空白后不能为空
+pub(crate) fn validate_name(name: &str) -> Result<&str> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(Error::Validation(t!("error-tag-name-empty")));
+    }
+    Ok(name)
+}
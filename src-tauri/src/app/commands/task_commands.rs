@@ -1,11 +1,27 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, NaiveDate, Utc};
 
+use crate::app::commands::{tag_commands, time_entry_commands};
+use crate::app::planning::{self, PLANNING_SETTINGS_KEY};
 use crate::app::state::AppState;
-use crate::db::queries::task_queries;
+use crate::app::task_completion::{self, TASK_COMPLETION_SETTINGS_KEY};
+use crate::app::task_duplication;
+use crate::app::{settings, vault};
+use crate::db::queries::{
+    project_queries, reminder_queries, tag_queries, task_queries, time_entry_queries,
+    workflow_status_queries,
+};
+use crate::domain::planning::DayPlan;
 use crate::domain::priority::Priority;
-use crate::domain::task::Task;
-use crate::error::Result;
+use crate::domain::settings::{OpenDescendantsPolicy, PlanningSettings, TaskCompletionSettings};
+use crate::domain::task::{
+    BulkTaskOutcome, BulkTaskResult, DuplicateTarget, ReminderCopyMode, Task, TaskStatusChange,
+};
+use crate::error::{Error, Result};
+use crate::t;
 use log::{debug, info};
+use rusqlite::Connection;
+use std::collections::{BTreeSet, HashSet};
+use tauri::Emitter;
 
 /// 使用 `#[tauri::command]` 宏将这个 Rust 函数标记为一个可以从前端调用的指令。
 /// 函数名 `create_task` 将成为前端 `invoke` 时使用的 command 字符串。
@@ -56,29 +72,112 @@ pub async fn get_tasks_by_parent(
     Ok(tasks)
 }
 
+/// Tauri 指令：更新任务的完成状态，并按“任务完成联动”设置处理后代任务和祖先任务。
+/// `open_descendants` 可以临时覆盖设置中的策略，例如用户在警告后确认“全部完成”。
 #[tauri::command]
 pub async fn update_task_status(
     id: i64,
     is_completed: bool,
+    open_descendants: Option<OpenDescendantsPolicy>,
+    app_handle: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
-) -> Result<()> {
+) -> Result<TaskStatusChange> {
     debug!(
-        "[Command] update_task_status, id: {}, is_completed: {}",
-        id, is_completed
+        "[Command] update_task_status, id: {}, is_completed: {}, open_descendants: {:?}",
+        id, is_completed, open_descendants
     );
-    // 这个指令在成功时不需要返回有意义的数据，所以用 `()` （空元组）表示。
-    let conn = state.db.lock().unwrap();
-    task_queries::update_task_status(&conn, id, is_completed)?;
-    info!("[Command] 成功更新任务状态, ID: {}", id);
-    Ok(())
+    let completion_settings = load_completion_settings(&app_handle, open_descendants)?;
+
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let change = task_completion::set_task_completion(&tx, id, is_completed, &completion_settings)?;
+    tx.commit()?;
+
+    if !change.unblocked_task_ids.is_empty() {
+        // 通知前端这些任务已经可以开始了
+        app_handle.emit("tasks_unblocked", &change.unblocked_task_ids)?;
+    }
+    if change.applied {
+        info!(
+            "[Command] 成功更新任务状态, ID: {}, 一并完成的后代任务: {:?}, 联动的祖先任务: {:?}",
+            id, change.completed_descendant_ids, change.updated_ancestor_ids
+        );
+    } else {
+        info!(
+            "[Command] 任务 {} 仍有 {} 个未完成的后代任务，未更新状态",
+            id, change.open_descendant_count
+        );
+    }
+    Ok(change)
+}
+
+/// Tauri 指令：将任务切换到指定的工作流状态（例如在看板中拖动任务）。
+/// 状态必须属于任务所在项目使用的状态集合；切换到视为已完成的状态时，联动规则与 `update_task_status` 相同。
+#[tauri::command]
+pub async fn set_task_status(
+    id: i64,
+    status_id: i64,
+    open_descendants: Option<OpenDescendantsPolicy>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<TaskStatusChange> {
+    debug!(
+        "[Command] set_task_status, id: {}, status_id: {}, open_descendants: {:?}",
+        id, status_id, open_descendants
+    );
+    let completion_settings = load_completion_settings(&app_handle, open_descendants)?;
+
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let task = task_queries::get_task_by_id(&tx, id)?;
+    let status = workflow_status_queries::get_status_by_id(&tx, status_id)?;
+    if status.project_id != workflow_status_queries::get_status_owner(&tx, task.project_id)? {
+        return Err(Error::Validation(t!("error-status-other-project")));
+    }
+    let change = task_completion::set_task_status(&tx, id, &status, &completion_settings)?;
+    tx.commit()?;
+
+    if !change.unblocked_task_ids.is_empty() {
+        app_handle.emit("tasks_unblocked", &change.unblocked_task_ids)?;
+    }
+    info!(
+        "[Command] 任务 {} 的状态更新为 '{}', 是否已更新: {}",
+        id, status.name, change.applied
+    );
+    Ok(change)
+}
+
+/// 内部辅助函数：读取任务完成联动设置，并应用本次调用临时指定的策略
+fn load_completion_settings(
+    app_handle: &tauri::AppHandle,
+    open_descendants: Option<OpenDescendantsPolicy>,
+) -> Result<TaskCompletionSettings> {
+    let mut completion_settings: TaskCompletionSettings =
+        settings::load(app_handle, TASK_COMPLETION_SETTINGS_KEY)?;
+    if let Some(policy) = open_descendants {
+        completion_settings.open_descendants = policy;
+    }
+    Ok(completion_settings)
 }
 
 #[tauri::command]
-pub async fn delete_task(id: i64, state: tauri::State<'_, AppState>) -> Result<()> {
+pub async fn delete_task(
+    id: i64,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
     debug!("[Command] delete_task, id: {}", id);
-    let conn = state.db.lock().unwrap();
-    task_queries::delete_task(&conn, id)?;
+    let timer_deleted = {
+        let conn = state.db.lock().unwrap();
+        let had_running_timer = time_entry_queries::get_running_entry(&conn)?.is_some();
+        task_queries::delete_task(&conn, id)?;
+        // 任务的时间记录会随任务一起删除，其中可能包括正在运行的计时器
+        had_running_timer && time_entry_queries::get_running_entry(&conn)?.is_none()
+    };
     info!("[Command] 成功删除任务, ID: {}", id);
+    if timer_deleted {
+        time_entry_commands::notify_timer_changed(&app_handle, None)?;
+    }
     Ok(())
 }
 
@@ -124,12 +223,116 @@ pub async fn update_task_due_date(
         id, due_date
     );
     // Tauri 的 serde 反序列化能力会自动将前端的 ISO 8601 日期字符串解析为 DateTime<Utc>
-    let conn = state.db.lock().unwrap();
-    task_queries::update_task_due_date(&conn, id, due_date)?;
+    let mut conn = state.db.lock().unwrap();
+    // 截止日期与相对提醒需要保持一致，因此放在同一个事务中更新
+    let tx = conn.transaction()?;
+    apply_due_date(&tx, id, due_date)?;
+    tx.commit()?;
     info!("[Command] 成功更新任务截止日期, ID: {}", id);
     Ok(())
 }
 
+/// 内部辅助函数：更新任务的截止日期，并同步更新相对提醒
+fn apply_due_date(conn: &Connection, id: i64, due_date: Option<DateTime<Utc>>) -> Result<()> {
+    let previous = task_queries::get_task_by_id(conn, id)?;
+    task_queries::update_task_due_date(conn, id, due_date)?;
+
+    match due_date {
+        Some(due) => {
+            let relative_count = reminder_queries::reschedule_relative_reminders(conn, id, due)?;
+            // 任务首次获得截止日期时，套用所属项目的默认提醒
+            if relative_count == 0 && previous.due_date.is_none() {
+                if let Some(project_id) = previous.project_id {
+                    for offset in project_queries::get_default_reminder_offsets(conn, project_id)? {
+                        reminder_queries::create_relative_reminder(conn, id, offset, due)?;
+                    }
+                }
+            }
+        }
+        None => {
+            // 没有截止日期，相对提醒也就失去了意义
+            reminder_queries::delete_relative_reminders(conn, id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Tauri 指令：深度复制一个任务及其所有后代任务，返回新的根任务。
+/// `target` 默认为原任务所在的位置，`shift_days` 为截止日期平移的天数（默认不平移），
+/// `reminders` 默认随截止日期一起平移。
+#[tauri::command]
+pub async fn duplicate_task(
+    id: i64,
+    target: Option<DuplicateTarget>,
+    shift_days: Option<i64>,
+    reminders: Option<ReminderCopyMode>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Task> {
+    debug!(
+        "[Command] duplicate_task, id: {}, target: {:?}, shift_days: {:?}, reminders: {:?}",
+        id, target, shift_days, reminders
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let copy = task_duplication::duplicate_task(
+        &tx,
+        id,
+        target.unwrap_or_default(),
+        shift_days.unwrap_or_default(),
+        reminders.unwrap_or_default(),
+        Utc::now(),
+    )?;
+    tx.commit()?;
+    info!(
+        "[Command] 成功复制任务 {}, 新任务ID: {}, 共复制 {} 个子孙任务",
+        id, copy.id, copy.descendant_count
+    );
+    Ok(copy)
+}
+
+/// Tauri 指令，用于更新一个任务的工作量估算，`estimate` 为 null 时清除估算
+#[tauri::command]
+pub async fn update_task_estimate(
+    id: i64,
+    estimate: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    debug!(
+        "[Command] update_task_estimate, id: {}, estimate: {:?}",
+        id, estimate
+    );
+    if estimate.is_some_and(|estimate| estimate < 0) {
+        return Err(Error::Validation(t!("error-estimate-negative")));
+    }
+    let conn = state.db.lock().unwrap();
+    task_queries::update_task_estimate(&conn, id, estimate)?;
+    info!("[Command] 成功更新任务估算, ID: {}", id);
+    Ok(())
+}
+
+/// Tauri 指令：规划某一天（默认为今天）。按优先级和截止日期挑选当天到期、已经逾期
+/// 或没有截止日期的任务，直到用完设置中的每日容量，同时返回放不下的任务。
+#[tauri::command]
+pub async fn plan_my_day(
+    date: Option<NaiveDate>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<DayPlan> {
+    debug!("[Command] plan_my_day, date: {:?}", date);
+    let planning_settings: PlanningSettings = settings::load(&app_handle, PLANNING_SETTINGS_KEY)?;
+    let date = date.unwrap_or_else(|| Local::now().date_naive());
+    let conn = state.db.lock().unwrap();
+    let plan = planning::plan_day(&conn, &planning_settings, date)?;
+    info!(
+        "[Command] 成功规划 {}: 安排 {} 个任务, {} 个任务超出容量",
+        date,
+        plan.planned.len(),
+        plan.overflow.len()
+    );
+    Ok(plan)
+}
+
 /// Tauri 指令，用于更新一个任务的描述
 #[tauri::command]
 pub async fn update_task_description(
@@ -143,3 +346,450 @@ pub async fn update_task_description(
     info!("[Command] 成功更新任务描述, ID: {}", id);
     Ok(())
 }
+
+// --- 批量操作 ---
+// 每个批量指令都在一个事务中处理所有任务，返回每个任务的结果，
+// 并只广播一次 `tasks_changed` 事件，携带所有改变了的任务 ID。
+
+/// Tauri 指令：批量更新任务的完成状态，联动规则与 `update_task_status` 相同。
+/// 在“提醒”策略下，仍有未完成后代任务的任务不会被更新，并在结果中标记为失败。
+#[tauri::command]
+pub async fn bulk_update_task_status(
+    ids: Vec<i64>,
+    is_completed: bool,
+    open_descendants: Option<OpenDescendantsPolicy>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<BulkTaskOutcome> {
+    debug!(
+        "[Command] bulk_update_task_status, ids: {:?}, is_completed: {}, open_descendants: {:?}",
+        ids, is_completed, open_descendants
+    );
+    let completion_settings = load_completion_settings(&app_handle, open_descendants)?;
+    let mut unblocked_task_ids: BTreeSet<i64> = BTreeSet::new();
+    let outcome = {
+        let mut conn = state.db.lock().unwrap();
+        run_bulk(&mut conn, &ids, |conn, id| {
+            set_completion_in_bulk(
+                conn,
+                id,
+                is_completed,
+                &completion_settings,
+                &mut unblocked_task_ids,
+            )
+        })?
+    };
+    notify_bulk_change(&app_handle, "bulk_update_task_status", &outcome)?;
+    if !unblocked_task_ids.is_empty() {
+        // 与单个任务完成时一样，通知前端这些任务已经可以开始了
+        let unblocked_task_ids: Vec<i64> = unblocked_task_ids.into_iter().collect();
+        app_handle.emit("tasks_unblocked", &unblocked_task_ids)?;
+    }
+    Ok(outcome)
+}
+
+/// Tauri 指令：批量更新任务的优先级
+#[tauri::command]
+pub async fn bulk_update_task_priority(
+    ids: Vec<i64>,
+    priority: Priority,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<BulkTaskOutcome> {
+    debug!(
+        "[Command] bulk_update_task_priority, ids: {:?}, priority: {:?}",
+        ids, priority
+    );
+    let outcome = {
+        let mut conn = state.db.lock().unwrap();
+        run_bulk(&mut conn, &ids, |conn, id| {
+            ensure_found(task_queries::update_task_priority(conn, id, priority)?)?;
+            Ok(vec![id])
+        })?
+    };
+    notify_bulk_change(&app_handle, "bulk_update_task_priority", &outcome)?;
+    Ok(outcome)
+}
+
+/// Tauri 指令：批量更新任务的截止日期，相对提醒的处理与 `update_task_due_date` 相同
+#[tauri::command]
+pub async fn bulk_update_task_due_date(
+    ids: Vec<i64>,
+    due_date: Option<DateTime<Utc>>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<BulkTaskOutcome> {
+    debug!(
+        "[Command] bulk_update_task_due_date, ids: {:?}, due_date: {:?}",
+        ids, due_date
+    );
+    let outcome = {
+        let mut conn = state.db.lock().unwrap();
+        run_bulk(&mut conn, &ids, |conn, id| {
+            apply_due_date(conn, id, due_date)?;
+            Ok(vec![id])
+        })?
+    };
+    notify_bulk_change(&app_handle, "bulk_update_task_due_date", &outcome)?;
+    Ok(outcome)
+}
+
+/// Tauri 指令：批量为任务添加标签，标签不存在时自动创建。已经有该标签的任务视为成功。
+#[tauri::command]
+pub async fn bulk_add_tag_to_tasks(
+    ids: Vec<i64>,
+    name: String,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<BulkTaskOutcome> {
+    debug!(
+        "[Command] bulk_add_tag_to_tasks, ids: {:?}, name: '{}'",
+        ids, name
+    );
+    let name = tag_commands::validate_name(&name)?;
+    let outcome = {
+        let mut conn = state.db.lock().unwrap();
+        run_bulk(&mut conn, &ids, |conn, id| add_tag_by_name(conn, id, name))?
+    };
+    notify_bulk_change(&app_handle, "bulk_add_tag_to_tasks", &outcome)?;
+    Ok(outcome)
+}
+
+/// Tauri 指令：批量将任务（连同它们的子任务）移动到另一个项目，`project_id` 为空时移动到收件箱。
+/// 父任务不在目标项目中的子任务会成为目标项目的顶层任务。
+#[tauri::command]
+pub async fn bulk_move_tasks_to_project(
+    ids: Vec<i64>,
+    project_id: Option<i64>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<BulkTaskOutcome> {
+    debug!(
+        "[Command] bulk_move_tasks_to_project, ids: {:?}, project_id: {:?}",
+        ids, project_id
+    );
+    let outcome = {
+        let mut conn = state.db.lock().unwrap();
+        if let Some(project_id) = project_id {
+            // 确保目标项目存在
+            project_queries::get_project_by_id(&conn, project_id)?;
+        }
+        run_bulk(&mut conn, &ids, |conn, id| {
+            move_subtree_to_project(conn, id, project_id)
+        })?
+    };
+    notify_bulk_change(&app_handle, "bulk_move_tasks_to_project", &outcome)?;
+    Ok(outcome)
+}
+
+/// Tauri 指令：批量删除任务，它们的子任务会一并删除。
+/// 已经随着另一个被选中的祖先任务一起删除的任务视为成功。
+#[tauri::command]
+pub async fn bulk_delete_tasks(
+    ids: Vec<i64>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<BulkTaskOutcome> {
+    debug!("[Command] bulk_delete_tasks, ids: {:?}", ids);
+    let mut deleted: HashSet<i64> = HashSet::new();
+    let (outcome, timer_deleted) = {
+        let mut conn = state.db.lock().unwrap();
+        let had_running_timer = time_entry_queries::get_running_entry(&conn)?.is_some();
+        let outcome = run_bulk(&mut conn, &ids, |conn, id| {
+            delete_subtree(conn, id, &mut deleted)
+        })?;
+        // 被删除的任务可能引用了保管库中的文件
+        if !deleted.is_empty() {
+            let vault_dir = vault::vault_dir(&settings::data_dir(&app_handle)?);
+            vault::collect_garbage(&conn, &vault_dir)?;
+        }
+        let timer_deleted =
+            had_running_timer && time_entry_queries::get_running_entry(&conn)?.is_none();
+        (outcome, timer_deleted)
+    };
+    notify_bulk_change(&app_handle, "bulk_delete_tasks", &outcome)?;
+    if timer_deleted {
+        time_entry_commands::notify_timer_changed(&app_handle, None)?;
+    }
+    Ok(outcome)
+}
+
+/// 内部辅助函数：在一个事务中依次对每个任务执行 `op`，`op` 返回因此改变的任务 ID。
+/// 每个任务使用各自的保存点，失败的任务只回滚自己的修改并记录错误信息，不影响其他任务。
+fn run_bulk(
+    conn: &mut Connection,
+    ids: &[i64],
+    mut op: impl FnMut(&Connection, i64) -> Result<Vec<i64>>,
+) -> Result<BulkTaskOutcome> {
+    let mut outcome = BulkTaskOutcome::default();
+    let mut changed_ids = BTreeSet::new();
+    let mut tx = conn.transaction()?;
+    for &id in ids {
+        let savepoint = tx.savepoint()?;
+        match op(&savepoint, id) {
+            Ok(ids) => {
+                savepoint.commit()?;
+                changed_ids.extend(ids);
+                outcome.succeeded_count += 1;
+                outcome.results.push(BulkTaskResult {
+                    id,
+                    success: true,
+                    error: None,
+                });
+            }
+            Err(e) => {
+                // 保存点在离开作用域时自动回滚
+                outcome.failed_count += 1;
+                outcome.results.push(BulkTaskResult {
+                    id,
+                    success: false,
+                    error: Some(e.localized_message()),
+                });
+            }
+        }
+    }
+    tx.commit()?;
+    outcome.changed_task_ids = changed_ids.into_iter().collect();
+    Ok(outcome)
+}
+
+/// 内部辅助函数：批量更新完成状态时处理单个任务，解除阻塞的任务会记录到 `unblocked_task_ids`
+fn set_completion_in_bulk(
+    conn: &Connection,
+    id: i64,
+    is_completed: bool,
+    completion_settings: &TaskCompletionSettings,
+    unblocked_task_ids: &mut BTreeSet<i64>,
+) -> Result<Vec<i64>> {
+    let change = task_completion::set_task_completion(conn, id, is_completed, completion_settings)?;
+    if !change.applied {
+        return Err(Error::Validation(t!(
+            "error-task-open-descendants",
+            count = change.open_descendant_count
+        )));
+    }
+    unblocked_task_ids.extend(&change.unblocked_task_ids);
+    Ok([id]
+        .into_iter()
+        .chain(change.completed_descendant_ids)
+        .chain(change.updated_ancestor_ids)
+        .chain(change.unblocked_task_ids)
+        .collect())
+}
+
+/// 内部辅助函数：为单个任务添加标签，标签不存在时自动创建
+fn add_tag_by_name(conn: &Connection, id: i64, name: &str) -> Result<Vec<i64>> {
+    // 确保任务存在
+    task_queries::get_task_by_id(conn, id)?;
+    let tag = tag_queries::get_or_create_tag(conn, name)?;
+    tag_queries::add_tag_to_task(conn, id, tag.id)?;
+    Ok(vec![id])
+}
+
+/// 内部辅助函数：将任务连同子任务移动到另一个项目，父任务不在目标项目中时成为顶层任务
+fn move_subtree_to_project(
+    conn: &Connection,
+    id: i64,
+    project_id: Option<i64>,
+) -> Result<Vec<i64>> {
+    let task = task_queries::get_task_by_id(conn, id)?;
+    let subtree_ids = task_queries::get_subtree_task_ids(conn, &[id])?;
+    task_queries::move_tasks_to_project(conn, &subtree_ids, project_id)?;
+    if let Some(parent_id) = task.parent_id {
+        let parent = task_queries::get_task_by_id(conn, parent_id)?;
+        if parent.project_id != project_id {
+            task_queries::update_task_parent(conn, id, None)?;
+        }
+    }
+    Ok(subtree_ids)
+}
+
+/// 内部辅助函数：删除任务及其子任务，`deleted` 记录已经删除的任务，
+/// 已经随着祖先任务一起删除的任务直接视为成功
+fn delete_subtree(conn: &Connection, id: i64, deleted: &mut HashSet<i64>) -> Result<Vec<i64>> {
+    if deleted.contains(&id) {
+        return Ok(Vec::new());
+    }
+    let subtree_ids = task_queries::get_subtree_task_ids(conn, &[id])?;
+    ensure_found(task_queries::delete_task(conn, id)?)?;
+    deleted.extend(&subtree_ids);
+    Ok(subtree_ids)
+}
+
+/// 内部辅助函数：更新语句没有影响任何行时，说明任务不存在
+fn ensure_found(changed_rows: usize) -> Result<()> {
+    if changed_rows == 0 {
+        return Err(Error::Sqlite(rusqlite::Error::QueryReturnedNoRows));
+    }
+    Ok(())
+}
+
+/// 内部辅助函数：记录批量操作的结果，并广播一次 `tasks_changed` 事件
+fn notify_bulk_change(
+    app_handle: &tauri::AppHandle,
+    command: &str,
+    outcome: &BulkTaskOutcome,
+) -> Result<()> {
+    info!(
+        "[Command] {} 完成: 成功 {} 个, 失败 {} 个",
+        command, outcome.succeeded_count, outcome.failed_count
+    );
+    if !outcome.changed_task_ids.is_empty() {
+        app_handle.emit("tasks_changed", &outcome.changed_task_ids)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::open_test_db;
+
+    fn task(
+        conn: &Connection,
+        title: &str,
+        project_id: Option<i64>,
+        parent_id: Option<i64>,
+    ) -> i64 {
+        task_queries::create_task(conn, title, project_id, parent_id)
+            .unwrap()
+            .id
+    }
+
+    fn successes(outcome: &BulkTaskOutcome) -> Vec<(i64, bool)> {
+        outcome.results.iter().map(|r| (r.id, r.success)).collect()
+    }
+
+    #[test]
+    fn bulk_complete_reports_tasks_with_open_descendants_as_failed() {
+        let mut conn = open_test_db();
+        let a = task(&conn, "a", None, None);
+        let b = task(&conn, "b", None, None);
+        let child = task(&conn, "child", None, Some(b));
+        let completion_settings = TaskCompletionSettings {
+            open_descendants: OpenDescendantsPolicy::Warn,
+            ..Default::default()
+        };
+        let mut unblocked = BTreeSet::new();
+
+        let outcome = run_bulk(&mut conn, &[a, b, child], |conn, id| {
+            set_completion_in_bulk(conn, id, true, &completion_settings, &mut unblocked)
+        })
+        .unwrap();
+
+        assert_eq!(
+            successes(&outcome),
+            vec![(a, true), (b, false), (child, true)]
+        );
+        assert_eq!((outcome.succeeded_count, outcome.failed_count), (2, 1));
+        assert!(outcome.results[1].error.is_some());
+        assert_eq!(outcome.changed_task_ids, vec![a, child]);
+        assert!(task_queries::get_task_by_id(&conn, a).unwrap().is_completed);
+        assert!(!task_queries::get_task_by_id(&conn, b).unwrap().is_completed);
+    }
+
+    #[test]
+    fn bulk_move_takes_subtasks_along_and_detaches_them_from_unmoved_parents() {
+        let mut conn = open_test_db();
+        let source = project_queries::create_project(&conn, "Source", None).unwrap();
+        let target = project_queries::create_project(&conn, "Target", None).unwrap();
+        let parent = task(&conn, "parent", Some(source.id), None);
+        let child = task(&conn, "child", Some(source.id), Some(parent));
+        let other = task(&conn, "other", Some(source.id), None);
+        let nested = task(&conn, "nested", Some(source.id), Some(other));
+
+        let outcome = run_bulk(&mut conn, &[parent, nested], |conn, id| {
+            move_subtree_to_project(conn, id, Some(target.id))
+        })
+        .unwrap();
+
+        assert_eq!(outcome.succeeded_count, 2);
+        assert_eq!(outcome.changed_task_ids, vec![parent, child, nested]);
+        let child = task_queries::get_task_by_id(&conn, child).unwrap();
+        assert_eq!(
+            (child.project_id, child.parent_id),
+            (Some(target.id), Some(parent))
+        );
+        let nested = task_queries::get_task_by_id(&conn, nested).unwrap();
+        assert_eq!(
+            (nested.project_id, nested.parent_id),
+            (Some(target.id), None)
+        );
+        let other = task_queries::get_task_by_id(&conn, other).unwrap();
+        assert_eq!(other.project_id, Some(source.id));
+    }
+
+    #[test]
+    fn bulk_delete_counts_tasks_removed_with_an_ancestor_as_deleted() {
+        let mut conn = open_test_db();
+        let parent = task(&conn, "parent", None, None);
+        let child = task(&conn, "child", None, Some(parent));
+        let kept = task(&conn, "kept", None, None);
+        let mut deleted = HashSet::new();
+
+        let outcome = run_bulk(&mut conn, &[parent, child, 999], |conn, id| {
+            delete_subtree(conn, id, &mut deleted)
+        })
+        .unwrap();
+
+        assert_eq!(
+            successes(&outcome),
+            vec![(parent, true), (child, true), (999, false)]
+        );
+        assert_eq!(outcome.changed_task_ids, vec![parent, child]);
+        assert!(task_queries::get_task_by_id(&conn, parent).is_err());
+        assert!(task_queries::get_task_by_id(&conn, child).is_err());
+        assert!(task_queries::get_task_by_id(&conn, kept).is_ok());
+    }
+
+    #[test]
+    fn bulk_add_tag_creates_the_tag_once_and_skips_missing_tasks() {
+        let mut conn = open_test_db();
+        let a = task(&conn, "a", None, None);
+        let b = task(&conn, "b", None, None);
+
+        let outcome = run_bulk(&mut conn, &[a, 999, b], |conn, id| {
+            add_tag_by_name(conn, id, "urgent")
+        })
+        .unwrap();
+
+        assert_eq!(
+            successes(&outcome),
+            vec![(a, true), (999, false), (b, true)]
+        );
+        for id in [a, b] {
+            let tags = tag_queries::get_tags_for_task(&conn, id).unwrap();
+            assert_eq!(
+                tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+                ["urgent"]
+            );
+        }
+    }
+
+    #[test]
+    fn a_failed_task_only_rolls_back_its_own_changes() {
+        let mut conn = open_test_db();
+        let a = task(&conn, "a", None, None);
+        let b = task(&conn, "b", None, None);
+
+        let outcome = run_bulk(&mut conn, &[a, b], |conn, id| {
+            task_queries::update_task_priority(conn, id, Priority::High)?;
+            if id == b {
+                return Err(Error::Validation("boom".into()));
+            }
+            Ok(vec![id])
+        })
+        .unwrap();
+
+        assert_eq!(successes(&outcome), vec![(a, true), (b, false)]);
+        assert_eq!(outcome.results[1].error.as_deref(), Some("boom"));
+        assert_eq!(
+            task_queries::get_task_by_id(&conn, a).unwrap().priority,
+            Priority::High
+        );
+        assert_ne!(
+            task_queries::get_task_by_id(&conn, b).unwrap().priority,
+            Priority::High
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::app::state::AppState;
+use crate::app::{settings, templates, vault};
+use crate::db::queries::template_queries;
+use crate::domain::template::{Template, TemplateInstance, TemplateTask};
+use crate::error::Result;
+use chrono::{Local, NaiveDate};
+use log::{debug, info};
+use std::collections::HashMap;
+
+/// Tauri 指令：将任务及其所有子任务保存为模板
+#[tauri::command]
+pub async fn create_task_template(
+    task_id: i64,
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Template> {
+    debug!(
+        "[Command] create_task_template, task_id: {}, name: '{}'",
+        task_id, name
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let template = templates::capture_task(&tx, task_id, &name)?;
+    tx.commit()?;
+    info!(
+        "[Command] 成功创建任务模板, ID: {}, 任务数: {}",
+        template.id, template.task_count
+    );
+    Ok(template)
+}
+
+/// Tauri 指令：将项目及其中的所有任务保存为模板
+#[tauri::command]
+pub async fn create_project_template(
+    project_id: i64,
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Template> {
+    debug!(
+        "[Command] create_project_template, project_id: {}, name: '{}'",
+        project_id, name
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let template = templates::capture_project(&tx, project_id, &name)?;
+    tx.commit()?;
+    info!(
+        "[Command] 成功创建项目模板, ID: {}, 任务数: {}",
+        template.id, template.task_count
+    );
+    Ok(template)
+}
+
+/// Tauri 指令：获取所有模板
+#[tauri::command]
+pub async fn get_templates(state: tauri::State<'_, AppState>) -> Result<Vec<Template>> {
+    debug!("[Command] get_templates");
+    let conn = state.db.lock().unwrap();
+    Ok(template_queries::get_all_templates(&conn)?)
+}
+
+/// Tauri 指令：获取模板中的所有任务（扁平列表，通过 `parent_id` 组成层级）
+#[tauri::command]
+pub async fn get_template_tasks(
+    template_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<TemplateTask>> {
+    debug!("[Command] get_template_tasks, template_id: {}", template_id);
+    let conn = state.db.lock().unwrap();
+    Ok(template_queries::get_template_tasks(&conn, template_id)?)
+}
+
+/// Tauri 指令：重命名模板
+#[tauri::command]
+pub async fn rename_template(
+    id: i64,
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Template> {
+    debug!("[Command] rename_template, id: {}, name: '{}'", id, name);
+    let conn = state.db.lock().unwrap();
+    let template = template_queries::rename_template(&conn, id, &name)?;
+    info!("[Command] 成功重命名模板, ID: {}", id);
+    Ok(template)
+}
+
+/// Tauri 指令：删除模板
+#[tauri::command]
+pub async fn delete_template(
+    id: i64,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    debug!("[Command] delete_template, id: {}", id);
+    let conn = state.db.lock().unwrap();
+    template_queries::delete_template(&conn, id)?;
+    // 模板中的附件可能是保管库中某些文件的最后一个引用
+    let vault_dir = vault::vault_dir(&settings::data_dir(&app_handle)?);
+    vault::collect_garbage(&conn, &vault_dir)?;
+    info!("[Command] 成功删除模板, ID: {}", id);
+    Ok(())
+}
+
+/// Tauri 指令：使用模板创建任务（和项目），所有内容在同一个事务中创建。
+/// `variables` 为 `{{变量}}` 的取值；`anchor_date` 为计算截止日期的基准日期，默认为今天。
+/// `project_id` 和 `parent_id` 的含义见 `templates::instantiate`。
+#[tauri::command]
+pub async fn instantiate_template(
+    template_id: i64,
+    variables: Option<HashMap<String, String>>,
+    anchor_date: Option<NaiveDate>,
+    project_id: Option<i64>,
+    parent_id: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<TemplateInstance> {
+    let anchor_date = anchor_date.unwrap_or_else(|| Local::now().date_naive());
+    debug!(
+        "[Command] instantiate_template, template_id: {}, anchor_date: {}, project_id: {:?}, parent_id: {:?}",
+        template_id, anchor_date, project_id, parent_id
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let instance = templates::instantiate(
+        &tx,
+        template_id,
+        &variables.unwrap_or_default(),
+        anchor_date,
+        project_id,
+        parent_id,
+    )?;
+    tx.commit()?;
+    info!(
+        "[Command] 成功使用模板 {} 创建了 {} 个任务",
+        template_id, instance.created_task_count
+    );
+    Ok(instance)
+}
@@ -0,0 +1,225 @@
+use crate::app::state::AppState;
+use crate::app::tray;
+use crate::db::queries::{task_queries, time_entry_queries};
+use crate::domain::time_entry::{ProjectTimeReport, TimeEntry};
+use crate::error::{Error, Result};
+use crate::t;
+use chrono::{DateTime, Utc};
+use log::{debug, info};
+use rusqlite::Connection;
+use tauri::Emitter;
+
+/// Tauri 指令：为任务启动计时器。同一时间只能有一个正在运行的计时器。
+#[tauri::command]
+pub async fn start_timer(
+    task_id: i64,
+    note: Option<String>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<TimeEntry> {
+    debug!("[Command] start_timer, task_id: {}", task_id);
+    let entry = {
+        let conn = state.db.lock().unwrap();
+        if let Some(running) = time_entry_queries::get_running_entry(&conn)? {
+            let task = task_queries::get_task_by_id(&conn, running.task_id)?;
+            return Err(Error::Validation(t!(
+                "error-timer-already-running",
+                task = task.title
+            )));
+        }
+        time_entry_queries::start_timer(&conn, task_id, note.as_deref(), Utc::now())?
+    };
+    info!(
+        "[Command] 成功为任务 {} 启动计时器, 记录ID: {}",
+        task_id, entry.id
+    );
+    notify_timer_changed(&app_handle, Some(&entry))?;
+    Ok(entry)
+}
+
+/// Tauri 指令：停止正在运行的计时器，返回停止后的记录
+#[tauri::command]
+pub async fn stop_timer(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<TimeEntry> {
+    debug!("[Command] stop_timer");
+    let entry = {
+        let conn = state.db.lock().unwrap();
+        time_entry_queries::stop_running_entry(&conn, Utc::now())?
+            .ok_or_else(|| Error::Validation(t!("error-timer-not-running")))?
+    };
+    info!(
+        "[Command] 成功停止计时器, 记录ID: {}, 时长: {} 秒",
+        entry.id, entry.duration_seconds
+    );
+    notify_timer_changed(&app_handle, None)?;
+    Ok(entry)
+}
+
+/// Tauri 指令：获取正在运行的计时器（如果有的话）
+#[tauri::command]
+pub async fn get_running_timer(state: tauri::State<'_, AppState>) -> Result<Option<TimeEntry>> {
+    debug!("[Command] get_running_timer");
+    let conn = state.db.lock().unwrap();
+    Ok(time_entry_queries::get_running_entry(&conn)?)
+}
+
+/// Tauri 指令：获取任务的所有时间记录，最近的记录在前
+#[tauri::command]
+pub async fn get_time_entries_for_task(
+    task_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<TimeEntry>> {
+    debug!("[Command] get_time_entries_for_task, task_id: {}", task_id);
+    let conn = state.db.lock().unwrap();
+    Ok(time_entry_queries::get_time_entries_for_task(
+        &conn, task_id,
+    )?)
+}
+
+/// Tauri 指令：手动录入一条时间记录
+#[tauri::command]
+pub async fn create_time_entry(
+    task_id: i64,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    note: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<TimeEntry> {
+    debug!(
+        "[Command] create_time_entry, task_id: {}, started_at: {}, ended_at: {}",
+        task_id, started_at, ended_at
+    );
+    validate_range(started_at, Some(ended_at))?;
+    let conn = state.db.lock().unwrap();
+    ensure_no_overlap(&conn, started_at, Some(ended_at), None)?;
+    let entry = time_entry_queries::create_time_entry(
+        &conn,
+        task_id,
+        started_at,
+        ended_at,
+        note.as_deref(),
+    )?;
+    info!(
+        "[Command] 成功为任务 {} 录入时间记录, ID: {}",
+        task_id, entry.id
+    );
+    Ok(entry)
+}
+
+/// Tauri 指令：修改一条时间记录。
+/// 正在运行的记录可以通过设置结束时间来停止，但已结束的记录不能重新变为运行状态。
+#[tauri::command]
+pub async fn update_time_entry(
+    id: i64,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+    note: Option<String>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<TimeEntry> {
+    debug!(
+        "[Command] update_time_entry, id: {}, started_at: {}, ended_at: {:?}",
+        id, started_at, ended_at
+    );
+    validate_range(started_at, ended_at)?;
+    let entry = {
+        let conn = state.db.lock().unwrap();
+        let existing = time_entry_queries::get_time_entry_by_id(&conn, id)?;
+        // 已结束的记录不能重新变为运行状态，否则可能同时出现两个计时器
+        if existing.ended_at.is_some() && ended_at.is_none() {
+            return Err(Error::Validation(t!("error-time-entry-end-required")));
+        }
+        ensure_no_overlap(&conn, started_at, ended_at, Some(id))?;
+        time_entry_queries::update_time_entry(&conn, id, started_at, ended_at, note.as_deref())?
+    };
+    info!("[Command] 成功更新时间记录, ID: {}", id);
+    // 修改的可能是正在运行的计时器
+    tray::refresh_tooltip(&app_handle)?;
+    Ok(entry)
+}
+
+/// Tauri 指令：删除一条时间记录
+#[tauri::command]
+pub async fn delete_time_entry(
+    id: i64,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    debug!("[Command] delete_time_entry, id: {}", id);
+    {
+        let conn = state.db.lock().unwrap();
+        time_entry_queries::delete_time_entry(&conn, id)?;
+    }
+    info!("[Command] 成功删除时间记录, ID: {}", id);
+    tray::refresh_tooltip(&app_handle)?;
+    Ok(())
+}
+
+/// Tauri 指令：汇总项目在 `[from, to)` 范围内记录的工时，包含项目中任务的各级子任务
+#[tauri::command]
+pub async fn get_project_time_report(
+    project_id: i64,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    state: tauri::State<'_, AppState>,
+) -> Result<ProjectTimeReport> {
+    debug!(
+        "[Command] get_project_time_report, project_id: {}, from: {:?}, to: {:?}",
+        project_id, from, to
+    );
+    let conn = state.db.lock().unwrap();
+    let root_ids = task_queries::get_task_ids_by_project(&conn, project_id)?;
+    let task_ids = task_queries::get_subtree_task_ids(&conn, &root_ids)?;
+    let tasks =
+        time_entry_queries::get_tracked_time_by_task(&conn, &task_ids, from, to, Utc::now())?;
+    Ok(ProjectTimeReport {
+        project_id,
+        from,
+        to,
+        total_seconds: tasks.iter().map(|t| t.total_seconds).sum(),
+        tasks,
+    })
+}
+
+/// 内部辅助函数：校验时间记录的结束时间晚于开始时间
+fn validate_range(started_at: DateTime<Utc>, ended_at: Option<DateTime<Utc>>) -> Result<()> {
+    if ended_at.is_some_and(|ended_at| ended_at <= started_at) {
+        return Err(Error::Validation(t!("error-time-entry-invalid-range")));
+    }
+    Ok(())
+}
+
+/// 内部辅助函数：确保时间段不与其他时间记录重叠，否则同一段时间会被重复计算
+fn ensure_no_overlap(
+    conn: &Connection,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+    exclude_id: Option<i64>,
+) -> Result<()> {
+    if let Some(entry) = time_entry_queries::find_overlapping_entry(
+        conn,
+        started_at,
+        ended_at,
+        exclude_id,
+        Utc::now(),
+    )? {
+        let task = task_queries::get_task_by_id(conn, entry.task_id)?;
+        return Err(Error::Validation(t!(
+            "error-time-entry-overlap",
+            task = task.title
+        )));
+    }
+    Ok(())
+}
+
+/// 计时器启动或停止（包括随任务被删除）后，刷新托盘提示并通知前端
+pub(crate) fn notify_timer_changed(
+    app_handle: &tauri::AppHandle,
+    running: Option<&TimeEntry>,
+) -> Result<()> {
+    tray::refresh_tooltip(app_handle)?;
+    app_handle.emit("timer_changed", running)?;
+    Ok(())
+}
@@ -0,0 +1,249 @@
+use crate::app::commands::ensure_same_ids;
+use crate::app::settings;
+use crate::app::state::AppState;
+use crate::app::task_completion::{self, TASK_COMPLETION_SETTINGS_KEY};
+use crate::db::queries::{task_queries, workflow_status_queries};
+use crate::domain::settings::TaskCompletionSettings;
+use crate::domain::workflow_status::{StatusTasks, WorkflowStatus};
+use crate::error::{Error, Result};
+use crate::t;
+use log::{debug, info};
+use tauri::Emitter;
+
+/// Tauri 指令：获取项目实际使用的工作流状态，`project_id` 为空时获取全局状态
+#[tauri::command]
+pub async fn get_workflow_statuses(
+    project_id: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<WorkflowStatus>> {
+    debug!(
+        "[Command] get_workflow_statuses, project_id: {:?}",
+        project_id
+    );
+    let conn = state.db.lock().unwrap();
+    Ok(workflow_status_queries::get_statuses(&conn, project_id)?)
+}
+
+/// Tauri 指令：在状态集合的末尾创建一个新状态，`project_id` 为空时创建全局状态。
+/// 如果项目还在使用全局状态，会先把全局状态复制为项目自己的状态，再添加新状态。
+#[tauri::command]
+pub async fn create_workflow_status(
+    project_id: Option<i64>,
+    name: String,
+    counts_as_done: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<WorkflowStatus> {
+    debug!(
+        "[Command] create_workflow_status, project_id: {:?}, name: '{}', counts_as_done: {}",
+        project_id, name, counts_as_done
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    if let Some(project_id) = project_id {
+        if workflow_status_queries::get_status_owner(&tx, Some(project_id))?.is_none() {
+            workflow_status_queries::copy_global_statuses_to_project(&tx, project_id)?;
+        }
+    }
+    let statuses = workflow_status_queries::get_statuses(&tx, project_id)?;
+    ensure_unique_name(&statuses, &name, None)?;
+    let status = workflow_status_queries::create_status(&tx, project_id, &name, counts_as_done)?;
+    tx.commit()?;
+    info!(
+        "[Command] 成功创建工作流状态, ID: {}, 项目: {:?}",
+        status.id, project_id
+    );
+    Ok(status)
+}
+
+/// Tauri 指令：修改状态的名称和“视为已完成”标记。
+/// 标记改变时，处于该状态的任务会按完成联动设置一并完成或重新打开。
+#[tauri::command]
+pub async fn update_workflow_status(
+    id: i64,
+    name: String,
+    counts_as_done: bool,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<WorkflowStatus> {
+    debug!(
+        "[Command] update_workflow_status, id: {}, name: '{}', counts_as_done: {}",
+        id, name, counts_as_done
+    );
+    let completion_settings: TaskCompletionSettings =
+        settings::load(&app_handle, TASK_COMPLETION_SETTINGS_KEY)?;
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let existing = workflow_status_queries::get_status_by_id(&tx, id)?;
+    let mut statuses = workflow_status_queries::get_statuses(&tx, existing.project_id)?;
+    ensure_unique_name(&statuses, &name, Some(id))?;
+    for status in statuses.iter_mut().filter(|s| s.id == id) {
+        status.counts_as_done = counts_as_done;
+    }
+    ensure_open_and_done(&statuses)?;
+    let status = workflow_status_queries::update_status(&tx, id, &name, counts_as_done)?;
+    let unblocked_task_ids = if existing.counts_as_done != counts_as_done {
+        task_completion::sync_tasks_in_status(&tx, id, &status, &completion_settings)?
+    } else {
+        Vec::new()
+    };
+    tx.commit()?;
+
+    if !unblocked_task_ids.is_empty() {
+        app_handle.emit("tasks_unblocked", &unblocked_task_ids)?;
+    }
+    info!("[Command] 成功更新工作流状态, ID: {}", id);
+    Ok(status)
+}
+
+/// Tauri 指令：按给定的 ID 顺序重新排列状态集合。
+/// `status_ids` 必须恰好包含集合中的每个状态一次。
+#[tauri::command]
+pub async fn reorder_workflow_statuses(
+    project_id: Option<i64>,
+    status_ids: Vec<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<WorkflowStatus>> {
+    debug!(
+        "[Command] reorder_workflow_statuses, project_id: {:?}, status_ids: {:?}",
+        project_id, status_ids
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let owner = workflow_status_queries::get_status_owner(&tx, project_id)?;
+    let current = workflow_status_queries::get_statuses(&tx, project_id)?;
+    ensure_same_ids(
+        current.iter().map(|status| status.id).collect(),
+        &status_ids,
+    )?;
+    workflow_status_queries::reorder_statuses(&tx, owner, &status_ids)?;
+    let statuses = workflow_status_queries::get_statuses(&tx, project_id)?;
+    tx.commit()?;
+    info!("[Command] 成功调整工作流状态的顺序, 项目: {:?}", project_id);
+    Ok(statuses)
+}
+
+/// Tauri 指令：删除一个状态，返回删除后的状态集合。
+/// 处于该状态的任务会切换到 `replacement_status_id` 指定的状态；
+/// 未指定时，使用同一集合中第一个完成标记相同的状态。
+#[tauri::command]
+pub async fn delete_workflow_status(
+    id: i64,
+    replacement_status_id: Option<i64>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<WorkflowStatus>> {
+    debug!(
+        "[Command] delete_workflow_status, id: {}, replacement_status_id: {:?}",
+        id, replacement_status_id
+    );
+    let completion_settings: TaskCompletionSettings =
+        settings::load(&app_handle, TASK_COMPLETION_SETTINGS_KEY)?;
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    let status = workflow_status_queries::get_status_by_id(&tx, id)?;
+    let remaining: Vec<WorkflowStatus> =
+        workflow_status_queries::get_statuses(&tx, status.project_id)?
+            .into_iter()
+            .filter(|s| s.id != id)
+            .collect();
+    ensure_open_and_done(&remaining)?;
+
+    let replacement = match replacement_status_id {
+        Some(replacement_id) => remaining
+            .iter()
+            .find(|s| s.id == replacement_id)
+            .ok_or_else(|| Error::Validation(t!("error-status-replacement-invalid")))?,
+        None => remaining
+            .iter()
+            .find(|s| s.counts_as_done == status.counts_as_done)
+            .unwrap_or(&remaining[0]),
+    };
+    let unblocked_task_ids =
+        task_completion::sync_tasks_in_status(&tx, id, replacement, &completion_settings)?;
+    workflow_status_queries::delete_status(&tx, id)?;
+    tx.commit()?;
+
+    if !unblocked_task_ids.is_empty() {
+        app_handle.emit("tasks_unblocked", &unblocked_task_ids)?;
+    }
+    info!(
+        "[Command] 成功删除工作流状态, ID: {}, 任务已切换到状态 {}",
+        id, replacement.id
+    );
+    Ok(remaining)
+}
+
+/// Tauri 指令：删除项目自己的状态，让项目重新使用全局状态
+#[tauri::command]
+pub async fn reset_project_workflow_statuses(
+    project_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<WorkflowStatus>> {
+    debug!(
+        "[Command] reset_project_workflow_statuses, project_id: {}",
+        project_id
+    );
+    let mut conn = state.db.lock().unwrap();
+    let tx = conn.transaction()?;
+    workflow_status_queries::delete_statuses_for_project(&tx, project_id)?;
+    let statuses = workflow_status_queries::get_statuses(&tx, Some(project_id))?;
+    tx.commit()?;
+    info!("[Command] 项目 {} 已恢复使用全局工作流状态", project_id);
+    Ok(statuses)
+}
+
+/// Tauri 指令：获取项目（为空时为收件箱）下的顶级任务，并按工作流状态归类，用于看板视图。
+/// 每个状态都会返回一列，包括没有任务的空列。
+#[tauri::command]
+pub async fn get_tasks_grouped_by_status(
+    project_id: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<StatusTasks>> {
+    debug!(
+        "[Command] get_tasks_grouped_by_status, project_id: {:?}",
+        project_id
+    );
+    let conn = state.db.lock().unwrap();
+    let statuses = workflow_status_queries::get_statuses(&conn, project_id)?;
+    let tasks = task_queries::get_tasks_by_parent(&conn, project_id, None)?;
+
+    let mut columns: Vec<StatusTasks> = statuses
+        .into_iter()
+        .map(|status| StatusTasks {
+            status,
+            tasks: Vec::new(),
+        })
+        .collect();
+    for task in tasks {
+        if let Some(column) = columns.iter_mut().find(|c| c.status.id == task.status.id) {
+            column.tasks.push(task);
+        }
+    }
+    Ok(columns)
+}
+
+/// 内部辅助函数：确保状态集合中没有同名的状态
+fn ensure_unique_name(
+    statuses: &[WorkflowStatus],
+    name: &str,
+    exclude_id: Option<i64>,
+) -> Result<()> {
+    if statuses
+        .iter()
+        .any(|s| s.name == name && Some(s.id) != exclude_id)
+    {
+        return Err(Error::Validation(t!("error-status-name-duplicate")));
+    }
+    Ok(())
+}
+
+/// 内部辅助函数：确保状态集合中至少有一个未完成状态和一个视为已完成的状态，
+/// 否则无法通过“完成/重新打开”切换任务
+fn ensure_open_and_done(statuses: &[WorkflowStatus]) -> Result<()> {
+    let has_open = statuses.iter().any(|s| !s.counts_as_done);
+    let has_done = statuses.iter().any(|s| s.counts_as_done);
+    if !(has_open && has_done) {
+        return Err(Error::Validation(t!("error-status-set-incomplete")));
+    }
+    Ok(())
+}
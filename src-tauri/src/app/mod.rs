@@ -1,4 +1,17 @@
+pub mod attachment_health;
+pub mod attachment_payloads;
 pub mod commands;
+pub mod planning;
+pub mod pomodoro;
+pub mod project_stats;
 pub mod reminder_service;
+pub mod settings;
 pub mod setup;
 pub mod state;
+pub mod task_completion;
+pub mod task_duplication;
+pub mod templates;
+pub mod time_tracking;
+pub mod tray;
+pub mod url_attachments;
+pub mod vault;
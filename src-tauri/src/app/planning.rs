@@ -0,0 +1,155 @@
+use crate::db::queries::task_queries;
+use crate::domain::planning::DayPlan;
+use crate::domain::settings::PlanningSettings;
+use crate::domain::task::Task;
+use chrono::{Local, NaiveDate};
+use rusqlite::Connection;
+
+/// 工作量估算和每日计划的设置在 `settings.json` 中的键
+pub const PLANNING_SETTINGS_KEY: &str = "planning";
+
+/// 为 `date` 这一天制定计划
+pub fn plan_day(
+    conn: &Connection,
+    settings: &PlanningSettings,
+    date: NaiveDate,
+) -> rusqlite::Result<DayPlan> {
+    let candidates = task_queries::get_plannable_tasks(conn)?;
+    Ok(build_day_plan(candidates, settings, date))
+}
+
+/// 按顺序把候选任务放进当天的计划，直到用完每日容量。
+/// 只考虑与 `date` 相关的任务：当天到期、已经逾期，或没有截止日期的任务；截止日期在之后的任务不参与规划。
+/// 放不下的任务进入溢出列表，但之后更小的任务仍然可以填补剩余的容量。
+/// 尚未估算的任务按 `default_estimate` 计算。
+pub fn build_day_plan(
+    candidates: Vec<Task>,
+    settings: &PlanningSettings,
+    date: NaiveDate,
+) -> DayPlan {
+    let capacity = i64::from(settings.daily_capacity);
+    let default_estimate = i64::from(settings.default_estimate);
+    let mut planned_estimate = 0;
+    let mut planned = Vec::new();
+    let mut overflow = Vec::new();
+
+    for task in candidates.into_iter().filter(|t| is_relevant_on(t, date)) {
+        let estimate = task.estimate.unwrap_or(default_estimate);
+        if planned_estimate + estimate <= capacity {
+            planned_estimate += estimate;
+            planned.push(task);
+        } else {
+            overflow.push(task);
+        }
+    }
+
+    DayPlan {
+        date,
+        unit: settings.estimate_unit,
+        capacity,
+        planned_estimate,
+        planned,
+        overflow,
+    }
+}
+
+/// 内部辅助函数：任务是否与本地日期 `date` 相关，即没有截止日期或截止日期（按本地日期）不晚于 `date`
+fn is_relevant_on(task: &Task, date: NaiveDate) -> bool {
+    task.due_date
+        .is_none_or(|due| due.with_timezone(&Local).date_naive() <= date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::open_test_db;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    /// 创建一个带估算和截止日期的任务
+    fn task(
+        conn: &Connection,
+        title: &str,
+        estimate: Option<i64>,
+        due: Option<DateTime<Utc>>,
+    ) -> Task {
+        let task = task_queries::create_task(conn, title, None, None).unwrap();
+        task_queries::update_task_estimate(conn, task.id, estimate).unwrap();
+        task_queries::update_task_due_date(conn, task.id, due).unwrap();
+        task_queries::get_task_by_id(conn, task.id).unwrap()
+    }
+
+    /// `date` 这一天本地时间中午对应的 UTC 时间
+    fn local_noon(date: NaiveDate) -> DateTime<Utc> {
+        Local
+            .from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn fills_capacity_and_lets_smaller_tasks_use_the_rest() {
+        let conn = open_test_db();
+        let settings = PlanningSettings {
+            daily_capacity: 100,
+            default_estimate: 30,
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2030, 1, 2).unwrap();
+        let candidates = vec![
+            task(&conn, "a", Some(60), None),
+            task(&conn, "b", Some(50), None),
+            task(&conn, "c", None, None),
+            task(&conn, "d", Some(20), None),
+        ];
+
+        let plan = build_day_plan(candidates, &settings, date);
+        assert_eq!(titles(&plan.planned), ["a", "c"]);
+        assert_eq!(titles(&plan.overflow), ["b", "d"]);
+        assert_eq!((plan.planned_estimate, plan.capacity), (90, 100));
+    }
+
+    #[test]
+    fn only_plans_tasks_relevant_on_the_date() {
+        let conn = open_test_db();
+        let date = NaiveDate::from_ymd_opt(2030, 1, 2).unwrap();
+        let candidates = vec![
+            task(
+                &conn,
+                "overdue",
+                Some(10),
+                Some(local_noon(date.pred_opt().unwrap())),
+            ),
+            task(&conn, "due", Some(10), Some(local_noon(date))),
+            task(
+                &conn,
+                "later",
+                Some(10),
+                Some(local_noon(date.succ_opt().unwrap())),
+            ),
+            task(&conn, "undated", Some(10), None),
+        ];
+
+        let plan = build_day_plan(candidates, &PlanningSettings::default(), date);
+        assert_eq!(titles(&plan.planned), ["overdue", "due", "undated"]);
+        assert!(plan.overflow.is_empty());
+    }
+
+    #[test]
+    fn plan_day_skips_completed_and_blocked_tasks() {
+        let conn = open_test_db();
+        let date = NaiveDate::from_ymd_opt(2030, 1, 2).unwrap();
+        let done = task(&conn, "done", Some(10), None);
+        task_queries::update_task_status(&conn, done.id, true).unwrap();
+        let blocker = task(&conn, "blocker", Some(10), None);
+        let blocked = task(&conn, "blocked", Some(10), None);
+        crate::db::queries::dependency_queries::add_dependency(&conn, blocked.id, blocker.id)
+            .unwrap();
+
+        let plan = plan_day(&conn, &PlanningSettings::default(), date).unwrap();
+        assert_eq!(titles(&plan.planned), ["blocker"]);
+    }
+}
@@ -0,0 +1,332 @@
+use crate::app::reminder_service;
+use crate::app::settings;
+use crate::app::state::AppState;
+use crate::db::queries::pomodoro_queries;
+use crate::domain::pomodoro::{PomodoroPhase, PomodoroState};
+use crate::domain::settings::PomodoroSettings;
+use crate::t;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use log::{debug, error, info};
+use std::{thread, time::Duration};
+use tauri::{Emitter, Manager};
+
+/// 番茄钟设置在 `settings.json` 中的键
+pub const POMODORO_SETTINGS_KEY: &str = "pomodoro";
+
+/// 后台推进番茄钟的间隔，也是 `pomodoro_tick` 事件的频率
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 启动后台番茄钟服务：每秒推进一次正在进行的番茄钟
+pub fn start(app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        info!("[Pomodoro] 番茄钟服务已启动。");
+        loop {
+            thread::sleep(TICK_INTERVAL);
+            run_tick(&app_handle, Utc::now());
+        }
+    });
+}
+
+/// 推进一次番茄钟：未结束时广播 `pomodoro_tick`，阶段结束时切换到下一阶段
+fn run_tick(app_handle: &tauri::AppHandle, now: DateTime<Utc>) {
+    let state = app_handle.state::<AppState>();
+    if state.pomodoro.lock().unwrap().is_none() {
+        return;
+    }
+    let pomodoro_settings: PomodoroSettings = settings::load(app_handle, POMODORO_SETTINGS_KEY)
+        .unwrap_or_else(|e| {
+            error!("[Pomodoro] 读取番茄钟设置时出错: {}", e);
+            PomodoroSettings::default()
+        });
+
+    let mut guard = state.pomodoro.lock().unwrap();
+    let Some(session) = guard.as_mut() else {
+        return;
+    };
+    if !tick(session, now) {
+        let snapshot = session.clone();
+        drop(guard);
+        if !snapshot.is_paused {
+            if let Err(e) = app_handle.emit("pomodoro_tick", &snapshot) {
+                error!("[Pomodoro] 广播 pomodoro_tick 事件时出错: {}", e);
+            }
+        }
+        return;
+    }
+
+    let finished = session.clone();
+    *guard = next_phase(&finished, &pomodoro_settings, now, true);
+    let next = guard.clone();
+    drop(guard);
+    on_phase_finished(app_handle, &finished, next.as_ref(), true, now);
+}
+
+/// 一个阶段结束（或被跳过）后：记录完成的专注时段、发送通知，并广播 `pomodoro_phase_changed`。
+/// 调用方需要先释放番茄钟的锁。
+pub fn on_phase_finished(
+    app_handle: &tauri::AppHandle,
+    finished: &PomodoroState,
+    next: Option<&PomodoroState>,
+    completed: bool,
+    now: DateTime<Utc>,
+) {
+    info!(
+        "[Pomodoro] 阶段 {:?} 已{}, 下一阶段: {:?}",
+        finished.phase,
+        if completed { "完成" } else { "跳过" },
+        next.map(|n| n.phase)
+    );
+
+    if completed {
+        if finished.phase == PomodoroPhase::Work {
+            let state = app_handle.state::<AppState>();
+            let conn = state.db.lock().unwrap();
+            if let Err(e) = pomodoro_queries::record_pomodoro(
+                &conn,
+                finished.task_id,
+                finished.phase_started_at,
+                now,
+                finished.phase_seconds,
+            ) {
+                error!("[Pomodoro] 记录专注时段时出错: {}", e);
+            }
+        }
+        notify_phase_change(app_handle, finished, next);
+    }
+
+    if let Err(e) = app_handle.emit("pomodoro_phase_changed", next) {
+        error!("[Pomodoro] 广播 pomodoro_phase_changed 事件时出错: {}", e);
+    }
+}
+
+/// 内部辅助函数：阶段正常结束时发送系统通知
+fn notify_phase_change(
+    app_handle: &tauri::AppHandle,
+    finished: &PomodoroState,
+    next: Option<&PomodoroState>,
+) {
+    let (title, body) = match (finished.phase, next) {
+        (PomodoroPhase::Work, Some(next)) => (
+            t!("notification-pomodoro-break-title"),
+            t!(
+                "notification-pomodoro-break-body",
+                minutes = next.phase_seconds / 60
+            ),
+        ),
+        (_, Some(next)) => (
+            t!("notification-pomodoro-work-title"),
+            t!(
+                "notification-pomodoro-work-body",
+                minutes = next.phase_seconds / 60
+            ),
+        ),
+        (_, None) => (
+            t!("notification-pomodoro-finished-title"),
+            t!("notification-pomodoro-finished-body"),
+        ),
+    };
+    if let Err(e) = reminder_service::notify(app_handle, title, body, false) {
+        error!("[Pomodoro] 发送番茄钟通知失败: {}", e);
+    }
+}
+
+/// 开始一轮新的番茄钟，从专注阶段开始
+pub fn start_session(
+    task_id: Option<i64>,
+    settings: &PomodoroSettings,
+    now: DateTime<Utc>,
+) -> PomodoroState {
+    enter_phase(task_id, PomodoroPhase::Work, 0, settings, now)
+}
+
+/// 计算当前阶段结束后的下一阶段。
+/// `completed` 为 `false` 表示阶段被跳过，跳过的专注时段不计入本轮的完成数量。
+/// 休息结束且未开启“自动开始专注”时返回 `None`，表示这一轮番茄钟结束。
+pub fn next_phase(
+    current: &PomodoroState,
+    settings: &PomodoroSettings,
+    now: DateTime<Utc>,
+    completed: bool,
+) -> Option<PomodoroState> {
+    match current.phase {
+        PomodoroPhase::Work => {
+            let done = current.completed_work_sessions + u32::from(completed);
+            let long_break = completed
+                && settings.long_break_interval > 0
+                && done.is_multiple_of(settings.long_break_interval);
+            let phase = if long_break {
+                PomodoroPhase::LongBreak
+            } else {
+                PomodoroPhase::ShortBreak
+            };
+            Some(enter_phase(current.task_id, phase, done, settings, now))
+        }
+        PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak => {
+            settings.auto_start_work.then(|| {
+                enter_phase(
+                    current.task_id,
+                    PomodoroPhase::Work,
+                    current.completed_work_sessions,
+                    settings,
+                    now,
+                )
+            })
+        }
+    }
+}
+
+/// 更新剩余时间，返回当前阶段是否已经结束。暂停中的番茄钟不会前进。
+pub fn tick(state: &mut PomodoroState, now: DateTime<Utc>) -> bool {
+    if state.is_paused {
+        return false;
+    }
+    state.remaining_seconds = (state.phase_ends_at - now).num_seconds().max(0);
+    now >= state.phase_ends_at
+}
+
+/// 暂停番茄钟，保留剩余时间
+pub fn pause(state: &mut PomodoroState, now: DateTime<Utc>) {
+    if !state.is_paused {
+        tick(state, now);
+        state.is_paused = true;
+        debug!("[Pomodoro] 已暂停, 剩余 {} 秒", state.remaining_seconds);
+    }
+}
+
+/// 继续已暂停的番茄钟，结束时间按剩余时间顺延
+pub fn resume(state: &mut PomodoroState, now: DateTime<Utc>) {
+    if state.is_paused {
+        state.phase_ends_at = now + ChronoDuration::seconds(state.remaining_seconds);
+        state.is_paused = false;
+        debug!("[Pomodoro] 已继续, 将于 {} 结束", state.phase_ends_at);
+    }
+}
+
+/// 内部辅助函数：进入一个新阶段
+fn enter_phase(
+    task_id: Option<i64>,
+    phase: PomodoroPhase,
+    completed_work_sessions: u32,
+    settings: &PomodoroSettings,
+    now: DateTime<Utc>,
+) -> PomodoroState {
+    let minutes = match phase {
+        PomodoroPhase::Work => settings.work_minutes,
+        PomodoroPhase::ShortBreak => settings.short_break_minutes,
+        PomodoroPhase::LongBreak => settings.long_break_minutes,
+    };
+    let phase_seconds = i64::from(minutes) * 60;
+    PomodoroState {
+        task_id,
+        phase,
+        phase_started_at: now,
+        phase_ends_at: now + ChronoDuration::seconds(phase_seconds),
+        remaining_seconds: phase_seconds,
+        phase_seconds,
+        is_paused: false,
+        completed_work_sessions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    /// 依次完成阶段，返回经过的阶段序列
+    fn run_phases(settings: &PomodoroSettings, count: usize) -> Vec<PomodoroPhase> {
+        let mut state = start_session(None, settings, at(0));
+        let mut phases = vec![state.phase];
+        for _ in 1..count {
+            let now = state.phase_ends_at;
+            assert!(tick(&mut state, now));
+            state = next_phase(&state, settings, now, true).unwrap();
+            phases.push(state.phase);
+        }
+        phases
+    }
+
+    #[test]
+    fn long_break_follows_every_nth_work_session() {
+        use PomodoroPhase::*;
+        let settings = PomodoroSettings {
+            long_break_interval: 2,
+            auto_start_work: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            run_phases(&settings, 8),
+            vec![Work, ShortBreak, Work, LongBreak, Work, ShortBreak, Work, LongBreak]
+        );
+
+        // 跳过的专注时段不计数
+        let work = start_session(None, &settings, at(0));
+        let skipped = next_phase(&work, &settings, at(10), false).unwrap();
+        assert_eq!(
+            (skipped.phase, skipped.completed_work_sessions),
+            (ShortBreak, 0)
+        );
+
+        // 间隔为 0 时不进入长休息
+        let settings = PomodoroSettings {
+            long_break_interval: 0,
+            auto_start_work: true,
+            ..Default::default()
+        };
+        assert!(!run_phases(&settings, 8).contains(&LongBreak));
+    }
+
+    #[test]
+    fn break_ends_the_session_unless_work_starts_automatically() {
+        let settings = PomodoroSettings::default();
+        let work = start_session(Some(7), &settings, at(0));
+        let brk = next_phase(&work, &settings, work.phase_ends_at, true).unwrap();
+        assert_eq!(brk.phase, PomodoroPhase::ShortBreak);
+        assert_eq!(brk.task_id, Some(7));
+        assert_eq!(brk.phase_seconds, 5 * 60);
+        assert_eq!(brk.completed_work_sessions, 1);
+        assert_eq!(next_phase(&brk, &settings, brk.phase_ends_at, true), None);
+    }
+
+    #[test]
+    fn pause_keeps_the_remaining_time_until_resumed() {
+        let settings = PomodoroSettings::default();
+        let mut state = start_session(None, &settings, at(0));
+
+        assert!(!tick(&mut state, at(60)));
+        assert_eq!(state.remaining_seconds, 24 * 60);
+
+        pause(&mut state, at(100));
+        assert!(state.is_paused);
+        assert_eq!(state.remaining_seconds, 1400);
+        // 暂停期间时间不前进，即使超过了原定的结束时间
+        assert!(!tick(&mut state, at(10_000)));
+        assert_eq!(state.remaining_seconds, 1400);
+
+        resume(&mut state, at(10_000));
+        assert!(!state.is_paused);
+        assert_eq!(state.phase_ends_at, at(11_400));
+        assert!(!tick(&mut state, at(11_399)));
+        assert_eq!(state.remaining_seconds, 1);
+    }
+
+    #[test]
+    fn tick_reports_the_end_of_the_phase_once_time_runs_out() {
+        let settings = PomodoroSettings::default();
+        let mut state = start_session(None, &settings, at(0));
+        assert!(!tick(&mut state, at(25 * 60 - 1)));
+        assert!(tick(&mut state, at(25 * 60)));
+        assert_eq!(state.remaining_seconds, 0);
+        // 超时后剩余时间不会变成负数
+        assert!(tick(&mut state, at(25 * 60 + 30)));
+        assert_eq!(state.remaining_seconds, 0);
+
+        let next = next_phase(&state, &settings, at(25 * 60 + 30), true).unwrap();
+        assert_eq!(next.phase_started_at, at(25 * 60 + 30));
+        assert_eq!(next.remaining_seconds, next.phase_seconds);
+        assert!(!next.is_paused);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::db::queries::task_queries;
+use crate::domain::project::{ProjectStats, WeeklyCompletion};
+use crate::domain::task::Task;
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, Utc};
+use rusqlite::Connection;
+
+/// 统计每周完成数量时覆盖的周数（包含本周）
+const STATS_WEEKS: u64 = 12;
+
+/// 计算项目的统计数据，统计范围包含项目中任务的各级子任务
+pub fn get_project_stats(
+    conn: &Connection,
+    project_id: i64,
+    now: DateTime<Utc>,
+) -> rusqlite::Result<ProjectStats> {
+    let root_ids = task_queries::get_task_ids_by_project(conn, project_id)?;
+    let task_ids = task_queries::get_subtree_task_ids(conn, &root_ids)?;
+    let tasks = task_queries::get_tasks_by_ids(conn, &task_ids)?;
+    let today = now.with_timezone(&Local).date_naive();
+    Ok(build_project_stats(project_id, &tasks, now, today))
+}
+
+/// 根据任务列表计算统计数据。
+/// `created_at` 和 `completed_at` 存储的是本地时间，因此按本地日期 `today` 划分每周的统计。
+pub fn build_project_stats(
+    project_id: i64,
+    tasks: &[Task],
+    now: DateTime<Utc>,
+    today: NaiveDate,
+) -> ProjectStats {
+    let total_count = tasks.len() as i64;
+    let completed: Vec<&Task> = tasks.iter().filter(|t| t.is_completed).collect();
+    let completed_count = completed.len() as i64;
+    let overdue_count = tasks
+        .iter()
+        .filter(|t| !t.is_completed && t.due_date.is_some_and(|due| due < now))
+        .count() as i64;
+
+    // 最近 STATS_WEEKS 周（周一为每周的第一天），不足的周计为 0
+    let this_week_start = today - Days::new(today.weekday().num_days_from_monday() as u64);
+    let first_week_start = this_week_start - Days::new(7 * (STATS_WEEKS - 1));
+    let mut completed_per_week: Vec<WeeklyCompletion> = (0..STATS_WEEKS)
+        .map(|week| WeeklyCompletion {
+            week_start: first_week_start + Days::new(7 * week),
+            completed_count: 0,
+        })
+        .collect();
+    for completed_at in completed.iter().filter_map(|t| t.completed_at) {
+        let date = completed_at.date_naive();
+        if date < first_week_start || date > today {
+            continue;
+        }
+        let week = (date - first_week_start).num_days() / 7;
+        completed_per_week[week as usize].completed_count += 1;
+    }
+
+    let durations: Vec<f64> = completed
+        .iter()
+        .filter_map(|t| t.completed_at.map(|done| done - t.created_at))
+        .filter(|duration| duration.num_seconds() >= 0)
+        .map(|duration| duration.num_seconds() as f64 / 3600.0)
+        .collect();
+    let average_completion_hours =
+        (!durations.is_empty()).then(|| durations.iter().sum::<f64>() / durations.len() as f64);
+
+    ProjectStats {
+        project_id,
+        total_count,
+        completed_count,
+        open_count: total_count - completed_count,
+        overdue_count,
+        completion_percentage: if total_count == 0 {
+            0.0
+        } else {
+            completed_count as f64 * 100.0 / total_count as f64
+        },
+        completed_per_week,
+        average_completion_hours,
+        pomodoro_count: tasks.iter().map(|t| t.pomodoro_count).sum(),
+        tracked_seconds: tasks.iter().map(|t| t.tracked_seconds).sum(),
+        // `tasks` 已经包含各级子任务，因此只累加每个任务本身的估算
+        total_estimate: tasks.iter().filter_map(|t| t.estimate).sum(),
+        remaining_estimate: tasks
+            .iter()
+            .filter(|t| !t.is_completed)
+            .filter_map(|t| t.estimate)
+            .sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::{project_queries, time_entry_queries};
+    use crate::db::test_support::open_test_db;
+    use chrono::Duration;
+
+    #[test]
+    fn empty_project_has_zero_completion() {
+        let conn = open_test_db();
+        let project = project_queries::create_project(&conn, "Empty", None).unwrap();
+
+        let stats = get_project_stats(&conn, project.id, Utc::now()).unwrap();
+
+        assert_eq!(stats.total_count, 0);
+        assert_eq!(stats.completion_percentage, 0.0);
+        assert_eq!(stats.average_completion_hours, None);
+        assert_eq!(stats.completed_per_week.len(), STATS_WEEKS as usize);
+    }
+
+    #[test]
+    fn stats_cover_subtasks_overdue_tasks_and_logged_time() {
+        let conn = open_test_db();
+        let now = Utc::now();
+        let project = project_queries::create_project(&conn, "Project", None).unwrap();
+        let other = project_queries::create_project(&conn, "Other", None).unwrap();
+        let parent = task_queries::create_task(&conn, "parent", Some(project.id), None).unwrap();
+        let child = task_queries::create_task(&conn, "child", None, Some(parent.id)).unwrap();
+        let done = task_queries::create_task(&conn, "done", Some(project.id), None).unwrap();
+        let outside = task_queries::create_task(&conn, "outside", Some(other.id), None).unwrap();
+
+        // 未完成且已过期的任务才计入过期数量
+        let yesterday = Some(now - Duration::days(1));
+        task_queries::update_task_due_date(&conn, child.id, yesterday).unwrap();
+        task_queries::update_task_due_date(&conn, done.id, yesterday).unwrap();
+        task_queries::update_task_due_date(&conn, parent.id, Some(now + Duration::days(1)))
+            .unwrap();
+        task_queries::update_task_due_date(&conn, outside.id, yesterday).unwrap();
+        task_queries::update_task_status(&conn, done.id, true).unwrap();
+
+        for (task_id, minutes) in [(parent.id, 30), (child.id, 15), (outside.id, 60)] {
+            time_entry_queries::create_time_entry(
+                &conn,
+                task_id,
+                now - Duration::minutes(minutes),
+                now,
+                None,
+            )
+            .unwrap();
+        }
+
+        let stats = get_project_stats(&conn, project.id, now).unwrap();
+
+        assert_eq!(stats.total_count, 3);
+        assert_eq!(stats.completed_count, 1);
+        assert_eq!(stats.open_count, 2);
+        assert_eq!(stats.overdue_count, 1);
+        assert!((stats.completion_percentage - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats.tracked_seconds, 45 * 60);
+        let completed_this_week: i64 = stats
+            .completed_per_week
+            .iter()
+            .map(|w| w.completed_count)
+            .sum();
+        assert_eq!(completed_this_week, 1);
+        assert_eq!(stats.completed_per_week.last().unwrap().completed_count, 1);
+    }
+}
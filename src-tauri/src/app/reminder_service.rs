@@ -1,16 +1,42 @@
+use crate::app::settings;
 use crate::app::state::AppState;
-use crate::db::queries::{reminder_queries, task_queries};
+use crate::db::queries::{project_queries, reminder_queries, task_queries};
+use crate::domain::digest::{DailyDigest, DigestProjectGroup};
+use crate::domain::priority::Priority;
+use crate::domain::reminder::Reminder;
+use crate::domain::settings::{DigestSettings, QuietHoursSettings};
+use crate::domain::time::local_to_utc;
+use crate::t;
+use chrono::{DateTime, Days, Local, NaiveDate, NaiveTime, Utc};
 use log::{debug, error, info};
+use rusqlite::Connection;
+use std::collections::HashMap;
 use std::{thread, time::Duration};
 use tauri::{Emitter, Manager};
 use tauri_plugin_notification::NotificationExt;
 
+/// 汇总通知中最多列出的任务标题数量
+const MISSED_SUMMARY_TITLE_LIMIT: usize = 3;
+/// 启动时，到期不超过这么多分钟的提醒仍然正常发送，而不是记为错过
+const MISSED_GRACE_MINUTES: i64 = 5;
+
+/// 每日概览设置在 `settings.json` 中的键
+pub const DIGEST_SETTINGS_KEY: &str = "dailyDigest";
+/// 免打扰设置在 `settings.json` 中的键
+pub const QUIET_HOURS_SETTINGS_KEY: &str = "quietHours";
+/// 最近一次发送每日概览的本地日期，用于保证每天只发送一次（重启后依然有效）
+const DIGEST_LAST_SENT_KEY: &str = "dailyDigestLastSent";
+
 /// 启动后台提醒轮询服务
 pub fn start(app_handle: tauri::AppHandle) {
     // 创建一个新线程，防止阻塞 Tauri 的主线程
     thread::spawn(move || {
         info!("[ReminderService] 后台提醒服务已启动，每 60 秒轮询一次。");
 
+        // --- 0. 处理应用关闭期间错过的提醒 ---
+        // 启动时已经到期的提醒不再逐条弹出，而是汇总为一条通知
+        catch_up_missed_reminders(&app_handle);
+
         // 这是一个无限循环，代表服务在持续运行
         loop {
             // --- 1. 暂停一分钟 ---
@@ -36,30 +62,50 @@ pub fn start(app_handle: tauri::AppHandle) {
                 let mut sent_ids = Vec::new();
                 // 被提醒的任务id
                 let mut affected_task_ids = Vec::new();
+                // 因免打扰而推迟的提醒id
+                let mut deferred_ids = Vec::new();
 
                 // --- 4. 遍历提醒并发送通知 ---
                 for reminder in reminders {
                     // 获取提醒关联的任务，以在通知中显示任务标题
                     if let Ok(task) = task_queries::get_task_by_id(&conn_guard, reminder.task_id) {
-                        // 使用 tauri-plugin-notification 构建和发送通知
-                        let notification_result = app_handle
-                            .notification()
-                            .builder()
-                            .title("Momentum 任务提醒")
-                            .body(format!("任务 '{}' 即将到期！", task.title))
-                            .show();
-
-                        if let Ok(_) = notification_result {
-                            // 如果通知发送成功，记录其 ID
-                            sent_ids.push(reminder.id);
-                            // 记录任务id
-                            affected_task_ids.push(task.id);
-                            info!(
-                                "[ReminderService] 任务 '{}' (ID: {}) 的提醒已发送。",
-                                task.title, task.id
-                            );
-                        } else {
-                            error!("[ReminderService] 发送提醒失败, 提醒ID: {}", reminder.id);
+                        // 处于免打扰时段时推迟提醒，除非允许高优先级任务突破
+                        match notify(
+                            &app_handle,
+                            t!("notification-reminder-title"),
+                            t!("notification-reminder-body", title = task.title),
+                            task.priority == Priority::High,
+                        ) {
+                            Ok(true) => {
+                                // 如果通知发送成功，记录其 ID
+                                sent_ids.push(reminder.id);
+                                // 记录任务id
+                                affected_task_ids.push(task.id);
+                                info!(
+                                    "[ReminderService] 任务 '{}' (ID: {}) 的提醒已发送。",
+                                    task.title, task.id
+                                );
+                            }
+                            Ok(false) => deferred_ids.push(reminder.id),
+                            Err(e) => error!(
+                                "[ReminderService] 发送提醒失败, 提醒ID: {}, 错误: {}",
+                                reminder.id, e
+                            ),
+                        }
+                    }
+                }
+
+                // --- 4b. 记录被推迟的提醒，它们会在免打扰时段结束后再次到期 ---
+                if !deferred_ids.is_empty() {
+                    if let Some(until) =
+                        load_quiet_settings(&app_handle).active_window_end(Utc::now())
+                    {
+                        match reminder_queries::defer_reminders(&conn_guard, &deferred_ids, until) {
+                            Ok(count) => info!(
+                                "[ReminderService] 免打扰中，{} 个提醒已推迟到 {}。",
+                                count, until
+                            ),
+                            Err(e) => error!("[ReminderService] 推迟提醒时出错: {}", e),
                         }
                     }
                 }
@@ -84,6 +130,359 @@ pub fn start(app_handle: tauri::AppHandle) {
                     }
                 }
             }
+            // 尽早释放数据库锁，每日概览会按需重新获取
+            drop(conn_guard);
+
+            // --- 6. 检查是否到了发送每日概览的时间 ---
+            maybe_send_daily_digest(&app_handle);
         }
     });
 }
+
+/// 将启动时已经到期超过宽限时间的提醒标记为“错过”，并发送一条汇总通知
+fn catch_up_missed_reminders(app_handle: &tauri::AppHandle) {
+    let state = app_handle.state::<AppState>();
+    let conn_guard = state.db.lock().unwrap();
+
+    let reminders = match take_missed_reminders(&conn_guard, Utc::now()) {
+        Ok(reminders) => reminders,
+        Err(e) => {
+            error!("[ReminderService] 处理错过的提醒时出错: {}", e);
+            return;
+        }
+    };
+    if reminders.is_empty() {
+        return;
+    }
+    info!(
+        "[ReminderService] 发现 {} 个在应用关闭期间错过的提醒。",
+        reminders.len()
+    );
+    let body = missed_summary_body(&conn_guard, &reminders);
+
+    // 免打扰时段内不弹出汇总通知，错过的提醒仍会通过事件出现在收件箱中
+    if let Err(e) = notify(app_handle, t!("notification-missed-title"), body, false) {
+        error!("[ReminderService] 发送错过提醒的汇总通知失败: {}", e);
+    }
+
+    // 广播 reminders_missed 事件，payload 为所有被标记为错过的提醒
+    if let Err(e) = app_handle.emit("reminders_missed", &reminders) {
+        error!("[ReminderService] 广播 reminders_missed 事件时出错: {}", e);
+    }
+}
+
+/// 查询在 `now` 之前已经到期超过宽限时间的提醒，并将它们标记为“错过”。
+/// 被标记的提醒不会再被返回，因此每个错过的提醒只会处理一次。
+fn take_missed_reminders(conn: &Connection, now: DateTime<Utc>) -> rusqlite::Result<Vec<Reminder>> {
+    // 刚刚到期（例如在应用启动过程中到期）的提醒留给轮询正常发送
+    let cutoff = now - chrono::Duration::minutes(MISSED_GRACE_MINUTES);
+    let reminders: Vec<Reminder> = reminder_queries::get_due_reminders(conn)?
+        .into_iter()
+        .filter(|r| r.deferred_until.unwrap_or(r.remind_at).max(r.remind_at) <= cutoff)
+        .collect();
+    let missed_ids: Vec<i64> = reminders.iter().map(|r| r.id).collect();
+    reminder_queries::mark_reminders_as_missed(conn, &missed_ids)?;
+    Ok(reminders)
+}
+
+/// 内部辅助函数：生成错过提醒的汇总通知正文
+fn missed_summary_body(conn: &Connection, reminders: &[Reminder]) -> String {
+    // 同一个任务可能有多个提醒，汇总时只列出一次
+    let mut titles: Vec<String> = Vec::new();
+    for reminder in reminders {
+        if let Ok(task) = task_queries::get_task_by_id(conn, reminder.task_id) {
+            if !titles.contains(&task.title) {
+                titles.push(task.title);
+            }
+        }
+    }
+    // 每个任务标题单独一行，超出上限的部分只显示数量
+    let mut body = t!("notification-missed-body", count = reminders.len());
+    for title in titles.iter().take(MISSED_SUMMARY_TITLE_LIMIT) {
+        body.push_str(&format!("\n• {}", title));
+    }
+    if titles.len() > MISSED_SUMMARY_TITLE_LIMIT {
+        body.push('\n');
+        body.push_str(&t!(
+            "notification-missed-more",
+            count = titles.len() - MISSED_SUMMARY_TITLE_LIMIT
+        ));
+    }
+    body
+}
+
+/// 如果已到设定的时间、今天不是休息日且今天尚未发送过，则发送每日概览
+fn maybe_send_daily_digest(app_handle: &tauri::AppHandle) {
+    let digest_settings: DigestSettings = match settings::load(app_handle, DIGEST_SETTINGS_KEY) {
+        Ok(digest_settings) => digest_settings,
+        Err(e) => {
+            error!("[ReminderService] 读取每日概览设置时出错: {}", e);
+            return;
+        }
+    };
+    if !digest_settings.enabled {
+        return;
+    }
+
+    let now = Local::now();
+    let today = now.date_naive();
+    if digest_settings.is_day_off(today) || now.time() < digest_settings.time {
+        return;
+    }
+    let last_sent: Option<NaiveDate> =
+        settings::load(app_handle, DIGEST_LAST_SENT_KEY).unwrap_or_default();
+    if last_sent == Some(today) {
+        return;
+    }
+
+    let digest = {
+        let state = app_handle.state::<AppState>();
+        let conn_guard = state.db.lock().unwrap();
+        match build_daily_digest(&conn_guard, today) {
+            Ok(digest) => digest,
+            Err(e) => {
+                error!("[ReminderService] 生成每日概览时出错: {}", e);
+                return;
+            }
+        }
+    };
+
+    // 没有需要关注的任务时不打扰用户，但仍然广播事件并记为已发送
+    if !digest.is_empty() {
+        match notify(
+            app_handle,
+            t!("notification-digest-title"),
+            t!(
+                "notification-digest-body",
+                due_today = digest.due_today_count,
+                overdue = digest.overdue_count,
+                high_priority = digest.high_priority_count
+            ),
+            false,
+        ) {
+            Ok(true) => {}
+            // 免打扰时段内先不发送，也不记为已发送，时段结束后的轮询会再次尝试
+            Ok(false) => {
+                debug!("[ReminderService] 免打扰中，每日概览推迟发送。");
+                return;
+            }
+            Err(e) => error!("[ReminderService] 发送每日概览通知失败: {}", e),
+        }
+    }
+
+    // 广播 daily_digest 事件，payload 为完整的概览内容
+    if let Err(e) = app_handle.emit("daily_digest", &digest) {
+        error!("[ReminderService] 广播 daily_digest 事件时出错: {}", e);
+    }
+    if let Err(e) = settings::save(app_handle, DIGEST_LAST_SENT_KEY, &today) {
+        error!("[ReminderService] 记录每日概览发送日期时出错: {}", e);
+    }
+    info!("[ReminderService] 已发送 {} 的每日概览。", today);
+}
+
+/// 发送一条系统通知，所有后台通知都应经过这里。
+/// 处于安静时段或勿扰模式时不发送并返回 `Ok(false)`；`urgent` 的通知在设置允许高优先级突破时照常发送。
+pub(crate) fn notify(
+    app_handle: &tauri::AppHandle,
+    title: String,
+    body: String,
+    urgent: bool,
+) -> tauri_plugin_notification::Result<bool> {
+    let quiet_settings = load_quiet_settings(app_handle);
+    let breaks_through = urgent && quiet_settings.allow_high_priority;
+    if quiet_settings.active_window_end(Utc::now()).is_some() && !breaks_through {
+        return Ok(false);
+    }
+    app_handle
+        .notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()?;
+    Ok(true)
+}
+
+/// 内部辅助函数：读取免打扰设置。
+/// 读取失败时按“未开启免打扰”处理，宁可打扰也不要漏掉提醒。
+fn load_quiet_settings(app_handle: &tauri::AppHandle) -> QuietHoursSettings {
+    settings::load(app_handle, QUIET_HOURS_SETTINGS_KEY).unwrap_or_else(|e| {
+        error!("[ReminderService] 读取免打扰设置时出错: {}", e);
+        QuietHoursSettings::default()
+    })
+}
+
+/// 生成指定本地日期的每日概览
+pub fn build_daily_digest(conn: &Connection, date: NaiveDate) -> rusqlite::Result<DailyDigest> {
+    let day_start = local_to_utc(date, NaiveTime::MIN);
+    let day_end = local_to_utc(date + Days::new(1), NaiveTime::MIN);
+
+    let project_names: HashMap<i64, String> = project_queries::get_all_projects(conn, false)?
+        .into_iter()
+        .map(|project| (project.id, project.name))
+        .collect();
+
+    let mut digest = DailyDigest {
+        date,
+        due_today_count: 0,
+        overdue_count: 0,
+        high_priority_count: 0,
+        projects: Vec::new(),
+    };
+
+    for task in task_queries::get_open_tasks_due_before_or_high_priority(conn, day_end)? {
+        let index = match digest
+            .projects
+            .iter()
+            .position(|group| group.project_id == task.project_id)
+        {
+            Some(index) => index,
+            None => {
+                digest.projects.push(DigestProjectGroup {
+                    project_id: task.project_id,
+                    project_name: task
+                        .project_id
+                        .and_then(|id| project_names.get(&id).cloned()),
+                    due_today: Vec::new(),
+                    overdue: Vec::new(),
+                    high_priority: Vec::new(),
+                });
+                digest.projects.len() - 1
+            }
+        };
+        let group = &mut digest.projects[index];
+
+        match task.due_date {
+            Some(due) if due < day_start => {
+                digest.overdue_count += 1;
+                group.overdue.push(task);
+            }
+            Some(due) if due < day_end => {
+                digest.due_today_count += 1;
+                group.due_today.push(task);
+            }
+            _ => {
+                digest.high_priority_count += 1;
+                group.high_priority.push(task);
+            }
+        }
+    }
+
+    // 收件箱排在最前，其余项目按名称排序
+    digest
+        .projects
+        .sort_by(|a, b| a.project_name.cmp(&b.project_name));
+    Ok(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::open_test_db;
+
+    /// 创建一个在 `due` 到期的任务
+    fn task_due(
+        conn: &Connection,
+        title: &str,
+        project_id: Option<i64>,
+        due: DateTime<Utc>,
+    ) -> i64 {
+        let task = task_queries::create_task(conn, title, project_id, None).unwrap();
+        task_queries::update_task_due_date(conn, task.id, Some(due)).unwrap();
+        task.id
+    }
+
+    fn titles(tasks: &[crate::domain::task::Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn digest_groups_overdue_due_today_and_high_priority_tasks() {
+        let conn = open_test_db();
+        let date = NaiveDate::from_ymd_opt(2030, 1, 2).unwrap();
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let yesterday = local_to_utc(date - Days::new(1), noon);
+        let today = local_to_utc(date, noon);
+        let tomorrow = local_to_utc(date + Days::new(1), noon);
+        let project = project_queries::create_project(&conn, "Work", None).unwrap();
+
+        task_due(&conn, "Overdue", None, yesterday);
+        task_due(&conn, "Today", Some(project.id), today);
+        task_due(&conn, "Later", None, tomorrow);
+        let done = task_due(&conn, "Done today", None, today);
+        task_queries::update_task_status(&conn, done, true).unwrap();
+        let urgent = task_queries::create_task(&conn, "Urgent", None, None).unwrap();
+        task_queries::update_task_priority(&conn, urgent.id, Priority::High).unwrap();
+
+        let digest = build_daily_digest(&conn, date).unwrap();
+
+        assert_eq!(digest.overdue_count, 1);
+        assert_eq!(digest.due_today_count, 1);
+        assert_eq!(digest.high_priority_count, 1);
+        // 收件箱排在最前，已完成和之后才到期的任务不出现
+        assert_eq!(digest.projects.len(), 2);
+        let inbox = &digest.projects[0];
+        assert_eq!(inbox.project_id, None);
+        assert_eq!(titles(&inbox.overdue), vec!["Overdue"]);
+        assert_eq!(titles(&inbox.high_priority), vec!["Urgent"]);
+        assert!(inbox.due_today.is_empty());
+        let work = &digest.projects[1];
+        assert_eq!(work.project_name.as_deref(), Some("Work"));
+        assert_eq!(titles(&work.due_today), vec!["Today"]);
+    }
+
+    #[test]
+    fn missed_reminders_are_returned_once_and_marked_as_missed() {
+        let conn = open_test_db();
+        let now = Utc::now();
+        let task = task_queries::create_task(&conn, "Call back", None, None).unwrap();
+        let missed =
+            reminder_queries::create_reminder(&conn, task.id, now - chrono::Duration::hours(2))
+                .unwrap();
+        // 刚到期和尚未到期的提醒不算错过
+        let recent =
+            reminder_queries::create_reminder(&conn, task.id, now - chrono::Duration::minutes(1))
+                .unwrap();
+        reminder_queries::create_reminder(&conn, task.id, now + chrono::Duration::hours(1))
+            .unwrap();
+
+        let taken = take_missed_reminders(&conn, now).unwrap();
+        assert_eq!(
+            taken.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![missed.id]
+        );
+
+        let stored = reminder_queries::get_missed_reminders(&conn).unwrap();
+        assert_eq!(
+            stored.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![missed.id]
+        );
+        assert!(take_missed_reminders(&conn, now).unwrap().is_empty());
+        // 仍在宽限时间内的提醒留给轮询正常发送
+        let due = reminder_queries::get_due_reminders(&conn).unwrap();
+        assert_eq!(
+            due.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![recent.id]
+        );
+    }
+
+    #[test]
+    fn missed_summary_lists_each_task_once() {
+        let conn = open_test_db();
+        let earlier = Utc::now() - chrono::Duration::hours(3);
+        let mut reminders = Vec::new();
+        for title in ["A", "B", "C", "D"] {
+            let task = task_queries::create_task(&conn, title, None, None).unwrap();
+            reminders.push(reminder_queries::create_reminder(&conn, task.id, earlier).unwrap());
+        }
+        let repeated = reminders[0].task_id;
+        reminders.push(reminder_queries::create_reminder(&conn, repeated, earlier).unwrap());
+
+        let body = missed_summary_body(&conn, &reminders);
+
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].contains('5'));
+        assert_eq!(&lines[1..4], &["• A", "• B", "• C"]);
+        assert!(lines[4].contains('1'));
+    }
+}
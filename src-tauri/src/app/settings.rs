@@ -0,0 +1,49 @@
+use crate::error::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::Manager;
+use tauri_plugin_store::StoreExt;
+
+/// 应用设置所在的 store 文件
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// 界面语言设置在 `settings.json` 中的键
+pub const LANGUAGE_SETTINGS_KEY: &str = "language";
+
+/// 从 `settings.json` 中读取一项结构化设置。
+/// 键不存在或内容无法解析时，返回该设置的默认值。
+pub fn load<T: DeserializeOwned + Default>(app_handle: &tauri::AppHandle, key: &str) -> Result<T> {
+    let store = app_handle.store(SETTINGS_FILE)?;
+    let value = store
+        .get(key)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default();
+    Ok(value)
+}
+
+/// 将一项结构化设置写入 `settings.json` 并立即保存到磁盘
+pub fn save<T: Serialize>(app_handle: &tauri::AppHandle, key: &str, value: &T) -> Result<()> {
+    let store = app_handle.store(SETTINGS_FILE)?;
+    store.set(key.to_string(), serde_json::to_value(value)?);
+    store.save()?;
+    Ok(())
+}
+
+/// 获取当前数据目录（即数据库文件所在的目录）
+pub fn data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf> {
+    let store = app_handle.store(SETTINGS_FILE)?;
+
+    let custom_path = if let Some(path_value) = store.get("databasePath") {
+        path_value.as_str().map(PathBuf::from)
+    } else {
+        None
+    };
+
+    if let Some(path) = custom_path {
+        Ok(path)
+    } else {
+        let default_path = app_handle.path().app_data_dir()?;
+        Ok(default_path)
+    }
+}
@@ -1,9 +1,11 @@
+use crate::app::settings;
 use crate::app::state::AppState;
 use crate::error::Result;
-use log::{error, info};
+use crate::i18n::Language;
+use log::{error, info, warn};
 use rusqlite::Connection;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use tauri::Manager;
 use tauri_plugin_store::StoreExt;
@@ -16,6 +18,9 @@ struct Migration {
     sql: String,
 }
 
+/// 引入界面本地化之前的最后一个数据库版本
+const LAST_CHINESE_ONLY_VERSION: u32 = 5;
+
 pub fn init_database(app_handle: &tauri::AppHandle) -> Result<AppState> {
     info!("[Setup] 正在初始化数据库...");
 
@@ -48,6 +53,20 @@ pub fn init_database(app_handle: &tauri::AppHandle) -> Result<AppState> {
     // 开启 WAL 模式以提高并发性能
     conn.execute_batch("PRAGMA journal_mode = WAL;")?;
 
+    // 本地化之前的版本只有中文界面，从这些版本升级且从未设置过语言的用户继续使用中文，
+    // 而不是根据（在 Windows 和 macOS 图形界面下通常不存在的）环境变量推断
+    let previous_version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if (1..=LAST_CHINESE_ONLY_VERSION).contains(&previous_version)
+        && store.get(settings::LANGUAGE_SETTINGS_KEY).is_none()
+    {
+        info!("[Setup] 检测到从本地化之前的版本升级，界面语言保持为中文。");
+        settings::save(
+            app_handle,
+            settings::LANGUAGE_SETTINGS_KEY,
+            &Language::Chinese,
+        )?;
+    }
+
     // --- 数据库迁移核心逻辑 ---
     run_migrations(&mut conn, app_handle)?;
 
@@ -57,22 +76,27 @@ pub fn init_database(app_handle: &tauri::AppHandle) -> Result<AppState> {
     );
     Ok(AppState {
         db: Mutex::new(conn),
+        pomodoro: Mutex::new(None),
     })
 }
 
 /// 执行数据库迁移
 fn run_migrations(conn: &mut Connection, app_handle: &tauri::AppHandle) -> Result<()> {
+    // `tauri::path::BaseDirectory::Resource` 会定位到在 tauri.conf.json 中配置的 resources 目录
+    let migration_dir = app_handle
+        .path()
+        .resolve("migrations", tauri::path::BaseDirectory::Resource)?;
+    migrate(conn, &migration_dir)
+}
+
+/// 读取 `migration_dir` 中的迁移文件，并执行所有版本号高于当前数据库版本的迁移
+pub(crate) fn migrate(conn: &mut Connection, migration_dir: &Path) -> Result<()> {
     // 1. 获取当前数据库版本
     let current_version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
     info!("[DB Migration] 当前数据库版本: {}", current_version);
 
     // 2. 读取并解析所有迁移文件
     let mut migrations = Vec::new();
-    // `tauri::path::BaseDirectory::Resource` 会定位到在 tauri.conf.json 中配置的 resources 目录
-    let migration_dir = app_handle
-        .path()
-        .resolve("migrations", tauri::path::BaseDirectory::Resource)?;
-
     for entry in fs::read_dir(migration_dir)? {
         let entry = entry?;
         let path = entry.path();
@@ -95,6 +119,32 @@ fn run_migrations(conn: &mut Connection, app_handle: &tauri::AppHandle) -> Resul
     // 3. 按版本号从小到大排序
     migrations.sort();
 
+    // 迁移期间关闭外键约束，否则重建表（DROP TABLE）时会触发子表上的
+    // ON DELETE 动作（例如把所有任务的 project_id 置空）。
+    // 该设置在事务中无效，因此必须在开启事务之前修改。
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+    let result = apply_migrations(conn, migrations, current_version);
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    result?;
+
+    // 迁移不应产生违反外键约束的数据，如有则记录下来以便排查
+    let violations: i64 =
+        conn.query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |row| {
+            row.get(0)
+        })?;
+    if violations > 0 {
+        warn!("[DB Migration] 发现 {} 条违反外键约束的数据", violations);
+    }
+
+    Ok(())
+}
+
+/// 在事务中依次执行所有版本号高于当前数据库版本的迁移
+fn apply_migrations(
+    conn: &mut Connection,
+    migrations: Vec<Migration>,
+    current_version: u32,
+) -> Result<()> {
     // 4. 执行所有版本号高于当前数据库版本的迁移
     for migration in migrations {
         if migration.version > current_version {
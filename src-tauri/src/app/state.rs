@@ -1,3 +1,4 @@
+use crate::domain::pomodoro::PomodoroState;
 use std::sync::Mutex;
 
 // AppState 结构体持有所有需要在Tauri指令间共享的状态。
@@ -5,4 +6,7 @@ pub struct AppState {
     // 将数据库连接 `rusqlite::Connection` 包裹在 `Mutex` 中。
     // Mutex（互斥锁）确保在任何时候只有一个线程能访问数据库连接，从而保证了线程安全。
     pub db: Mutex<rusqlite::Connection>,
+    // 正在进行的番茄钟，为 `None` 时表示没有番茄钟在运行。
+    // 它只存在于内存中，由后台线程推进，因此隐藏窗口后依然会继续计时。
+    pub pomodoro: Mutex<Option<PomodoroState>>,
 }
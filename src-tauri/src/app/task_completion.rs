@@ -0,0 +1,249 @@
+use crate::db::queries::{dependency_queries, task_queries, workflow_status_queries};
+use crate::domain::settings::{OpenDescendantsPolicy, TaskCompletionSettings};
+use crate::domain::task::TaskStatusChange;
+use crate::domain::workflow_status::WorkflowStatus;
+use rusqlite::Connection;
+
+/// 任务完成联动设置在 `settings.json` 中的键
+pub const TASK_COMPLETION_SETTINGS_KEY: &str = "taskCompletion";
+
+/// 更新任务的完成状态，并按设置联动处理它的后代任务和祖先任务。
+/// 调用方需要在事务中执行，以保证联动更新的原子性。
+pub fn set_task_completion(
+    conn: &Connection,
+    id: i64,
+    is_completed: bool,
+    settings: &TaskCompletionSettings,
+) -> rusqlite::Result<TaskStatusChange> {
+    apply_status_change(conn, id, None, is_completed, settings, |conn| {
+        task_queries::update_task_status(conn, id, is_completed)
+    })
+}
+
+/// 将任务切换到指定的工作流状态。
+/// 状态视为已完成时，与 `set_task_completion` 一样联动处理后代任务和祖先任务。
+pub fn set_task_status(
+    conn: &Connection,
+    id: i64,
+    status: &WorkflowStatus,
+    settings: &TaskCompletionSettings,
+) -> rusqlite::Result<TaskStatusChange> {
+    apply_status_change(conn, id, None, status.counts_as_done, settings, |conn| {
+        task_queries::set_task_status(conn, id, status)
+    })
+}
+
+/// 将处于 `from_status_id` 的所有任务切换到状态 `to`，返回因此解除阻塞的任务。
+/// 用于修改状态的“视为已完成”标记（`from_status_id` 与 `to.id` 相同）和删除状态（切换到替代状态）。
+///
+/// 任务是否完成由其状态的 `counts_as_done` 决定。修改标记时调用方应先更新状态定义，
+/// 任务的完成标记随之改变，这里再逐个任务补做完成时间，并与手动完成任务时一样联动祖先任务和依赖任务。
+/// 状态定义的修改无法中途放弃，“提醒”策略在这里按“忽略”处理。
+pub fn sync_tasks_in_status(
+    conn: &Connection,
+    from_status_id: i64,
+    to: &WorkflowStatus,
+    settings: &TaskCompletionSettings,
+) -> rusqlite::Result<Vec<i64>> {
+    let mut settings = settings.clone();
+    if settings.open_descendants == OpenDescendantsPolicy::Warn {
+        settings.open_descendants = OpenDescendantsPolicy::Ignore;
+    }
+    let mut unblocked_task_ids = Vec::new();
+    // 修改标记时，处于该状态的任务原本都是相反的完成标记
+    let flag_changed = from_status_id == to.id;
+    for task_id in workflow_status_queries::get_task_ids_in_status(conn, from_status_id)? {
+        let task = task_queries::get_task_by_id(conn, task_id)?;
+        // 在本轮中已被联动切换到其他状态的任务不再处理
+        if task.status.id != from_status_id {
+            continue;
+        }
+        let was_completed = if flag_changed {
+            !to.counts_as_done
+        } else {
+            task.is_completed
+        };
+        // 完成标记不变的任务只需要切换状态
+        if was_completed == to.counts_as_done {
+            task_queries::set_task_status(conn, task_id, to)?;
+            continue;
+        }
+        let change = apply_status_change(
+            conn,
+            task_id,
+            Some(was_completed),
+            to.counts_as_done,
+            &settings,
+            |conn| task_queries::set_task_status(conn, task_id, to),
+        )?;
+        for id in change.unblocked_task_ids {
+            if !unblocked_task_ids.contains(&id) {
+                unblocked_task_ids.push(id);
+            }
+        }
+    }
+    Ok(unblocked_task_ids)
+}
+
+/// 内部辅助函数：执行状态更新 `update`，并在其前后处理联动。
+/// `was_completed` 为空时，以任务当前的完成标记作为更新前的值。
+fn apply_status_change(
+    conn: &Connection,
+    id: i64,
+    was_completed: Option<bool>,
+    is_completed: bool,
+    settings: &TaskCompletionSettings,
+    update: impl FnOnce(&Connection) -> rusqlite::Result<usize>,
+) -> rusqlite::Result<TaskStatusChange> {
+    let mut change = TaskStatusChange {
+        task: task_queries::get_task_by_id(conn, id)?,
+        applied: true,
+        open_descendant_count: 0,
+        completed_descendant_ids: Vec::new(),
+        updated_ancestor_ids: Vec::new(),
+        unblocked_task_ids: Vec::new(),
+    };
+    let was_completed = was_completed.unwrap_or(change.task.is_completed);
+
+    // --- 1. 处理尚未完成的后代任务 ---
+    if is_completed {
+        let open_descendant_ids = task_queries::get_open_descendant_ids(conn, id)?;
+        change.open_descendant_count = open_descendant_ids.len() as i64;
+        match settings.open_descendants {
+            OpenDescendantsPolicy::Warn if !open_descendant_ids.is_empty() => {
+                change.applied = false;
+                return Ok(change);
+            }
+            OpenDescendantsPolicy::Complete => {
+                for descendant_id in &open_descendant_ids {
+                    task_queries::update_task_status(conn, *descendant_id, true)?;
+                }
+                change.completed_descendant_ids = open_descendant_ids;
+            }
+            _ => {}
+        }
+    }
+
+    // --- 2. 更新任务本身 ---
+    update(conn)?;
+
+    // --- 3. 联动祖先任务 ---
+    // 完成时：逐级向上，直到某个祖先仍有未完成的子任务；
+    // 重新打开时：逐级向上重新打开已完成的祖先，保证“父任务完成则子任务都已完成”。
+    if settings.auto_complete_parent {
+        for ancestor_id in task_queries::get_ancestor_ids(conn, id)? {
+            let ancestor = task_queries::get_task_by_id(conn, ancestor_id)?;
+            let should_update = if is_completed {
+                !ancestor.is_completed && task_queries::count_open_subtasks(conn, ancestor_id)? == 0
+            } else {
+                ancestor.is_completed
+            };
+            if !should_update {
+                break;
+            }
+            task_queries::update_task_status(conn, ancestor_id, is_completed)?;
+            change.updated_ancestor_ids.push(ancestor_id);
+        }
+    }
+
+    // --- 4. 找出因本次完成而解除阻塞的任务 ---
+    if is_completed {
+        let mut newly_completed_ids = change.completed_descendant_ids.clone();
+        newly_completed_ids.extend(&change.updated_ancestor_ids);
+        if !was_completed {
+            newly_completed_ids.push(id);
+        }
+        change.unblocked_task_ids =
+            dependency_queries::get_unblocked_dependent_ids(conn, &newly_completed_ids)?;
+    }
+
+    change.task = task_queries::get_task_by_id(conn, id)?;
+    Ok(change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::project_queries;
+    use crate::db::test_support::open_test_db;
+
+    #[test]
+    fn marking_a_status_as_done_completes_its_tasks_and_unblocks_dependents() {
+        let conn = open_test_db();
+        let settings = TaskCompletionSettings {
+            auto_complete_parent: true,
+            ..Default::default()
+        };
+        let project = project_queries::create_project(&conn, "P", None).unwrap();
+        // 新任务使用第一个未完成状态，因此先创建“Todo”，只有 child 处于“Review”
+        workflow_status_queries::create_status(&conn, Some(project.id), "Todo", false).unwrap();
+        let review =
+            workflow_status_queries::create_status(&conn, Some(project.id), "Review", false)
+                .unwrap();
+        workflow_status_queries::create_status(&conn, Some(project.id), "Closed", true).unwrap();
+        let parent = task_queries::create_task(&conn, "parent", Some(project.id), None).unwrap();
+        let child =
+            task_queries::create_task(&conn, "child", Some(project.id), Some(parent.id)).unwrap();
+        let blocked = task_queries::create_task(&conn, "blocked", Some(project.id), None).unwrap();
+        dependency_queries::add_dependency(&conn, blocked.id, child.id).unwrap();
+        task_queries::set_task_status(&conn, child.id, &review).unwrap();
+
+        let review =
+            workflow_status_queries::update_status(&conn, review.id, "Review", true).unwrap();
+        let unblocked = sync_tasks_in_status(&conn, review.id, &review, &settings).unwrap();
+
+        assert_eq!(unblocked, [blocked.id]);
+        let child = task_queries::get_task_by_id(&conn, child.id).unwrap();
+        assert!(child.is_completed && child.completed_at.is_some());
+        assert_eq!(child.status.id, review.id);
+        assert!(
+            task_queries::get_task_by_id(&conn, parent.id)
+                .unwrap()
+                .is_completed
+        );
+    }
+
+    #[test]
+    fn moving_tasks_to_an_open_replacement_reopens_them() {
+        let conn = open_test_db();
+        let statuses = workflow_status_queries::get_statuses(&conn, None).unwrap();
+        let (todo, cancelled) = (&statuses[0], &statuses[4]);
+        let task = task_queries::create_task(&conn, "t", None, None).unwrap();
+        task_queries::set_task_status(&conn, task.id, cancelled).unwrap();
+
+        let settings = TaskCompletionSettings::default();
+        sync_tasks_in_status(&conn, cancelled.id, todo, &settings).unwrap();
+
+        let task = task_queries::get_task_by_id(&conn, task.id).unwrap();
+        assert!(!task.is_completed && task.completed_at.is_none());
+        assert_eq!(task.status.id, todo.id);
+        assert!(
+            workflow_status_queries::get_task_ids_in_status(&conn, cancelled.id)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn warn_policy_does_not_stop_a_status_sync() {
+        let conn = open_test_db();
+        let settings = TaskCompletionSettings {
+            open_descendants: OpenDescendantsPolicy::Warn,
+            ..Default::default()
+        };
+        let statuses = workflow_status_queries::get_statuses(&conn, None).unwrap();
+        let waiting = statuses[2].clone();
+        let parent = task_queries::create_task(&conn, "parent", None, None).unwrap();
+        task_queries::create_task(&conn, "child", None, Some(parent.id)).unwrap();
+        task_queries::set_task_status(&conn, parent.id, &waiting).unwrap();
+
+        let waiting =
+            workflow_status_queries::update_status(&conn, waiting.id, &waiting.name, true).unwrap();
+        sync_tasks_in_status(&conn, waiting.id, &waiting, &settings).unwrap();
+        assert!(
+            task_queries::get_task_by_id(&conn, parent.id)
+                .unwrap()
+                .is_completed
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use crate::db::queries::{
+    attachment_queries, checklist_queries, reminder_queries, tag_queries, task_queries,
+};
+use crate::domain::task::{DuplicateTarget, ReminderCopyMode, Task};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+use std::collections::HashSet;
+
+/// 深度复制一个任务及其所有后代任务，返回新的根任务。调用方需要在事务中执行。
+///
+/// 副本复制标题、描述、优先级、估算、截止日期、附件、检查项和（按 `reminders` 处理的）提醒，
+/// 但总是作为未完成的任务（检查项也都未勾选）创建，并使用目标项目的默认工作流状态。
+/// 截止日期平移 `shift_days` 天；副本与原任务在同一个项目中时保留原来的分组。
+pub fn duplicate_task(
+    conn: &Connection,
+    id: i64,
+    target: DuplicateTarget,
+    shift_days: i64,
+    reminders: ReminderCopyMode,
+    now: DateTime<Utc>,
+) -> rusqlite::Result<Task> {
+    let original = task_queries::get_task_by_id(conn, id)?;
+    let (project_id, parent_id) = match target {
+        DuplicateTarget::Original => (original.project_id, original.parent_id),
+        DuplicateTarget::Parent(parent_id) => {
+            let parent = task_queries::get_task_by_id(conn, parent_id)?;
+            (parent.project_id, Some(parent_id))
+        }
+        DuplicateTarget::Project(project_id) => (project_id, None),
+    };
+    let shift = Duration::days(shift_days);
+
+    // 复制之前先确定原任务的子树，即使副本被放在原任务的子树中也不会把副本再复制一遍
+    let subtree: HashSet<i64> = task_queries::get_subtree_task_ids(conn, &[id])?
+        .into_iter()
+        .collect();
+    let mut pending: Vec<(Task, i64)> = Vec::new();
+    let root_copy_id = copy_task(
+        conn, &original, project_id, parent_id, shift, reminders, now,
+    )?;
+    push_children(conn, &subtree, &mut pending, original.id, root_copy_id)?;
+    while let Some((task, copy_parent_id)) = pending.pop() {
+        let copy_id = copy_task(
+            conn,
+            &task,
+            project_id,
+            Some(copy_parent_id),
+            shift,
+            reminders,
+            now,
+        )?;
+        push_children(conn, &subtree, &mut pending, task.id, copy_id)?;
+    }
+    task_queries::get_task_by_id(conn, root_copy_id)
+}
+
+/// 内部辅助函数：将原任务在 `subtree` 中的子任务加入待复制的列表，`copy_id` 为它们的副本的父任务
+fn push_children(
+    conn: &Connection,
+    subtree: &HashSet<i64>,
+    pending: &mut Vec<(Task, i64)>,
+    task_id: i64,
+    copy_id: i64,
+) -> rusqlite::Result<()> {
+    let children = task_queries::get_tasks_by_parent(conn, None, Some(task_id))?;
+    pending.extend(
+        children
+            .into_iter()
+            .rev()
+            .filter(|child| subtree.contains(&child.id))
+            .map(|child| (child, copy_id)),
+    );
+    Ok(())
+}
+
+/// 内部辅助函数：复制单个任务（不含子任务），返回副本的 ID
+fn copy_task(
+    conn: &Connection,
+    task: &Task,
+    project_id: Option<i64>,
+    parent_id: Option<i64>,
+    shift: Duration,
+    reminders: ReminderCopyMode,
+    now: DateTime<Utc>,
+) -> rusqlite::Result<i64> {
+    let copy = task_queries::create_task(conn, &task.title, project_id, parent_id)?;
+    task_queries::update_task_description(conn, copy.id, task.description.clone())?;
+    task_queries::update_task_priority(conn, copy.id, task.priority)?;
+    task_queries::update_task_estimate(conn, copy.id, task.estimate)?;
+    let due_date = task.due_date.map(|due| due + shift);
+    task_queries::update_task_due_date(conn, copy.id, due_date)?;
+    if project_id == task.project_id {
+        task_queries::update_task_section(conn, copy.id, task.section_id)?;
+    }
+    for tag in tag_queries::get_tags_for_task(conn, task.id)? {
+        tag_queries::add_tag_to_task(conn, copy.id, tag.id)?;
+    }
+
+    if reminders == ReminderCopyMode::Shift {
+        for reminder in reminder_queries::get_reminders_for_task(conn, task.id)? {
+            match (reminder.offset_minutes, due_date) {
+                (Some(offset), Some(due)) => {
+                    if due - Duration::minutes(offset) > now {
+                        reminder_queries::create_relative_reminder(conn, copy.id, offset, due)?;
+                    }
+                }
+                _ => {
+                    let remind_at = reminder.remind_at + shift;
+                    if remind_at > now {
+                        reminder_queries::create_reminder(conn, copy.id, remind_at)?;
+                    }
+                }
+            }
+        }
+    }
+    attachment_queries::copy_attachments(conn, task.id, copy.id)?;
+    checklist_queries::copy_checklist_items(conn, task.id, copy.id)?;
+    Ok(copy.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::{project_queries, section_queries, time_entry_queries};
+    use crate::db::test_support::open_test_db;
+    use crate::domain::priority::Priority;
+
+    fn duplicate(conn: &Connection, id: i64, target: DuplicateTarget) -> Task {
+        duplicate_task(conn, id, target, 0, ReminderCopyMode::Drop, Utc::now()).unwrap()
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn copy_keeps_the_content_but_not_the_progress() {
+        let conn = open_test_db();
+        let task = task_queries::create_task(&conn, "Write report", None, None).unwrap();
+        task_queries::update_task_description(&conn, task.id, Some("draft first".into())).unwrap();
+        task_queries::update_task_priority(&conn, task.id, Priority::High).unwrap();
+        let tag = tag_queries::get_or_create_tag(&conn, "work").unwrap();
+        tag_queries::add_tag_to_task(&conn, task.id, tag.id).unwrap();
+        let item = checklist_queries::create_checklist_item(&conn, task.id, "outline").unwrap();
+        checklist_queries::set_checklist_item_checked(&conn, item.id, true).unwrap();
+        let now = Utc::now();
+        time_entry_queries::create_time_entry(&conn, task.id, now - Duration::hours(1), now, None)
+            .unwrap();
+        task_queries::update_task_status(&conn, task.id, true).unwrap();
+
+        let copy = duplicate(&conn, task.id, DuplicateTarget::Original);
+
+        assert_ne!(copy.id, task.id);
+        assert_eq!(copy.title, "Write report");
+        assert_eq!(copy.description.as_deref(), Some("draft first"));
+        assert_eq!(copy.priority, Priority::High);
+        assert_eq!(
+            tag_queries::get_tags_for_task(&conn, copy.id).unwrap(),
+            vec![tag]
+        );
+        let items = checklist_queries::get_checklist_items(&conn, copy.id).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].text, "outline");
+        assert!(!items[0].is_checked);
+        // 完成状态和时间记录不会被复制
+        assert!(!copy.is_completed && copy.completed_at.is_none());
+        assert!(
+            time_entry_queries::get_time_entries_for_task(&conn, copy.id)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(copy.tracked_seconds, 0);
+    }
+
+    #[test]
+    fn subtasks_are_cloned_under_the_copy() {
+        let conn = open_test_db();
+        let task = task_queries::create_task(&conn, "parent", None, None).unwrap();
+        let child = task_queries::create_task(&conn, "child", None, Some(task.id)).unwrap();
+        task_queries::create_task(&conn, "grandchild", None, Some(child.id)).unwrap();
+
+        let copy = duplicate(&conn, task.id, DuplicateTarget::Original);
+
+        assert_eq!(copy.descendant_count, 2);
+        let children = task_queries::get_tasks_by_parent(&conn, None, Some(copy.id)).unwrap();
+        assert_eq!(titles(&children), vec!["child"]);
+        assert_ne!(children[0].id, child.id);
+        let grandchildren =
+            task_queries::get_tasks_by_parent(&conn, None, Some(children[0].id)).unwrap();
+        assert_eq!(titles(&grandchildren), vec!["grandchild"]);
+        // 原任务的子树保持不变
+        let original_children =
+            task_queries::get_tasks_by_parent(&conn, None, Some(task.id)).unwrap();
+        assert_eq!(
+            original_children.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![child.id]
+        );
+    }
+
+    #[test]
+    fn copy_stays_in_the_same_project_and_section() {
+        let conn = open_test_db();
+        let project = project_queries::create_project(&conn, "Project", None).unwrap();
+        let section = section_queries::create_section(&conn, project.id, "Next").unwrap();
+        let task = task_queries::create_task(&conn, "task", Some(project.id), None).unwrap();
+        task_queries::update_task_section(&conn, task.id, Some(section.id)).unwrap();
+
+        let copy = duplicate(&conn, task.id, DuplicateTarget::Original);
+        assert_eq!(copy.project_id, Some(project.id));
+        assert_eq!(copy.section_id, Some(section.id));
+
+        // 复制到其他项目时不保留分组
+        let other = project_queries::create_project(&conn, "Other", None).unwrap();
+        let moved = duplicate(&conn, task.id, DuplicateTarget::Project(Some(other.id)));
+        assert_eq!((moved.project_id, moved.section_id), (Some(other.id), None));
+    }
+}
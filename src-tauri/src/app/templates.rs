@@ -0,0 +1,225 @@
+use crate::app::commands::project_commands;
+use crate::db::queries::{
+    attachment_queries, project_queries, reminder_queries, task_queries, template_queries,
+};
+use crate::domain::attachment::AttachmentType;
+use crate::domain::task::Task;
+use crate::domain::template::{render_template_text, Template, TemplateInstance, TemplateKind};
+use crate::domain::time::local_to_utc;
+use crate::error::{Error, Result};
+use crate::t;
+use chrono::{Duration, Local, NaiveDate, NaiveTime};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+/// 将任务及其所有子任务保存为模板
+pub fn capture_task(conn: &Connection, task_id: i64, name: &str) -> Result<Template> {
+    let task = task_queries::get_task_by_id(conn, task_id)?;
+    let template_id = template_queries::create_template(conn, name, TemplateKind::Task, None)?;
+    capture_subtrees(conn, template_id, vec![task])?;
+    Ok(template_queries::get_template_by_id(conn, template_id)?)
+}
+
+/// 将项目（名称、颜色、图标、描述）及其中的所有任务保存为模板。子项目不包含在内。
+pub fn capture_project(conn: &Connection, project_id: i64, name: &str) -> Result<Template> {
+    let project = project_queries::get_project_by_id(conn, project_id)?;
+    let template_id =
+        template_queries::create_template(conn, name, TemplateKind::Project, Some(&project))?;
+    let roots = task_queries::get_tasks_by_parent(conn, Some(project_id), None)?;
+    capture_subtrees(conn, template_id, roots)?;
+    Ok(template_queries::get_template_by_id(conn, template_id)?)
+}
+
+/// 内部辅助函数：按原有的层级和顺序保存任务树。
+/// 截止日期被保存为相对于锚点的偏移量，锚点是其中最早的截止日期所在的本地日期。
+fn capture_subtrees(conn: &Connection, template_id: i64, roots: Vec<Task>) -> Result<()> {
+    let root_ids: Vec<i64> = roots.iter().map(|t| t.id).collect();
+    let subtree_ids = task_queries::get_subtree_task_ids(conn, &root_ids)?;
+    let anchor = task_queries::get_tasks_by_ids(conn, &subtree_ids)?
+        .iter()
+        .filter_map(|t| t.due_date)
+        .min()
+        .map(|due| local_to_utc(due.with_timezone(&Local).date_naive(), NaiveTime::MIN));
+
+    // 待保存的任务：(任务, 模板中的父任务 ID, 在兄弟任务中的顺序)
+    let mut pending: Vec<(Task, Option<i64>, i64)> = roots
+        .into_iter()
+        .enumerate()
+        .rev()
+        .map(|(position, task)| (task, None, position as i64))
+        .collect();
+    while let Some((task, parent_id, position)) = pending.pop() {
+        let due_offset_minutes = task
+            .due_date
+            .zip(anchor)
+            .map(|(due, anchor)| (due - anchor).num_minutes());
+        let template_task_id = template_queries::add_template_task(
+            conn,
+            template_id,
+            parent_id,
+            position,
+            &task,
+            due_offset_minutes,
+        )?;
+        let children = task_queries::get_tasks_by_parent(conn, None, Some(task.id))?;
+        pending.extend(
+            children
+                .into_iter()
+                .enumerate()
+                .rev()
+                .map(|(position, child)| (child, Some(template_task_id), position as i64)),
+        );
+    }
+    Ok(())
+}
+
+/// 使用模板创建任务（和项目）。调用方负责将其放在一个事务中。
+///
+/// - 模板中的 `{{变量}}` 会被替换为 `variables` 中对应的值，缺少任何一个变量都会报错；
+/// - 截止日期以 `anchor_date` 当天的本地零点为基准，按模板中的偏移量计算；
+/// - 任务模板创建在 `parent_id` 指定的任务下（此时使用父任务所在的项目），
+///   或者 `project_id` 指定的项目中（为空时为收件箱）；
+/// - 项目模板会创建一个新项目，`project_id` 为新项目的父项目，不能指定 `parent_id`。
+pub fn instantiate(
+    conn: &Connection,
+    template_id: i64,
+    variables: &HashMap<String, String>,
+    anchor_date: NaiveDate,
+    project_id: Option<i64>,
+    parent_id: Option<i64>,
+) -> Result<TemplateInstance> {
+    let template = template_queries::get_template_by_id(conn, template_id)?;
+    if let Some(missing) = template
+        .variables
+        .iter()
+        .find(|name| !variables.contains_key(*name))
+    {
+        return Err(Error::Validation(t!(
+            "error-template-missing-variable",
+            name = missing
+        )));
+    }
+    let render = |text: &str| render_template_text(text, variables);
+
+    let (project, project_id) = match template.kind {
+        TemplateKind::Project => {
+            if parent_id.is_some() {
+                return Err(Error::Validation(t!("error-template-project-under-task")));
+            }
+            let name = render(template.project_name.as_deref().unwrap_or(&template.name));
+            project_commands::ensure_unique_sibling_name(conn, project_id, &name, None)?;
+            let project = project_queries::create_project(conn, &name, project_id)?;
+            project_queries::update_project_details(
+                conn,
+                project.id,
+                template.project_color.as_deref(),
+                template.project_icon.as_deref(),
+                template
+                    .project_description
+                    .as_deref()
+                    .map(render)
+                    .as_deref(),
+            )?;
+            (Some(project.id), Some(project.id))
+        }
+        TemplateKind::Task => match parent_id {
+            Some(parent_id) => {
+                let parent = task_queries::get_task_by_id(conn, parent_id)?;
+                (None, parent.project_id)
+            }
+            None => (None, project_id),
+        },
+    };
+    let default_offsets = match project_id {
+        Some(project_id) => project_queries::get_default_reminder_offsets(conn, project_id)?,
+        None => Vec::new(),
+    };
+
+    let anchor = local_to_utc(anchor_date, NaiveTime::MIN);
+    // 模板任务 ID -> 新任务 ID。父任务总是先于子任务创建
+    let mut created: HashMap<i64, i64> = HashMap::new();
+    let mut root_ids = Vec::new();
+    for template_task in template_queries::get_template_tasks(conn, template_id)? {
+        let task_parent_id = match template_task.parent_id {
+            Some(template_parent_id) => created.get(&template_parent_id).copied(),
+            None => parent_id,
+        };
+        let task = task_queries::create_task(
+            conn,
+            &render(&template_task.title),
+            project_id,
+            task_parent_id,
+        )?;
+        task_queries::update_task_description(
+            conn,
+            task.id,
+            template_task.description.as_deref().map(render),
+        )?;
+        task_queries::update_task_priority(conn, task.id, template_task.priority)?;
+        task_queries::update_task_estimate(conn, task.id, template_task.estimate)?;
+
+        let due_date = template_task
+            .due_offset_minutes
+            .map(|offset| anchor + Duration::minutes(offset));
+        if let Some(due) = due_date {
+            task_queries::update_task_due_date(conn, task.id, Some(due))?;
+            // 与手动设置截止日期一样，模板没有提醒时套用项目的默认提醒
+            let offsets = if template_task.reminder_offsets.is_empty() {
+                &default_offsets
+            } else {
+                &template_task.reminder_offsets
+            };
+            for offset in offsets {
+                reminder_queries::create_relative_reminder(conn, task.id, *offset, due)?;
+            }
+        }
+
+        for attachment in &template_task.attachments {
+            let created_attachment = match attachment.attachment_type {
+                AttachmentType::VaultFile => attachment_queries::create_vault_attachment(
+                    conn,
+                    task.id,
+                    &attachment.payload,
+                    attachment.file_size.unwrap_or_default(),
+                    attachment.mime_type.as_deref().unwrap_or_default(),
+                    attachment.original_name.as_deref().unwrap_or_default(),
+                )?,
+                AttachmentType::Note | AttachmentType::Url => {
+                    attachment_queries::create_attachment(
+                        conn,
+                        task.id,
+                        attachment.attachment_type,
+                        &render(&attachment.payload),
+                    )?
+                }
+                _ => attachment_queries::create_attachment(
+                    conn,
+                    task.id,
+                    attachment.attachment_type,
+                    &attachment.payload,
+                )?,
+            };
+            if let Some(title) = &attachment.title {
+                attachment_queries::update_attachment_title(
+                    conn,
+                    created_attachment.id,
+                    Some(&render(title)),
+                )?;
+            }
+        }
+
+        created.insert(template_task.id, task.id);
+        if template_task.parent_id.is_none() {
+            root_ids.push(task.id);
+        }
+    }
+
+    Ok(TemplateInstance {
+        template_id,
+        project: project
+            .map(|id| project_queries::get_project_by_id(conn, id))
+            .transpose()?,
+        tasks: task_queries::get_tasks_by_ids(conn, &root_ids)?,
+        created_task_count: created.len() as i64,
+    })
+}
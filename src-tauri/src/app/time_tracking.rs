@@ -0,0 +1,33 @@
+use crate::app::state::AppState;
+use crate::app::tray;
+use crate::db::queries::time_entry_queries;
+use log::{error, info};
+use std::{thread, time::Duration};
+use tauri::Manager;
+
+/// 计时器运行时刷新托盘提示文字的间隔
+const TOOLTIP_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 启动后台服务：计时器运行期间定期刷新托盘提示中的已计时长
+pub fn start(app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        info!("[TimeTracking] 计时器托盘刷新服务已启动。");
+        loop {
+            thread::sleep(TOOLTIP_REFRESH_INTERVAL);
+            let running = {
+                let state = app_handle.state::<AppState>();
+                let conn = state.db.lock().unwrap();
+                time_entry_queries::get_running_entry(&conn)
+            };
+            match running {
+                Ok(Some(_)) => {
+                    if let Err(e) = tray::refresh_tooltip(&app_handle) {
+                        error!("[TimeTracking] 刷新托盘提示失败: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => error!("[TimeTracking] 读取正在运行的计时器失败: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,64 @@
+use crate::app::state::AppState;
+use crate::db::queries::{task_queries, time_entry_queries};
+use crate::t;
+use log::error;
+use tauri::menu::{Menu, MenuItem};
+use tauri::{Manager, Wry};
+
+/// 系统托盘图标的 ID，用于在运行时重新获取托盘并更新其内容
+pub const TRAY_ID: &str = "main";
+
+/// 按当前语言构建托盘右键菜单
+pub fn build_menu(app_handle: &tauri::AppHandle) -> tauri::Result<Menu<Wry>> {
+    let show_item = MenuItem::with_id(app_handle, "show", t!("tray-show"), true, None::<&str>)?;
+    let exit_item = MenuItem::with_id(app_handle, "exit", t!("tray-exit"), true, None::<&str>)?;
+    Menu::with_items(app_handle, &[&show_item, &exit_item])
+}
+
+/// 按当前语言生成托盘提示文字，有正在运行的计时器时显示任务和已计时长
+pub fn tooltip(app_handle: &tauri::AppHandle) -> String {
+    let state = app_handle.state::<AppState>();
+    let conn = state.db.lock().unwrap();
+    let running = time_entry_queries::get_running_entry(&conn).and_then(|entry| {
+        entry
+            .map(|entry| {
+                task_queries::get_task_by_id(&conn, entry.task_id)
+                    .map(|task| (task.title, entry.duration_seconds))
+            })
+            .transpose()
+    });
+    match running {
+        Ok(Some((title, seconds))) => t!(
+            "tray-tooltip-timer",
+            task = title,
+            elapsed = format_elapsed(seconds)
+        ),
+        Ok(None) => t!("tray-tooltip"),
+        Err(e) => {
+            error!("[Tray] 读取正在运行的计时器失败: {}", e);
+            t!("tray-tooltip")
+        }
+    }
+}
+
+/// 将秒数格式化为 `H:MM`
+fn format_elapsed(seconds: i64) -> String {
+    format!("{}:{:02}", seconds / 3600, seconds % 3600 / 60)
+}
+
+/// 只刷新托盘提示文字，例如计时器启动、停止或计时增加后
+pub fn refresh_tooltip(app_handle: &tauri::AppHandle) -> tauri::Result<()> {
+    if let Some(tray) = app_handle.tray_by_id(TRAY_ID) {
+        tray.set_tooltip(Some(tooltip(app_handle)))?;
+    }
+    Ok(())
+}
+
+/// 语言切换后，重新生成托盘菜单和提示文字
+pub fn refresh(app_handle: &tauri::AppHandle) -> tauri::Result<()> {
+    if let Some(tray) = app_handle.tray_by_id(TRAY_ID) {
+        tray.set_menu(Some(build_menu(app_handle)?))?;
+        tray.set_tooltip(Some(tooltip(app_handle)))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,351 @@
+use crate::db::queries::attachment_queries;
+use crate::domain::attachment::{Attachment, AttachmentType};
+use crate::error::{Error, Result};
+use crate::t;
+use log::warn;
+use rusqlite::Connection;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::Duration;
+use url::Url;
+
+/// 规范化时会被移除的跟踪参数（`utm_` 开头的参数会全部移除）
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "msclkid", "yclid", "igshid", "mc_cid", "mc_eid", "_ga", "spm",
+    "ref_src",
+];
+
+/// 获取网页标题时最多读取的字节数，`<title>` 通常位于页面开头
+const MAX_TITLE_FETCH_BYTES: usize = 512 * 1024;
+/// 获取网页标题的超时时间
+const TITLE_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 解析并规范化一个 URL：
+/// - 缺少协议时默认补全为 `https://`
+/// - 只允许 `http` 和 `https` 协议
+/// - 移除常见的跟踪参数
+pub fn normalize_url(input: &str) -> Result<Url> {
+    let input = input.trim();
+    let mut url = match Url::parse(input) {
+        Ok(url) => url,
+        Err(url::ParseError::RelativeUrlWithoutBase) => Url::parse(&format!("https://{input}"))
+            .map_err(|_| Error::Validation(t!("error-url-invalid")))?,
+        Err(_) => return Err(Error::Validation(t!("error-url-invalid"))),
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(Error::Validation(t!(
+            "error-url-unsupported-scheme",
+            scheme = url.scheme()
+        )));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(Error::Validation(t!("error-url-invalid")));
+    }
+
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else if url.query_pairs().count() != kept.len() {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    Ok(url)
+}
+
+/// 判断一个查询参数是否为跟踪参数
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+/// `TitleFetcher::fetch_title` 返回的 future
+pub type TitleFuture<'a> = Pin<Box<dyn Future<Output = Result<Option<String>>> + Send + 'a>>;
+
+/// 网页标题的获取方式。
+/// 抽象为 trait 以便替换实现，例如在测试中指向本地的 HTTP 服务。
+pub trait TitleFetcher: Send + Sync {
+    /// 获取网页标题，页面没有标题时返回 `None`
+    fn fetch_title<'a>(&'a self, url: &'a Url) -> TitleFuture<'a>;
+}
+
+/// 通过 HTTP 请求网页并解析 `<title>` 的标题获取器
+pub struct HttpTitleFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpTitleFetcher {
+    pub fn new() -> Result<Self> {
+        let client = Self::client_builder()
+            .build()
+            .map_err(|e| Error::Validation(t!("error-url-fetch-failed", detail = e)))?;
+        Ok(Self { client })
+    }
+
+    /// 内部辅助函数：带有超时和 User-Agent 的 HTTP 客户端配置
+    fn client_builder() -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .timeout(TITLE_FETCH_TIMEOUT)
+            .user_agent(concat!("Momentum/", env!("CARGO_PKG_VERSION")))
+    }
+}
+
+impl TitleFetcher for HttpTitleFetcher {
+    fn fetch_title<'a>(&'a self, url: &'a Url) -> TitleFuture<'a> {
+        Box::pin(async move {
+            let fetch_failed =
+                |e: reqwest::Error| Error::Validation(t!("error-url-fetch-failed", detail = e));
+            let mut response = self
+                .client
+                .get(url.clone())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(fetch_failed)?;
+
+            // 只读取页面开头的一部分，避免下载很大的文件
+            let mut body = Vec::new();
+            while let Some(chunk) = response.chunk().await.map_err(fetch_failed)? {
+                body.extend_from_slice(&chunk);
+                if body.len() >= MAX_TITLE_FETCH_BYTES {
+                    break;
+                }
+            }
+            Ok(extract_title(&String::from_utf8_lossy(&body)))
+        })
+    }
+}
+
+/// 为任务创建一个 URL 附件。
+/// URL 会先被校验和规范化，同一任务下不允许重复的 URL。
+/// 未填写标题且提供了 `fetcher` 时，会尝试获取网页标题，获取失败不影响附件的创建。
+///
+/// 获取标题期间不持有数据库锁，因此在插入前会在同一个锁内再次检查重复。
+pub async fn create_url_attachment(
+    db: &Mutex<Connection>,
+    fetcher: Option<&dyn TitleFetcher>,
+    task_id: i64,
+    url: &str,
+    title: Option<String>,
+) -> Result<Attachment> {
+    let url = normalize_url(url)?;
+    // 先检查一次，避免为重复的 URL 发起网络请求
+    ensure_not_duplicate(&db.lock().unwrap(), task_id, &url)?;
+
+    let mut title = title
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+    if let (None, Some(fetcher)) = (&title, fetcher) {
+        title = fetcher.fetch_title(&url).await.unwrap_or_else(|e| {
+            warn!(
+                "[UrlAttachment] 获取网页标题失败, url: {}, 错误: {}",
+                url, e
+            );
+            None
+        });
+    }
+
+    let conn = db.lock().unwrap();
+    ensure_not_duplicate(&conn, task_id, &url)?;
+    Ok(attachment_queries::create_url_attachment(
+        &conn,
+        task_id,
+        url.as_str(),
+        title.as_deref(),
+    )?)
+}
+
+/// 内部辅助函数：任务下已有规范化后相同的 URL 附件时返回错误
+fn ensure_not_duplicate(conn: &Connection, task_id: i64, url: &Url) -> Result<()> {
+    let is_duplicate = attachment_queries::get_attachments_for_task(conn, task_id)?
+        .iter()
+        .filter(|a| a.attachment_type == AttachmentType::Url)
+        .any(|a| normalize_url(&a.payload).is_ok_and(|u| &u == url));
+    if is_duplicate {
+        return Err(Error::Validation(t!("error-url-duplicate")));
+    }
+    Ok(())
+}
+
+/// 从 HTML 中提取 `<title>` 的内容，并合并空白、解码常见的 HTML 实体
+pub fn extract_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title = decode_html_entities(&html[start..end])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+/// 解码常见的命名实体和数字实体，无法识别的实体保持原样
+fn decode_html_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let entity = &rest[1..semi];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#')?.parse().ok())
+                    .and_then(char::from_u32),
+            }?;
+            Some((ch, semi + 1))
+        });
+        match decoded {
+            Some((ch, consumed)) => {
+                out.push(ch);
+                rest = &rest[consumed..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::task_queries;
+    use crate::db::test_support::open_test_db;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    /// 返回固定标题并记录调用次数的标题获取器
+    struct FixedTitle {
+        title: &'static str,
+        calls: AtomicUsize,
+    }
+
+    impl TitleFetcher for FixedTitle {
+        fn fetch_title<'a>(&'a self, _url: &'a Url) -> TitleFuture<'a> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Ok(Some(self.title.to_string())) })
+        }
+    }
+
+    /// 在本地启动一个只响应一次请求的 HTTP 服务，返回它的地址
+    fn serve_once(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}/page", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        address
+    }
+
+    #[test]
+    fn normalizes_scheme_and_tracking_parameters() {
+        assert_eq!(
+            normalize_url("  example.com/a?utm_source=x&id=1&fbclid=y ")
+                .unwrap()
+                .as_str(),
+            "https://example.com/a?id=1"
+        );
+        assert_eq!(
+            normalize_url("HTTP://Example.com?utm_medium=email")
+                .unwrap()
+                .as_str(),
+            "http://example.com/"
+        );
+        assert!(normalize_url("ftp://example.com").is_err());
+        assert!(normalize_url("https://").is_err());
+        assert!(normalize_url("not a url").is_err());
+    }
+
+    #[test]
+    fn extracts_and_cleans_the_page_title() {
+        assert_eq!(
+            extract_title("<html><TITLE lang=\"en\">\n  Tom &amp; Jerry &#x263A; </TITLE>"),
+            Some("Tom & Jerry ☺".to_string())
+        );
+        assert_eq!(extract_title("<title>   </title>"), None);
+        assert_eq!(extract_title("<title>unterminated"), None);
+        assert_eq!(
+            extract_title("<title>&unknown; &#99999999;</title>"),
+            Some("&unknown; &#99999999;".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn stores_the_fetched_title_and_rejects_duplicates() {
+        let db = Mutex::new(open_test_db());
+        let task_id = task_queries::create_task(&db.lock().unwrap(), "t", None, None)
+            .unwrap()
+            .id;
+        let fetcher = FixedTitle {
+            title: "Example",
+            calls: AtomicUsize::new(0),
+        };
+
+        let attachment =
+            create_url_attachment(&db, Some(&fetcher), task_id, "example.com/?utm_id=1", None)
+                .await
+                .unwrap();
+        assert_eq!(attachment.payload, "https://example.com/");
+        assert_eq!(attachment.title.as_deref(), Some("Example"));
+
+        let duplicate =
+            create_url_attachment(&db, Some(&fetcher), task_id, "https://example.com", None).await;
+        assert!(duplicate.is_err());
+        // 重复的 URL 不会发起请求
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 1);
+
+        let given = create_url_attachment(
+            &db,
+            Some(&fetcher),
+            task_id,
+            "example.org",
+            Some("Mine".into()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(given.title.as_deref(), Some("Mine"));
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn fetches_the_title_from_a_local_server() {
+        let address = serve_once("<html><head><title>Local page</title></head></html>");
+        let url = Url::parse(&address).unwrap();
+        // 不使用环境中配置的代理，确保请求直接到达本地服务
+        let fetcher = HttpTitleFetcher {
+            client: HttpTitleFetcher::client_builder()
+                .no_proxy()
+                .build()
+                .unwrap(),
+        };
+        let title = fetcher.fetch_title(&url).await.unwrap();
+        assert_eq!(title.as_deref(), Some("Local page"));
+    }
+}
@@ -0,0 +1,230 @@
+use crate::db::queries::attachment_queries;
+use crate::error::{Error, Result};
+use crate::t;
+use log::{info, warn};
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 附件保管库的目录名，位于数据目录（即 `momentum.db` 所在目录）下
+pub const VAULT_DIR_NAME: &str = "vault";
+
+/// 一个已存入保管库的文件的信息
+#[derive(Debug)]
+pub struct StoredFile {
+    /// 文件内容的 SHA-256 哈希（十六进制），同时也是它在保管库中的文件名
+    pub hash: String,
+    pub size: i64,
+    pub mime_type: String,
+    pub original_name: String,
+}
+
+/// 获取数据目录下的保管库目录
+pub fn vault_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(VAULT_DIR_NAME)
+}
+
+/// 获取指定哈希在保管库中的存储路径。
+/// 使用哈希的前两位作为子目录，避免单个目录下文件过多。
+pub fn blob_path(vault_dir: &Path, hash: &str) -> PathBuf {
+    vault_dir.join(&hash[..2]).join(hash)
+}
+
+/// 将一个文件按内容哈希复制进保管库。
+/// 内容相同的文件只会保存一份。
+///
+/// 存入的文件在被附件引用之前会被 `collect_garbage` 视为垃圾，因此调用方需要持有数据库锁，
+/// 并在释放锁之前创建引用它的附件。
+pub fn store_file(vault_dir: &Path, source: &Path) -> Result<StoredFile> {
+    if !source.is_file() {
+        return Err(Error::Validation(t!("error-vault-not-a-file")));
+    }
+
+    let mut hasher = Sha256::new();
+    let size = io::copy(&mut File::open(source)?, &mut hasher)?;
+    let hash = format!("{:x}", hasher.finalize());
+
+    let target = blob_path(vault_dir, &hash);
+    if target.exists() {
+        info!("[Vault] 内容相同的文件已存在, 复用: {}", hash);
+    } else {
+        fs::create_dir_all(target.parent().unwrap())?;
+        // 先复制到临时文件再重命名，避免中途失败时留下不完整的文件
+        let temp = target.with_extension("tmp");
+        fs::copy(source, &temp)?;
+        fs::rename(&temp, &target)?;
+        info!("[Vault] 已将 {} 存入保管库: {}", source.display(), hash);
+    }
+
+    Ok(StoredFile {
+        hash,
+        size: size as i64,
+        mime_type: mime_guess::from_path(source)
+            .first_or_octet_stream()
+            .essence_str()
+            .to_string(),
+        original_name: source
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    })
+}
+
+/// 如果某个文件已不再被任何附件引用，则将其从保管库中删除
+pub fn remove_if_unreferenced(conn: &Connection, vault_dir: &Path, hash: &str) -> Result<()> {
+    if attachment_queries::get_referenced_vault_hashes(conn)?.contains(hash) {
+        return Ok(());
+    }
+    let path = blob_path(vault_dir, hash);
+    if path.exists() {
+        fs::remove_file(&path)?;
+        info!("[Vault] 已删除不再被引用的文件: {}", hash);
+    }
+    Ok(())
+}
+
+/// 垃圾回收：删除保管库中所有不再被引用的文件（包括中断留下的临时文件），返回删除的数量。
+/// 调用方需要持有数据库锁，以免与 `store_file` 同时进行。
+pub fn collect_garbage(conn: &Connection, vault_dir: &Path) -> Result<usize> {
+    if !vault_dir.exists() {
+        return Ok(0);
+    }
+    let referenced = attachment_queries::get_referenced_vault_hashes(conn)?;
+
+    let mut removed = 0;
+    for shard in fs::read_dir(vault_dir)? {
+        let shard = shard?.path();
+        if !shard.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&shard)? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if !referenced.contains(name) {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        // 子目录清空后一并删除
+        if fs::read_dir(&shard)?.next().is_none() {
+            fs::remove_dir(&shard)?;
+        }
+    }
+
+    info!("[Vault] 垃圾回收完成, 删除了 {} 个文件。", removed);
+    Ok(removed)
+}
+
+/// 数据目录迁移时，将保管库一并移动到新的数据目录
+pub fn relocate(old_data_dir: &Path, new_data_dir: &Path) -> Result<()> {
+    let old_vault = vault_dir(old_data_dir);
+    let new_vault = vault_dir(new_data_dir);
+    if !old_vault.exists() || old_vault == new_vault {
+        return Ok(());
+    }
+    info!(
+        "[Vault] 正在从 {} 移动保管库到 {}",
+        old_vault.display(),
+        new_vault.display()
+    );
+    move_tree(&old_vault, &new_vault)?;
+    Ok(())
+}
+
+/// 内部辅助函数：递归地移动目录，目标已存在时进行合并。
+/// 优先使用重命名，跨磁盘等重命名失败的情况下退化为复制后删除。
+fn move_tree(from: &Path, to: &Path) -> io::Result<()> {
+    if !to.exists() && fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            move_tree(&source, &target)?;
+            continue;
+        }
+        // 保管库按内容寻址，同名文件内容必然相同，目标已存在时直接丢弃旧文件即可
+        if !target.exists() {
+            if let Err(e) = fs::rename(&source, &target) {
+                warn!("[Vault] 重命名 {} 失败, 改为复制: {}", source.display(), e);
+                fs::copy(&source, &target)?;
+            }
+        }
+        if source.exists() {
+            fs::remove_file(&source)?;
+        }
+    }
+    fs::remove_dir_all(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::task_queries;
+    use crate::db::test_support::open_test_db;
+
+    /// 为每个测试创建一个独立的临时目录
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("momentum-vault-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn identical_files_are_stored_once() {
+        let dir = temp_dir("dedupe");
+        let vault = vault_dir(&dir);
+        fs::write(dir.join("a.txt"), "same").unwrap();
+        fs::write(dir.join("b.md"), "same").unwrap();
+
+        let a = store_file(&vault, &dir.join("a.txt")).unwrap();
+        let b = store_file(&vault, &dir.join("b.md")).unwrap();
+        assert_eq!(a.hash, b.hash);
+        assert_eq!(a.size, 4);
+        assert_eq!(a.mime_type, "text/plain");
+        assert_eq!(b.original_name, "b.md");
+        assert!(blob_path(&vault, &a.hash).is_file());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn garbage_collection_keeps_referenced_files_only() {
+        let conn = open_test_db();
+        let dir = temp_dir("gc");
+        let vault = vault_dir(&dir);
+        fs::write(dir.join("kept.txt"), "kept").unwrap();
+        fs::write(dir.join("orphan.txt"), "orphan").unwrap();
+        let kept = store_file(&vault, &dir.join("kept.txt")).unwrap();
+        let orphan = store_file(&vault, &dir.join("orphan.txt")).unwrap();
+        // 中断的复制留下的临时文件
+        fs::write(
+            blob_path(&vault, &kept.hash).with_extension("tmp"),
+            "partial",
+        )
+        .unwrap();
+
+        let task = task_queries::create_task(&conn, "t", None, None).unwrap();
+        attachment_queries::create_vault_attachment(
+            &conn,
+            task.id,
+            &kept.hash,
+            kept.size,
+            &kept.mime_type,
+            &kept.original_name,
+        )
+        .unwrap();
+
+        assert_eq!(collect_garbage(&conn, &vault).unwrap(), 2);
+        assert!(blob_path(&vault, &kept.hash).is_file());
+        assert!(!blob_path(&vault, &orphan.hash).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1 +1,3 @@
 pub mod queries;
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -1,15 +1,24 @@
+use crate::db::queries::util::{format_utc, parse_datetime};
 use crate::domain::attachment::{Attachment, AttachmentType};
-use rusqlite::{params, Connection, Result as SqliteResult};
+use chrono::{DateTime, Utc};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, Result as SqliteResult, Row};
+use std::collections::HashSet;
+
+/// 所有读取附件的查询共用的列
+const ATTACHMENT_COLUMNS: &str = "id, task_id, type, payload, title, file_size, mime_type, \
+     original_name, path_exists, last_checked_at, modified_at";
 
 /// 为指定任务创建一个新的 URL 附件
 pub fn create_url_attachment(
     conn: &Connection,
     task_id: i64,
     url: &str,
+    title: Option<&str>,
 ) -> SqliteResult<Attachment> {
     let attachment_type_str: &str = AttachmentType::Url.into();
-    let sql = "INSERT INTO attachments (task_id, type, payload) VALUES (?1, ?2, ?3)";
-    conn.execute(sql, params![task_id, attachment_type_str, url])?;
+    let sql = "INSERT INTO attachments (task_id, type, payload, title) VALUES (?1, ?2, ?3, ?4)";
+    conn.execute(sql, params![task_id, attachment_type_str, url, title])?;
 
     let id = conn.last_insert_rowid();
     get_attachment_by_id(conn, id)
@@ -17,18 +26,10 @@ pub fn create_url_attachment(
 
 /// 获取指定任务的所有附件
 pub fn get_attachments_for_task(conn: &Connection, task_id: i64) -> SqliteResult<Vec<Attachment>> {
-    let sql = "SELECT id, task_id, type, payload FROM attachments WHERE task_id = ?1";
-    let mut stmt = conn.prepare(sql)?;
+    let sql = format!("SELECT {ATTACHMENT_COLUMNS} FROM attachments WHERE task_id = ?1");
+    let mut stmt = conn.prepare(&sql)?;
 
-    let iter = stmt.query_map(params![task_id], |row| {
-        let type_str: String = row.get("type")?;
-        Ok(Attachment {
-            id: row.get("id")?,
-            task_id: row.get("task_id")?,
-            attachment_type: AttachmentType::from(type_str.as_str()),
-            payload: row.get("payload")?,
-        })
-    })?;
+    let iter = stmt.query_map(params![task_id], row_to_attachment)?;
 
     iter.collect()
 }
@@ -39,18 +40,10 @@ pub fn delete_attachment(conn: &Connection, id: i64) -> SqliteResult<usize> {
     conn.execute(sql, params![id])
 }
 
-/// 内部辅助函数：根据 ID 获取单个附件
-fn get_attachment_by_id(conn: &Connection, id: i64) -> SqliteResult<Attachment> {
-    let sql = "SELECT id, task_id, type, payload FROM attachments WHERE id = ?";
-    conn.query_row(sql, params![id], |row| {
-        let type_str: String = row.get("type")?;
-        Ok(Attachment {
-            id: row.get("id")?,
-            task_id: row.get("task_id")?,
-            attachment_type: AttachmentType::from(type_str.as_str()),
-            payload: row.get("payload")?,
-        })
-    })
+/// 根据 ID 获取单个附件
+pub fn get_attachment_by_id(conn: &Connection, id: i64) -> SqliteResult<Attachment> {
+    let sql = format!("SELECT {ATTACHMENT_COLUMNS} FROM attachments WHERE id = ?");
+    conn.query_row(&sql, params![id], row_to_attachment)
 }
 
 /// 为指定任务创建一个新的本地路径附件
@@ -67,3 +60,214 @@ pub fn create_local_path_attachment(
     let id = conn.last_insert_rowid();
     get_attachment_by_id(conn, id)
 }
+
+/// 为指定任务创建一个附件，payload 由调用方按附件类型校验并编码
+pub fn create_attachment(
+    conn: &Connection,
+    task_id: i64,
+    attachment_type: AttachmentType,
+    payload: &str,
+) -> SqliteResult<Attachment> {
+    let attachment_type_str: &str = attachment_type.into();
+    let sql = "INSERT INTO attachments (task_id, type, payload) VALUES (?1, ?2, ?3)";
+    conn.execute(sql, params![task_id, attachment_type_str, payload])?;
+
+    let id = conn.last_insert_rowid();
+    get_attachment_by_id(conn, id)
+}
+
+/// 将一个任务的所有附件复制到另一个任务，包括文件元数据和最近一次健康检查的结果。
+/// 保管库中的文件按内容哈希共享，不需要复制。
+pub fn copy_attachments(
+    conn: &Connection,
+    from_task_id: i64,
+    to_task_id: i64,
+) -> SqliteResult<usize> {
+    let sql = "INSERT INTO attachments (task_id, type, payload, title, file_size, mime_type,
+                   original_name, path_exists, last_checked_at, modified_at)
+               SELECT ?1, type, payload, title, file_size, mime_type,
+                   original_name, path_exists, last_checked_at, modified_at
+               FROM attachments WHERE task_id = ?2 ORDER BY id ASC";
+    conn.execute(sql, params![to_task_id, from_task_id])
+}
+
+/// 更新一个附件的显示标题
+pub fn update_attachment_title(
+    conn: &Connection,
+    id: i64,
+    title: Option<&str>,
+) -> SqliteResult<usize> {
+    let sql = "UPDATE attachments SET title = ?1 WHERE id = ?2";
+    conn.execute(sql, params![title, id])
+}
+
+/// 更新一个附件的 payload（例如编辑笔记内容）
+pub fn update_attachment_payload(conn: &Connection, id: i64, payload: &str) -> SqliteResult<usize> {
+    let sql = "UPDATE attachments SET payload = ?1 WHERE id = ?2";
+    conn.execute(sql, params![payload, id])
+}
+
+/// 为指定任务创建一个保管库附件，payload 为文件内容的哈希
+pub fn create_vault_attachment(
+    conn: &Connection,
+    task_id: i64,
+    hash: &str,
+    file_size: i64,
+    mime_type: &str,
+    original_name: &str,
+) -> SqliteResult<Attachment> {
+    let attachment_type_str: &str = AttachmentType::VaultFile.into();
+    let sql =
+        "INSERT INTO attachments (task_id, type, payload, file_size, mime_type, original_name) 
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
+    conn.execute(
+        sql,
+        params![
+            task_id,
+            attachment_type_str,
+            hash,
+            file_size,
+            mime_type,
+            original_name
+        ],
+    )?;
+
+    let id = conn.last_insert_rowid();
+    get_attachment_by_id(conn, id)
+}
+
+/// 获取所有仍被保管库附件（包括模板中的附件）引用的文件哈希（用于垃圾回收）
+pub fn get_referenced_vault_hashes(conn: &Connection) -> SqliteResult<HashSet<String>> {
+    let attachment_type_str: &str = AttachmentType::VaultFile.into();
+    let sql = "SELECT payload FROM attachments WHERE type = ?1
+               UNION SELECT payload FROM template_attachments WHERE type = ?1";
+    let mut stmt = conn.prepare(sql)?;
+    let iter = stmt.query_map(params![attachment_type_str], |row| row.get("payload"))?;
+    iter.collect()
+}
+
+/// 获取所有指定类型的附件
+pub fn get_attachments_by_type(
+    conn: &Connection,
+    attachment_type: AttachmentType,
+) -> SqliteResult<Vec<Attachment>> {
+    let attachment_type_str: &str = attachment_type.into();
+    let sql = format!("SELECT {ATTACHMENT_COLUMNS} FROM attachments WHERE type = ?1");
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(params![attachment_type_str], row_to_attachment)?;
+    iter.collect()
+}
+
+/// 记录一次健康检查的结果。
+/// 路径不存在时只更新存在状态和检查时间，保留上一次已知的文件元数据，以便之后重新关联。
+/// 只有附件的路径仍然是被检查的 `path` 时才会更新，避免检查期间附件被重新关联后写入过期的结果。
+pub fn update_attachment_health(
+    conn: &Connection,
+    id: i64,
+    path: &str,
+    exists: bool,
+    file_size: Option<i64>,
+    mime_type: Option<&str>,
+    modified_at: Option<DateTime<Utc>>,
+) -> SqliteResult<usize> {
+    let checked_at = "strftime('%Y-%m-%d %H:%M:%S', 'now')";
+    if !exists {
+        let sql = format!(
+            "UPDATE attachments SET path_exists = 0, last_checked_at = {checked_at} WHERE id = ?1 AND payload = ?2"
+        );
+        return conn.execute(&sql, params![id, path]);
+    }
+    let sql = format!(
+        "UPDATE attachments 
+         SET path_exists = 1, last_checked_at = {checked_at}, 
+             file_size = ?1, mime_type = ?2, modified_at = ?3 
+         WHERE id = ?4 AND payload = ?5"
+    );
+    let modified_at_str: Option<String> = modified_at.map(format_utc);
+    conn.execute(
+        &sql,
+        params![file_size, mime_type, modified_at_str, id, path],
+    )
+}
+
+/// 将一个本地路径附件重新关联到新的路径
+pub fn relink_attachment(conn: &Connection, id: i64, new_path: &str) -> SqliteResult<usize> {
+    let sql = "UPDATE attachments SET payload = ?1 WHERE id = ?2";
+    conn.execute(sql, params![new_path, id])
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 Attachment
+fn row_to_attachment(row: &Row) -> SqliteResult<Attachment> {
+    let type_str: String = row.get("type")?;
+    let attachment_type = AttachmentType::try_from(type_str.as_str()).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(
+            row.as_ref().column_index("type").unwrap_or(2),
+            Type::Text,
+            Box::new(e),
+        )
+    })?;
+    Ok(Attachment {
+        id: row.get("id")?,
+        task_id: row.get("task_id")?,
+        attachment_type,
+        payload: row.get("payload")?,
+        title: row.get("title")?,
+        file_size: row.get("file_size")?,
+        mime_type: row.get("mime_type")?,
+        original_name: row.get("original_name")?,
+        exists: row.get::<_, Option<i32>>("path_exists")?.map(|v| v == 1),
+        last_checked_at: row
+            .get::<_, Option<String>>("last_checked_at")?
+            .map(|s| parse_datetime(&s)),
+        modified_at: row
+            .get::<_, Option<String>>("modified_at")?
+            .map(|s| parse_datetime(&s)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::task_queries;
+    use crate::db::test_support::open_test_db;
+
+    #[test]
+    fn health_results_for_a_stale_path_are_ignored() {
+        let conn = open_test_db();
+        let task = task_queries::create_task(&conn, "task", None, None).unwrap();
+        let attachment = create_local_path_attachment(&conn, task.id, "/old/file.txt").unwrap();
+        // 检查期间附件被重新关联到了新路径
+        relink_attachment(&conn, attachment.id, "/new/file.txt").unwrap();
+
+        let updated = update_attachment_health(
+            &conn,
+            attachment.id,
+            "/old/file.txt",
+            false,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(updated, 0);
+        assert_eq!(
+            get_attachment_by_id(&conn, attachment.id).unwrap().exists,
+            None
+        );
+
+        let updated = update_attachment_health(
+            &conn,
+            attachment.id,
+            "/new/file.txt",
+            true,
+            Some(42),
+            Some("text/plain"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(updated, 1);
+        let attachment = get_attachment_by_id(&conn, attachment.id).unwrap();
+        assert_eq!(attachment.exists, Some(true));
+        assert_eq!(attachment.file_size, Some(42));
+    }
+}
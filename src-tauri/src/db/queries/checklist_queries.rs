@@ -0,0 +1,106 @@
+use crate::domain::checklist::ChecklistItem;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection, Result as SqliteResult, Row};
+
+/// 所有读取检查项的查询共用的列
+const CHECKLIST_ITEM_COLUMNS: &str =
+    "id, task_id, text, is_checked, position, created_at, updated_at";
+
+/// 在任务的检查项列表末尾添加一个新检查项
+pub fn create_checklist_item(
+    conn: &Connection,
+    task_id: i64,
+    text: &str,
+) -> SqliteResult<ChecklistItem> {
+    let sql = "INSERT INTO checklist_items (task_id, text, position)
+               VALUES (?1, ?2, (SELECT COALESCE(MAX(position), -1) + 1 FROM checklist_items WHERE task_id = ?1))";
+    conn.execute(sql, params![task_id, text])?;
+    let id = conn.last_insert_rowid();
+    get_checklist_item_by_id(conn, id)
+}
+
+/// 获取任务的所有检查项，按顺序排列
+pub fn get_checklist_items(conn: &Connection, task_id: i64) -> SqliteResult<Vec<ChecklistItem>> {
+    let sql = format!(
+        "SELECT {CHECKLIST_ITEM_COLUMNS} FROM checklist_items WHERE task_id = ? ORDER BY position ASC, id ASC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(params![task_id], row_to_checklist_item)?;
+    iter.collect()
+}
+
+/// 根据 ID 获取单个检查项
+pub fn get_checklist_item_by_id(conn: &Connection, id: i64) -> SqliteResult<ChecklistItem> {
+    let sql = format!("SELECT {CHECKLIST_ITEM_COLUMNS} FROM checklist_items WHERE id = ?");
+    conn.query_row(&sql, params![id], row_to_checklist_item)
+}
+
+/// 修改检查项的文本
+pub fn update_checklist_item_text(
+    conn: &Connection,
+    id: i64,
+    text: &str,
+) -> SqliteResult<ChecklistItem> {
+    let sql = "UPDATE checklist_items SET text = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
+    conn.execute(sql, params![text, id])?;
+    get_checklist_item_by_id(conn, id)
+}
+
+/// 勾选或取消勾选一个检查项
+pub fn set_checklist_item_checked(
+    conn: &Connection,
+    id: i64,
+    is_checked: bool,
+) -> SqliteResult<ChecklistItem> {
+    let sql = "UPDATE checklist_items SET is_checked = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
+    conn.execute(sql, params![is_checked, id])?;
+    get_checklist_item_by_id(conn, id)
+}
+
+/// 按给定的 ID 顺序重新排列任务内的检查项
+pub fn reorder_checklist_items(
+    conn: &Connection,
+    task_id: i64,
+    item_ids: &[i64],
+) -> SqliteResult<()> {
+    let sql = "UPDATE checklist_items SET position = ?1 WHERE id = ?2 AND task_id = ?3";
+    for (position, id) in item_ids.iter().enumerate() {
+        conn.execute(sql, params![position as i64, id, task_id])?;
+    }
+    Ok(())
+}
+
+/// 删除一个检查项
+pub fn delete_checklist_item(conn: &Connection, id: i64) -> SqliteResult<usize> {
+    conn.execute("DELETE FROM checklist_items WHERE id = ?", params![id])
+}
+
+/// 将一个任务的所有检查项按原来的顺序复制到另一个任务，副本均为未勾选状态
+pub fn copy_checklist_items(
+    conn: &Connection,
+    from_task_id: i64,
+    to_task_id: i64,
+) -> SqliteResult<usize> {
+    let sql = "INSERT INTO checklist_items (task_id, text, position)
+               SELECT ?1, text, position FROM checklist_items WHERE task_id = ?2 ORDER BY position ASC, id ASC";
+    conn.execute(sql, params![to_task_id, from_task_id])
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 ChecklistItem
+fn row_to_checklist_item(row: &Row) -> SqliteResult<ChecklistItem> {
+    let created_at_str: String = row.get("created_at")?;
+    let updated_at_str: String = row.get("updated_at")?;
+    Ok(ChecklistItem {
+        id: row.get("id")?,
+        task_id: row.get("task_id")?,
+        text: row.get("text")?,
+        is_checked: row.get("is_checked")?,
+        position: row.get("position")?,
+        created_at: NaiveDateTime::parse_from_str(&created_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+        updated_at: NaiveDateTime::parse_from_str(&updated_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+    })
+}
@@ -0,0 +1,146 @@
+use crate::db::queries::task_queries;
+use crate::domain::task::Task;
+use rusqlite::{params, params_from_iter, Connection, Result as SqliteResult};
+
+/// 添加一条依赖：`task_id` 需要在 `depends_on_task_id` 完成后才能开始。
+/// 依赖已存在时不做任何改动。
+pub fn add_dependency(
+    conn: &Connection,
+    task_id: i64,
+    depends_on_task_id: i64,
+) -> SqliteResult<usize> {
+    let sql =
+        "INSERT OR IGNORE INTO task_dependencies (task_id, depends_on_task_id) VALUES (?1, ?2)";
+    conn.execute(sql, params![task_id, depends_on_task_id])
+}
+
+/// 删除一条依赖
+pub fn remove_dependency(
+    conn: &Connection,
+    task_id: i64,
+    depends_on_task_id: i64,
+) -> SqliteResult<usize> {
+    let sql = "DELETE FROM task_dependencies WHERE task_id = ?1 AND depends_on_task_id = ?2";
+    conn.execute(sql, params![task_id, depends_on_task_id])
+}
+
+/// 判断添加依赖 `task_id -> depends_on_task_id` 是否会形成循环，
+/// 即 `depends_on_task_id` 是否已经直接或间接地依赖于 `task_id`。
+pub fn would_create_cycle(
+    conn: &Connection,
+    task_id: i64,
+    depends_on_task_id: i64,
+) -> SqliteResult<bool> {
+    if task_id == depends_on_task_id {
+        return Ok(true);
+    }
+    let sql = "
+        WITH RECURSIVE upstream(id) AS (
+            SELECT depends_on_task_id FROM task_dependencies WHERE task_id = ?1
+            UNION
+            SELECT d.depends_on_task_id FROM task_dependencies d JOIN upstream u ON d.task_id = u.id
+        )
+        SELECT EXISTS (SELECT 1 FROM upstream WHERE id = ?2)";
+    conn.query_row(sql, params![depends_on_task_id, task_id], |row| row.get(0))
+}
+
+/// 获取指定任务直接依赖的所有任务（包括已完成的）
+pub fn get_dependencies(conn: &Connection, task_id: i64) -> SqliteResult<Vec<Task>> {
+    let mut stmt =
+        conn.prepare("SELECT depends_on_task_id FROM task_dependencies WHERE task_id = ?")?;
+    let ids = stmt
+        .query_map(params![task_id], |row| row.get(0))?
+        .collect::<SqliteResult<Vec<i64>>>()?;
+    task_queries::get_tasks_by_ids(conn, &ids)
+}
+
+/// 获取直接依赖于指定任务的所有任务
+pub fn get_dependents(conn: &Connection, task_id: i64) -> SqliteResult<Vec<Task>> {
+    let mut stmt =
+        conn.prepare("SELECT task_id FROM task_dependencies WHERE depends_on_task_id = ?")?;
+    let ids = stmt
+        .query_map(params![task_id], |row| row.get(0))?
+        .collect::<SqliteResult<Vec<i64>>>()?;
+    task_queries::get_tasks_by_ids(conn, &ids)
+}
+
+/// 在 `blocker_ids` 中的任务完成后，找出依赖于它们、且已不再被任何未完成任务阻塞的未完成任务
+pub fn get_unblocked_dependent_ids(
+    conn: &Connection,
+    blocker_ids: &[i64],
+) -> SqliteResult<Vec<i64>> {
+    if blocker_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let sql = format!(
+        "SELECT DISTINCT d.task_id FROM task_dependencies d
+         JOIN tasks t ON t.id = d.task_id
+         JOIN workflow_statuses ts ON ts.id = t.status_id
+         WHERE d.depends_on_task_id IN ({}) AND ts.counts_as_done = 0
+           AND NOT EXISTS (
+               SELECT 1 FROM task_dependencies o
+               JOIN tasks b ON b.id = o.depends_on_task_id
+               JOIN workflow_statuses bs ON bs.id = b.status_id
+               WHERE o.task_id = d.task_id AND bs.counts_as_done = 0
+           )",
+        task_queries::placeholders(blocker_ids.len())
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(params_from_iter(blocker_ids), |row| row.get(0))?;
+    iter.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::open_test_db;
+
+    /// 创建 `count` 个任务，返回它们的 ID
+    fn create_tasks(conn: &Connection, count: usize) -> Vec<i64> {
+        (0..count)
+            .map(|i| {
+                task_queries::create_task(conn, &format!("task {i}"), None, None)
+                    .unwrap()
+                    .id
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_task_cannot_depend_on_itself() {
+        let conn = open_test_db();
+        let ids = create_tasks(&conn, 1);
+        assert!(would_create_cycle(&conn, ids[0], ids[0]).unwrap());
+    }
+
+    #[test]
+    fn reversing_an_existing_dependency_is_a_cycle() {
+        let conn = open_test_db();
+        let ids = create_tasks(&conn, 2);
+        add_dependency(&conn, ids[0], ids[1]).unwrap();
+        assert!(would_create_cycle(&conn, ids[1], ids[0]).unwrap());
+    }
+
+    #[test]
+    fn closing_a_dependency_chain_is_a_cycle() {
+        let conn = open_test_db();
+        let ids = create_tasks(&conn, 3);
+        // 0 -> 1 -> 2
+        add_dependency(&conn, ids[0], ids[1]).unwrap();
+        add_dependency(&conn, ids[1], ids[2]).unwrap();
+        assert!(would_create_cycle(&conn, ids[2], ids[0]).unwrap());
+    }
+
+    #[test]
+    fn edges_that_keep_the_graph_acyclic_are_allowed() {
+        let conn = open_test_db();
+        let ids = create_tasks(&conn, 4);
+        // 0 -> 1 -> 2，3 独立
+        add_dependency(&conn, ids[0], ids[1]).unwrap();
+        add_dependency(&conn, ids[1], ids[2]).unwrap();
+        // 捷径和汇合都不会形成循环
+        assert!(!would_create_cycle(&conn, ids[0], ids[2]).unwrap());
+        assert!(!would_create_cycle(&conn, ids[3], ids[2]).unwrap());
+        assert!(!would_create_cycle(&conn, ids[2], ids[3]).unwrap());
+    }
+}
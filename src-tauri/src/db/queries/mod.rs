@@ -1,4 +1,13 @@
 pub mod attachment_queries;
+pub mod checklist_queries;
+pub mod dependency_queries;
+pub mod pomodoro_queries;
 pub mod project_queries;
+pub mod section_queries;
+pub mod tag_queries;
 pub mod task_queries;
+pub mod template_queries;
+pub mod time_entry_queries;
+pub mod workflow_status_queries;
 pub mod reminder_queries;
+pub mod util;
@@ -0,0 +1,52 @@
+use crate::db::queries::util::{format_utc, parse_datetime};
+use crate::domain::pomodoro::Pomodoro;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult, Row};
+
+/// 记录一个已完成的专注时段。
+/// 关联的任务在专注期间被删除时，记录仍会保存，但不再关联任务。
+pub fn record_pomodoro(
+    conn: &Connection,
+    task_id: Option<i64>,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    duration_seconds: i64,
+) -> SqliteResult<Pomodoro> {
+    let sql = "INSERT INTO pomodoros (task_id, started_at, ended_at, duration_seconds)
+               VALUES ((SELECT id FROM tasks WHERE id = ?1), ?2, ?3, ?4)";
+    conn.execute(
+        sql,
+        params![
+            task_id,
+            format_utc(started_at),
+            format_utc(ended_at),
+            duration_seconds
+        ],
+    )?;
+    let id = conn.last_insert_rowid();
+    conn.query_row(
+        "SELECT id, task_id, started_at, ended_at, duration_seconds FROM pomodoros WHERE id = ?",
+        params![id],
+        row_to_pomodoro,
+    )
+}
+
+/// 获取任务的所有专注记录，最近的记录在前
+pub fn get_pomodoros_for_task(conn: &Connection, task_id: i64) -> SqliteResult<Vec<Pomodoro>> {
+    let sql = "SELECT id, task_id, started_at, ended_at, duration_seconds FROM pomodoros
+               WHERE task_id = ? ORDER BY started_at DESC";
+    let mut stmt = conn.prepare(sql)?;
+    let iter = stmt.query_map(params![task_id], row_to_pomodoro)?;
+    iter.collect()
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 Pomodoro
+fn row_to_pomodoro(row: &Row) -> SqliteResult<Pomodoro> {
+    Ok(Pomodoro {
+        id: row.get("id")?,
+        task_id: row.get("task_id")?,
+        started_at: parse_datetime(&row.get::<_, String>("started_at")?),
+        ended_at: parse_datetime(&row.get::<_, String>("ended_at")?),
+        duration_seconds: row.get("duration_seconds")?,
+    })
+}
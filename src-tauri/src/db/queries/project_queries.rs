@@ -1,88 +1,435 @@
 // src-tauri/src/db/queries/project_queries.rs
 
-use crate::domain::project::Project;
+use crate::db::queries::{section_queries, task_queries};
+use crate::domain::project::{
+    Project, ProjectDeletionStrategy, ProjectDeletionSummary, ProjectStatus,
+};
 use chrono::NaiveDateTime;
-use rusqlite::{params, Connection, Error as SqliteError, Result as SqliteResult};
-
-/// 创建一个新项目
-pub fn create_project(conn: &Connection, name: &str) -> SqliteResult<Project> {
-    let check_sql = "SELECT id FROM projects WHERE name = ?";
-    let existing: SqliteResult<i64> = conn.query_row(check_sql, params![name], |row| row.get("id"));
-    if existing.is_ok() {
-        return Err(SqliteError::SqliteFailure(
-            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE),
-            Some("一个同名的项目已经存在。".to_string()),
-        ));
+use rusqlite::types::Type;
+use rusqlite::{
+    params, Connection, Error as SqliteError, OptionalExtension, Result as SqliteResult, Row,
+};
+
+/// 已归档的项目及其所有后代项目的 ID，可以作为子查询嵌入到其他查询中，
+/// 例如 `WHERE project_id NOT IN ({ARCHIVED_PROJECT_IDS})`
+pub const ARCHIVED_PROJECT_IDS: &str = "
+    WITH RECURSIVE archived(id) AS (
+        SELECT id FROM projects WHERE status = 'Archived'
+        UNION
+        SELECT c.id FROM projects c JOIN archived a ON c.parent_id = a.id
+    )
+    SELECT id FROM archived";
+
+/// 所有读取项目的查询共用的 SELECT 语句。
+/// `subtree` 是每个项目到它自身及所有后代项目的映射，用于汇总整个子树的任务数量。
+const PROJECT_SELECT: &str = "
+    WITH RECURSIVE subtree(root_id, id) AS (
+        SELECT id, id FROM projects
+        UNION
+        SELECT s.root_id, c.id FROM projects c JOIN subtree s ON c.parent_id = s.id
+    )
+    SELECT
+        p.id, p.name, p.color, p.icon, p.description, p.status, p.parent_id,
+        p.created_at, p.updated_at,
+        (SELECT COUNT(*) FROM tasks t WHERE t.project_id = p.id) AS task_count,
+        (SELECT COUNT(*) FROM tasks t JOIN workflow_statuses ts ON ts.id = t.status_id
+            WHERE t.project_id = p.id AND ts.counts_as_done = 0) AS open_task_count,
+        (SELECT COUNT(*) FROM subtree s JOIN tasks t ON t.project_id = s.id
+            WHERE s.root_id = p.id) AS subtree_task_count,
+        (SELECT COUNT(*) FROM subtree s JOIN tasks t ON t.project_id = s.id
+            JOIN workflow_statuses ts ON ts.id = t.status_id
+            WHERE s.root_id = p.id AND ts.counts_as_done = 0) AS subtree_open_task_count,
+        (SELECT COALESCE(SUM(t.estimate), 0) FROM subtree s JOIN tasks t ON t.project_id = s.id
+            WHERE s.root_id = p.id) AS subtree_estimate,
+        (SELECT COALESCE(SUM(t.estimate), 0) FROM subtree s JOIN tasks t ON t.project_id = s.id
+            JOIN workflow_statuses ts ON ts.id = t.status_id
+            WHERE s.root_id = p.id AND ts.counts_as_done = 0) AS subtree_remaining_estimate
+    FROM projects p";
+
+/// 创建一个新项目，`parent_id` 为空时创建顶层项目
+pub fn create_project(
+    conn: &Connection,
+    name: &str,
+    parent_id: Option<i64>,
+) -> SqliteResult<Project> {
+    if let Some(parent_id) = parent_id {
+        // 确保父项目存在
+        get_project_by_id(conn, parent_id)?;
     }
 
-    let sql = "INSERT INTO projects (name) VALUES (?)";
-    conn.execute(sql, params![name])?;
+    let sql = "INSERT INTO projects (name, parent_id) VALUES (?1, ?2)";
+    conn.execute(sql, params![name, parent_id])?;
     let id = conn.last_insert_rowid();
     get_project_by_id(conn, id)
 }
 
-/// 获取所有项目
-pub fn get_all_projects(conn: &Connection) -> SqliteResult<Vec<Project>> {
-    let sql = "SELECT id, name, created_at, updated_at FROM projects ORDER BY name ASC";
-    let mut stmt = conn.prepare(sql)?;
-
-    let project_iter = stmt.query_map([], |row| {
-        let created_at_str: String = row.get("created_at")?;
-        let updated_at_str: String = row.get("updated_at")?;
-        Ok(Project {
-            id: row.get("id")?,
-            name: row.get("name")?,
-            created_at: NaiveDateTime::parse_from_str(&created_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-            updated_at: NaiveDateTime::parse_from_str(&updated_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-        })
-    })?;
-
+/// 获取所有项目（扁平列表，通过 `parent_id` 组成层级）。
+/// 默认不包含已归档的项目及其后代项目。
+pub fn get_all_projects(conn: &Connection, include_archived: bool) -> SqliteResult<Vec<Project>> {
+    let sql = if include_archived {
+        format!("{PROJECT_SELECT} ORDER BY p.name ASC")
+    } else {
+        format!("{PROJECT_SELECT} WHERE p.id NOT IN ({ARCHIVED_PROJECT_IDS}) ORDER BY p.name ASC")
+    };
+    let mut stmt = conn.prepare(&sql)?;
+    let project_iter = stmt.query_map([], row_to_project)?;
     project_iter.collect()
 }
 
 /// 更新一个现有项目的名称
 pub fn update_project(conn: &Connection, id: i64, name: &str) -> SqliteResult<Project> {
-    let check_sql = "SELECT id FROM projects WHERE name = ? AND id != ?";
-    let existing: SqliteResult<i64> =
-        conn.query_row(check_sql, params![name, id], |row| row.get("id"));
-    if existing.is_ok() {
-        return Err(SqliteError::SqliteFailure(
-            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE),
-            Some("一个同名的项目已经存在。".to_string()),
-        ));
-    }
-
     let sql =
         "UPDATE projects SET name = ?, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?";
     conn.execute(sql, params![name, id])?;
     get_project_by_id(conn, id)
 }
 
-/// 删除一个项目
-pub fn delete_project(conn: &Connection, id: i64) -> SqliteResult<usize> {
-    let sql = "DELETE FROM projects WHERE id = ?";
-    conn.execute(sql, params![id])
+/// 更新项目的颜色、图标和描述
+pub fn update_project_details(
+    conn: &Connection,
+    id: i64,
+    color: Option<&str>,
+    icon: Option<&str>,
+    description: Option<&str>,
+) -> SqliteResult<Project> {
+    let sql = "UPDATE projects 
+               SET color = ?1, icon = ?2, description = ?3, 
+                   updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') 
+               WHERE id = ?4";
+    conn.execute(sql, params![color, icon, description, id])?;
+    get_project_by_id(conn, id)
+}
+
+/// 更新项目的状态
+pub fn update_project_status(
+    conn: &Connection,
+    id: i64,
+    status: ProjectStatus,
+) -> SqliteResult<Project> {
+    let status_str: &str = status.into();
+    let sql = "UPDATE projects SET status = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
+    conn.execute(sql, params![status_str, id])?;
+    get_project_by_id(conn, id)
+}
+
+/// 将项目移动到新的父项目下，`new_parent_id` 为空时移动到顶层。
+/// 调用方需要先确认新的父项目不是该项目自身或其后代，以免形成循环。
+pub fn move_project(
+    conn: &Connection,
+    id: i64,
+    new_parent_id: Option<i64>,
+) -> SqliteResult<Project> {
+    let sql =
+        "UPDATE projects SET parent_id = ?, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?";
+    conn.execute(sql, params![new_parent_id, id])?;
+    get_project_by_id(conn, id)
 }
 
-/// 内部辅助函数：根据 ID 获取单个项目
-fn get_project_by_id(conn: &Connection, id: i64) -> SqliteResult<Project> {
-    let sql = "SELECT id, name, created_at, updated_at FROM projects WHERE id = ?";
-    conn.query_row(sql, params![id], |row| {
-        let created_at_str: String = row.get("created_at")?;
-        let updated_at_str: String = row.get("updated_at")?;
-        Ok(Project {
-            id: row.get("id")?,
-            name: row.get("name")?,
-            created_at: NaiveDateTime::parse_from_str(&created_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-            updated_at: NaiveDateTime::parse_from_str(&updated_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-        })
+/// 获取一个项目的所有后代项目的 ID（不包含自身）
+pub fn get_descendant_project_ids(conn: &Connection, id: i64) -> SqliteResult<Vec<i64>> {
+    let sql = "
+        WITH RECURSIVE descendants(id) AS (
+            SELECT id FROM projects WHERE parent_id = ?1
+            UNION
+            SELECT c.id FROM projects c JOIN descendants d ON c.parent_id = d.id
+        )
+        SELECT id FROM descendants";
+    let mut stmt = conn.prepare(sql)?;
+    let iter = stmt.query_map(params![id], |row| row.get("id"))?;
+    iter.collect()
+}
+
+/// 删除一个项目，并按照指定的策略处理项目中的任务（包含它们的子任务）。
+/// 它的直接子项目会被移动到它的父项目下，它的分组和默认提醒会被一并删除。
+/// 调用方需要在事务中执行，以保证删除过程的原子性；
+/// 并且需要先确认子项目移动后不会与父项目下已有的项目重名。
+pub fn delete_project(
+    conn: &Connection,
+    id: i64,
+    strategy: ProjectDeletionStrategy,
+) -> SqliteResult<ProjectDeletionSummary> {
+    let project = get_project_by_id(conn, id)?;
+    let mut summary = ProjectDeletionSummary {
+        project_id: id,
+        moved_task_count: 0,
+        deleted_task_count: 0,
+        reparented_project_count: 0,
+    };
+
+    let root_ids = task_queries::get_task_ids_by_project(conn, id)?;
+    let task_ids = task_queries::get_subtree_task_ids(conn, &root_ids)?;
+    match strategy {
+        ProjectDeletionStrategy::MoveToInbox => {
+            summary.moved_task_count =
+                task_queries::move_tasks_to_project(conn, &task_ids, None)? as i64;
+        }
+        ProjectDeletionStrategy::MoveToProject(target_id) => {
+            summary.moved_task_count =
+                task_queries::move_tasks_to_project(conn, &task_ids, Some(target_id))? as i64;
+        }
+        ProjectDeletionStrategy::DeleteTasks => {
+            // 级联删除的子任务不会计入受影响的行数，因此直接使用收集到的任务数量
+            task_queries::delete_tasks_by_ids(conn, &task_ids)?;
+            summary.deleted_task_count = task_ids.len() as i64;
+        }
+    }
+
+    section_queries::delete_sections_for_project(conn, id)?;
+    set_default_reminder_offsets(conn, id, &[])?;
+
+    // 子项目可能与被删除的项目同名，因此先删除项目、再移动子项目。
+    // 子项目对项目的外键检查推迟到事务提交时进行，此时它们已经指向新的父项目。
+    let children = get_child_projects(conn, id)?;
+    conn.execute_batch("PRAGMA defer_foreign_keys = ON")?;
+    conn.execute("DELETE FROM projects WHERE id = ?", params![id])?;
+    for child in children {
+        move_project(conn, child.id, project.parent_id)?;
+        summary.reparented_project_count += 1;
+    }
+    Ok(summary)
+}
+
+/// 根据 ID 获取单个项目
+pub fn get_project_by_id(conn: &Connection, id: i64) -> SqliteResult<Project> {
+    let sql = format!("{PROJECT_SELECT} WHERE p.id = ?");
+    conn.query_row(&sql, params![id], row_to_project)
+}
+
+/// 获取一个项目的直接子项目
+pub fn get_child_projects(conn: &Connection, parent_id: i64) -> SqliteResult<Vec<Project>> {
+    let sql = format!("{PROJECT_SELECT} WHERE p.parent_id = ? ORDER BY p.name ASC");
+    let mut stmt = conn.prepare(&sql)?;
+    let project_iter = stmt.query_map(params![parent_id], row_to_project)?;
+    project_iter.collect()
+}
+
+/// 查找同一个父项目下指定名称的项目，`parent_id` 为空时在顶层项目中查找。
+/// 项目名称在同一个父项目下唯一，调用方应在创建、重命名或移动项目前检查。
+pub fn get_sibling_id_by_name(
+    conn: &Connection,
+    parent_id: Option<i64>,
+    name: &str,
+) -> SqliteResult<Option<i64>> {
+    conn.query_row(
+        "SELECT id FROM projects WHERE name = ?1 AND parent_id IS ?2",
+        params![name, parent_id],
+        |row| row.get("id"),
+    )
+    .optional()
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 Project
+fn row_to_project(row: &Row) -> SqliteResult<Project> {
+    let created_at_str: String = row.get("created_at")?;
+    let updated_at_str: String = row.get("updated_at")?;
+    let status_str: String = row.get("status")?;
+    let status = ProjectStatus::try_from(status_str.as_str()).map_err(|e| {
+        SqliteError::FromSqlConversionFailure(
+            row.as_ref().column_index("status").unwrap_or(5),
+            Type::Text,
+            Box::new(e),
+        )
+    })?;
+    Ok(Project {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        color: row.get("color")?,
+        icon: row.get("icon")?,
+        description: row.get("description")?,
+        status,
+        parent_id: row.get("parent_id")?,
+        task_count: row.get("task_count")?,
+        open_task_count: row.get("open_task_count")?,
+        subtree_task_count: row.get("subtree_task_count")?,
+        subtree_open_task_count: row.get("subtree_open_task_count")?,
+        subtree_estimate: row.get("subtree_estimate")?,
+        subtree_remaining_estimate: row.get("subtree_remaining_estimate")?,
+        stats: None,
+        created_at: NaiveDateTime::parse_from_str(&created_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+        updated_at: NaiveDateTime::parse_from_str(&updated_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
     })
-}
\ No newline at end of file
+}
+
+/// 获取项目的默认提醒偏移量（单位：分钟），按从远到近排序
+pub fn get_default_reminder_offsets(conn: &Connection, project_id: i64) -> SqliteResult<Vec<i64>> {
+    let sql = "SELECT offset_minutes FROM project_reminder_defaults 
+               WHERE project_id = ? ORDER BY offset_minutes DESC";
+    let mut stmt = conn.prepare(sql)?;
+    let iter = stmt.query_map(params![project_id], |row| row.get("offset_minutes"))?;
+    iter.collect()
+}
+
+/// 用给定的偏移量列表整体替换项目的默认提醒
+pub fn set_default_reminder_offsets(
+    conn: &Connection,
+    project_id: i64,
+    offsets_minutes: &[i64],
+) -> SqliteResult<()> {
+    conn.execute(
+        "DELETE FROM project_reminder_defaults WHERE project_id = ?",
+        params![project_id],
+    )?;
+    let sql = "INSERT OR IGNORE INTO project_reminder_defaults (project_id, offset_minutes) VALUES (?1, ?2)";
+    for offset in offsets_minutes {
+        conn.execute(sql, params![project_id, offset])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::workflow_status_queries;
+    use crate::db::test_support::open_test_db;
+
+    #[test]
+    fn estimates_roll_up_through_sub_projects() {
+        let conn = open_test_db();
+        let area = create_project(&conn, "Area", None).unwrap();
+        let child = create_project(&conn, "Child", Some(area.id)).unwrap();
+        let grandchild = create_project(&conn, "Grandchild", Some(child.id)).unwrap();
+        for (project_id, estimate, done) in [
+            (area.id, 10, false),
+            (child.id, 20, true),
+            (grandchild.id, 40, false),
+        ] {
+            let task = task_queries::create_task(&conn, "t", Some(project_id), None).unwrap();
+            task_queries::update_task_estimate(&conn, task.id, Some(estimate)).unwrap();
+            task_queries::update_task_status(&conn, task.id, done).unwrap();
+        }
+        task_queries::create_task(&conn, "unestimated", Some(child.id), None).unwrap();
+
+        let area = get_project_by_id(&conn, area.id).unwrap();
+        assert_eq!(
+            (area.subtree_estimate, area.subtree_remaining_estimate),
+            (70, 50)
+        );
+        let child = get_project_by_id(&conn, child.id).unwrap();
+        assert_eq!(
+            (child.subtree_estimate, child.subtree_remaining_estimate),
+            (60, 40)
+        );
+        let grandchild = get_project_by_id(&conn, grandchild.id).unwrap();
+        assert_eq!(grandchild.subtree_estimate, 40);
+    }
+
+    /// 在事务中删除项目，与 `delete_project` 指令的调用方式一致
+    fn delete(
+        conn: &mut Connection,
+        id: i64,
+        strategy: ProjectDeletionStrategy,
+    ) -> ProjectDeletionSummary {
+        let tx = conn.transaction().unwrap();
+        let summary = delete_project(&tx, id, strategy).unwrap();
+        tx.commit().unwrap();
+        summary
+    }
+
+    #[test]
+    fn deleting_into_the_inbox_moves_task_trees_and_cleans_up_the_project() {
+        let mut conn = open_test_db();
+        let area = create_project(&conn, "Area", None).unwrap();
+        let project = create_project(&conn, "Project", Some(area.id)).unwrap();
+        let child = create_project(&conn, "Child", Some(project.id)).unwrap();
+        let section = section_queries::create_section(&conn, project.id, "Later").unwrap();
+        set_default_reminder_offsets(&conn, project.id, &[15]).unwrap();
+        let task = task_queries::create_task(&conn, "task", Some(project.id), None).unwrap();
+        task_queries::update_task_section(&conn, task.id, Some(section.id)).unwrap();
+        let subtask =
+            task_queries::create_task(&conn, "subtask", Some(project.id), Some(task.id)).unwrap();
+
+        let summary = delete(&mut conn, project.id, ProjectDeletionStrategy::MoveToInbox);
+
+        assert_eq!(
+            (
+                summary.moved_task_count,
+                summary.deleted_task_count,
+                summary.reparented_project_count
+            ),
+            (2, 0, 1)
+        );
+        for id in [task.id, subtask.id] {
+            let task = task_queries::get_task_by_id(&conn, id).unwrap();
+            assert_eq!((task.project_id, task.section_id), (None, None));
+        }
+        assert_eq!(
+            get_project_by_id(&conn, child.id).unwrap().parent_id,
+            Some(area.id)
+        );
+        assert!(get_project_by_id(&conn, project.id).is_err());
+        assert!(section_queries::get_sections_for_project(&conn, project.id)
+            .unwrap()
+            .is_empty());
+        assert!(get_default_reminder_offsets(&conn, project.id)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn deleting_into_another_project_remaps_statuses() {
+        let mut conn = open_test_db();
+        let project = create_project(&conn, "Project", None).unwrap();
+        let target = create_project(&conn, "Target", None).unwrap();
+        workflow_status_queries::create_status(&conn, Some(target.id), "Backlog", false).unwrap();
+        let shipped =
+            workflow_status_queries::create_status(&conn, Some(target.id), "Shipped", true)
+                .unwrap();
+        let open = task_queries::create_task(&conn, "open", Some(project.id), None).unwrap();
+        let done = task_queries::create_task(&conn, "done", Some(project.id), None).unwrap();
+        task_queries::update_task_status(&conn, done.id, true).unwrap();
+
+        let summary = delete(
+            &mut conn,
+            project.id,
+            ProjectDeletionStrategy::MoveToProject(target.id),
+        );
+
+        assert_eq!(summary.moved_task_count, 2);
+        let open = task_queries::get_task_by_id(&conn, open.id).unwrap();
+        assert_eq!(open.project_id, Some(target.id));
+        assert_eq!(open.status.project_id, Some(target.id));
+        assert!(!open.is_completed);
+        let done = task_queries::get_task_by_id(&conn, done.id).unwrap();
+        assert_eq!(done.status.id, shipped.id);
+        assert!(done.is_completed);
+        let target = get_project_by_id(&conn, target.id).unwrap();
+        assert_eq!((target.task_count, target.open_task_count), (2, 1));
+    }
+
+    #[test]
+    fn deleting_tasks_counts_their_descendants() {
+        let mut conn = open_test_db();
+        let project = create_project(&conn, "Project", None).unwrap();
+        let task = task_queries::create_task(&conn, "task", Some(project.id), None).unwrap();
+        let subtask =
+            task_queries::create_task(&conn, "subtask", Some(project.id), Some(task.id)).unwrap();
+        // 子任务在收件箱中，但仍随父任务一起删除
+        let nested = task_queries::create_task(&conn, "nested", None, Some(subtask.id)).unwrap();
+
+        let summary = delete(&mut conn, project.id, ProjectDeletionStrategy::DeleteTasks);
+
+        assert_eq!(
+            (summary.moved_task_count, summary.deleted_task_count),
+            (0, 3)
+        );
+        for id in [task.id, subtask.id, nested.id] {
+            assert!(task_queries::get_task_by_id(&conn, id).is_err());
+        }
+    }
+
+    #[test]
+    fn sub_projects_may_share_the_deleted_projects_name() {
+        let mut conn = open_test_db();
+        let project = create_project(&conn, "Docs", None).unwrap();
+        let child = create_project(&conn, "Docs", Some(project.id)).unwrap();
+
+        let summary = delete(&mut conn, project.id, ProjectDeletionStrategy::MoveToInbox);
+
+        assert_eq!(summary.reparented_project_count, 1);
+        assert_eq!(get_project_by_id(&conn, child.id).unwrap().parent_id, None);
+    }
+}
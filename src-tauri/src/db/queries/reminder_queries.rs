@@ -1,6 +1,10 @@
 use crate::domain::reminder::Reminder;
-use chrono::{DateTime, NaiveDateTime, Utc};
-use rusqlite::{params, Connection, Result as SqliteResult};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult, Row};
+
+/// 所有读取提醒的查询共用的列
+const REMINDER_COLUMNS: &str = "id, task_id, remind_at, is_sent, offset_minutes, sent_at, \
+     is_dismissed, snooze_count, is_missed, deferred_until, snoozed_until";
 
 /// 为指定任务创建一个新的提醒
 pub fn create_reminder(
@@ -15,23 +19,31 @@ pub fn create_reminder(
     get_reminder_by_id(conn, id)
 }
 
+/// 为指定任务创建一个相对于截止日期的提醒
+/// 实际的提醒时间由 `due_date - offset_minutes` 计算得出
+pub fn create_relative_reminder(
+    conn: &Connection,
+    task_id: i64,
+    offset_minutes: i64,
+    due_date: DateTime<Utc>,
+) -> SqliteResult<Reminder> {
+    let sql = "INSERT INTO reminders (task_id, remind_at, offset_minutes) VALUES (?1, ?2, ?3)";
+    let remind_at = due_date - Duration::minutes(offset_minutes);
+    let remind_at_str = remind_at.format("%Y-%m-%d %H:%M:%S").to_string();
+    conn.execute(sql, params![task_id, remind_at_str, offset_minutes])?;
+    let id = conn.last_insert_rowid();
+    get_reminder_by_id(conn, id)
+}
+
 /// 获取指定任务的所有提醒
 pub fn get_reminders_for_task(conn: &Connection, task_id: i64) -> SqliteResult<Vec<Reminder>> {
-    let sql = "SELECT id, task_id, remind_at, is_sent FROM reminders 
-               WHERE task_id = ?1 AND is_sent = 0 
-               ORDER BY remind_at ASC";
-    let mut stmt = conn.prepare(sql)?;
-    let iter = stmt.query_map(params![task_id], |row| {
-        let remind_at_str: String = row.get("remind_at")?;
-        Ok(Reminder {
-            id: row.get("id")?,
-            task_id: row.get("task_id")?,
-            remind_at: NaiveDateTime::parse_from_str(&remind_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-            is_sent: row.get::<_, i32>("is_sent")? == 1,
-        })
-    })?;
+    let sql = format!(
+        "SELECT {REMINDER_COLUMNS} FROM reminders 
+         WHERE task_id = ?1 AND is_sent = 0 AND is_missed = 0 
+         ORDER BY remind_at ASC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(params![task_id], row_to_reminder)?;
     iter.collect()
 }
 
@@ -41,33 +53,160 @@ pub fn delete_reminder(conn: &Connection, id: i64) -> SqliteResult<usize> {
     conn.execute(sql, params![id])
 }
 
+/// 根据新的截止日期，重新计算指定任务所有相对提醒的提醒时间。
+/// 提醒时间发生变化的提醒会被重置为“未发送”（及“未错过”），以便在新的时间点再次触发。
+/// 被推迟且尚未再次发出的提醒保持用户选择的时间不变。
+/// 返回该任务拥有的相对提醒数量。
+pub fn reschedule_relative_reminders(
+    conn: &Connection,
+    task_id: i64,
+    due_date: DateTime<Utc>,
+) -> SqliteResult<usize> {
+    let relative: Vec<(i64, i64)> = {
+        let sql = "SELECT id, offset_minutes FROM reminders 
+                   WHERE task_id = ?1 AND offset_minutes IS NOT NULL";
+        let mut stmt = conn.prepare(sql)?;
+        let iter = stmt.query_map(params![task_id], |row| {
+            Ok((row.get("id")?, row.get("offset_minutes")?))
+        })?;
+        iter.collect::<SqliteResult<_>>()?
+    };
+
+    // SQLite 在 UPDATE 中引用的是旧值，因此可以先比较再赋值
+    let sql = "UPDATE reminders 
+               SET is_sent = CASE WHEN remind_at = ?1 THEN is_sent ELSE 0 END, 
+                   is_missed = CASE WHEN remind_at = ?1 THEN is_missed ELSE 0 END, 
+                   deferred_until = CASE WHEN remind_at = ?1 THEN deferred_until ELSE NULL END, 
+                   snoozed_until = NULL, 
+                   remind_at = ?1 
+               WHERE id = ?2 AND NOT (snoozed_until IS NOT NULL AND is_sent = 0)";
+    for (id, offset_minutes) in &relative {
+        let remind_at = due_date - Duration::minutes(*offset_minutes);
+        let remind_at_str = remind_at.format("%Y-%m-%d %H:%M:%S").to_string();
+        conn.execute(sql, params![remind_at_str, id])?;
+    }
+
+    Ok(relative.len())
+}
+
+/// 删除指定任务的所有相对提醒（例如截止日期被清除时）
+pub fn delete_relative_reminders(conn: &Connection, task_id: i64) -> SqliteResult<usize> {
+    let sql = "DELETE FROM reminders WHERE task_id = ? AND offset_minutes IS NOT NULL";
+    conn.execute(sql, params![task_id])
+}
+
 /// (为后台服务使用) 获取所有已到期且未发送的提醒
 pub fn get_due_reminders(conn: &Connection) -> SqliteResult<Vec<Reminder>> {
-    let sql = "SELECT id, task_id, remind_at, is_sent FROM reminders 
-               WHERE is_sent = 0 AND remind_at <= strftime('%Y-%m-%d %H:%M:%S', 'now')";
-    let mut stmt = conn.prepare(sql)?;
-    let iter = stmt.query_map([], |row| {
-        let remind_at_str: String = row.get("remind_at")?;
-        Ok(Reminder {
-            id: row.get("id")?,
-            task_id: row.get("task_id")?,
-            remind_at: NaiveDateTime::parse_from_str(&remind_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-            is_sent: row.get::<_, i32>("is_sent")? == 1,
-        })
-    })?;
+    let sql = format!(
+        "SELECT {REMINDER_COLUMNS} FROM reminders 
+         WHERE is_sent = 0 AND is_missed = 0 
+           AND remind_at <= strftime('%Y-%m-%d %H:%M:%S', 'now') 
+           AND (deferred_until IS NULL OR deferred_until <= strftime('%Y-%m-%d %H:%M:%S', 'now')) 
+         ORDER BY remind_at ASC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map([], row_to_reminder)?;
     iter.collect()
 }
 
 /// (为后台服务使用) 将一组提醒标记为已发送
 pub fn mark_reminders_as_sent(conn: &Connection, ids: &[i64]) -> SqliteResult<usize> {
+    update_reminders_by_ids(
+        conn,
+        "is_sent = 1, sent_at = strftime('%Y-%m-%d %H:%M:%S', 'now')",
+        ids,
+    )
+}
+
+/// (为后台服务使用) 将一组提醒标记为已错过
+pub fn mark_reminders_as_missed(conn: &Connection, ids: &[i64]) -> SqliteResult<usize> {
+    update_reminders_by_ids(conn, "is_missed = 1", ids)
+}
+
+/// (为后台服务使用) 将一组提醒推迟到免打扰时段结束后再发送
+pub fn defer_reminders(
+    conn: &Connection,
+    ids: &[i64],
+    until: DateTime<Utc>,
+) -> SqliteResult<usize> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let sql = format!(
+        "UPDATE reminders SET deferred_until = ?1 WHERE id IN ({})",
+        ids.iter().map(|_| "?").collect::<Vec<_>>().join(", ")
+    );
+    let until_str = until.format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut params_vec: Vec<&dyn rusqlite::ToSql> = vec![&until_str];
+    params_vec.extend(ids.iter().map(|id| id as &dyn rusqlite::ToSql));
+    conn.execute(&sql, &params_vec[..])
+}
+
+/// 获取所有错过且尚未被用户处理的提醒，最近错过的在前
+pub fn get_missed_reminders(conn: &Connection) -> SqliteResult<Vec<Reminder>> {
+    let sql = format!(
+        "SELECT {REMINDER_COLUMNS} FROM reminders 
+         WHERE is_missed = 1 AND is_dismissed = 0 
+         ORDER BY remind_at DESC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map([], row_to_reminder)?;
+    iter.collect()
+}
+
+/// 获取收件箱中的提醒：已发出但用户尚未处理的提醒，最新的在前
+pub fn get_inbox_reminders(conn: &Connection) -> SqliteResult<Vec<Reminder>> {
+    let sql = format!(
+        "SELECT {REMINDER_COLUMNS} FROM reminders 
+         WHERE is_sent = 1 AND is_dismissed = 0 
+         ORDER BY remind_at DESC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map([], row_to_reminder)?;
+    iter.collect()
+}
+
+/// 将一个提醒推迟到新的时间点。
+/// 直接复用原有的提醒记录：重置发送状态并累加推迟次数，而不是新建一条提醒。
+pub fn snooze_reminder(
+    conn: &Connection,
+    id: i64,
+    remind_at: DateTime<Utc>,
+) -> SqliteResult<Reminder> {
+    let sql = "UPDATE reminders 
+               SET remind_at = ?1, is_sent = 0, sent_at = NULL, is_dismissed = 0, is_missed = 0, 
+                   deferred_until = NULL, snoozed_until = ?1, 
+                   snooze_count = snooze_count + 1 
+               WHERE id = ?2";
+    let remind_at_str = remind_at.format("%Y-%m-%d %H:%M:%S").to_string();
+    conn.execute(sql, params![remind_at_str, id])?;
+    get_reminder_by_id(conn, id)
+}
+
+/// 将一个提醒标记为已处理，使其离开收件箱
+pub fn dismiss_reminder(conn: &Connection, id: i64) -> SqliteResult<usize> {
+    let sql = "UPDATE reminders SET is_dismissed = 1 WHERE id = ?";
+    conn.execute(sql, params![id])
+}
+
+/// 根据 ID 获取单个提醒
+pub fn get_reminder_by_id(conn: &Connection, id: i64) -> SqliteResult<Reminder> {
+    let sql = format!("SELECT {REMINDER_COLUMNS} FROM reminders WHERE id = ?");
+    conn.query_row(&sql, params![id], row_to_reminder)
+}
+
+/// 内部辅助函数：对一组提醒批量执行同一个 SET 子句
+fn update_reminders_by_ids(
+    conn: &Connection,
+    set_clause: &str,
+    ids: &[i64],
+) -> SqliteResult<usize> {
     if ids.is_empty() {
         return Ok(0);
     }
-    // 使用 rusqlite 的 `vtab` 功能来高效地处理 `IN` 查询
     let sql = format!(
-        "UPDATE reminders SET is_sent = 1 WHERE id IN ({})",
+        "UPDATE reminders SET {} WHERE id IN ({})",
+        set_clause,
         ids.iter().map(|_| "?").collect::<Vec<_>>().join(", ")
     );
     let params_vec: Vec<&dyn rusqlite::ToSql> =
@@ -75,18 +214,83 @@ pub fn mark_reminders_as_sent(conn: &Connection, ids: &[i64]) -> SqliteResult<us
     conn.execute(&sql, &params_vec[..])
 }
 
-/// 内部辅助函数：根据 ID 获取单个提醒
-fn get_reminder_by_id(conn: &Connection, id: i64) -> SqliteResult<Reminder> {
-    let sql = "SELECT id, task_id, remind_at, is_sent FROM reminders WHERE id = ?";
-    conn.query_row(sql, params![id], |row| {
-        let remind_at_str: String = row.get("remind_at")?;
-        Ok(Reminder {
-            id: row.get("id")?,
-            task_id: row.get("task_id")?,
-            remind_at: NaiveDateTime::parse_from_str(&remind_at_str, "%Y-%m-%d %H:%M:%S")
+/// 内部辅助函数：将查询结果的一行映射为 Reminder
+fn row_to_reminder(row: &Row) -> SqliteResult<Reminder> {
+    let remind_at_str: String = row.get("remind_at")?;
+    Ok(Reminder {
+        id: row.get("id")?,
+        task_id: row.get("task_id")?,
+        remind_at: NaiveDateTime::parse_from_str(&remind_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+        is_sent: row.get::<_, i32>("is_sent")? == 1,
+        offset_minutes: row.get("offset_minutes")?,
+        sent_at: row.get::<_, Option<String>>("sent_at")?.map(|s| {
+            NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+                .unwrap()
+                .and_utc()
+        }),
+        is_dismissed: row.get::<_, i32>("is_dismissed")? == 1,
+        snooze_count: row.get("snooze_count")?,
+        is_missed: row.get::<_, i32>("is_missed")? == 1,
+        deferred_until: row.get::<_, Option<String>>("deferred_until")?.map(|s| {
+            NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+                .unwrap()
+                .and_utc()
+        }),
+        snoozed_until: row.get::<_, Option<String>>("snoozed_until")?.map(|s| {
+            NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
                 .unwrap()
-                .and_utc(),
-            is_sent: row.get::<_, i32>("is_sent")? == 1,
-        })
+                .and_utc()
+        }),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::task_queries;
+    use crate::db::test_support::open_test_db;
+    use chrono::TimeZone;
+
+    #[test]
+    fn reschedule_moves_relative_reminders_with_the_due_date() {
+        let conn = open_test_db();
+        let task = task_queries::create_task(&conn, "t", None, None).unwrap();
+        let due = Utc.with_ymd_and_hms(2030, 1, 2, 10, 0, 0).unwrap();
+        create_relative_reminder(&conn, task.id, 15, due).unwrap();
+
+        let new_due = due + Duration::hours(1);
+        assert_eq!(
+            reschedule_relative_reminders(&conn, task.id, new_due).unwrap(),
+            1
+        );
+        let reminders = get_reminders_for_task(&conn, task.id).unwrap();
+        assert_eq!(reminders[0].remind_at, new_due - Duration::minutes(15));
+    }
+
+    #[test]
+    fn reschedule_keeps_an_active_snooze() {
+        let conn = open_test_db();
+        let task = task_queries::create_task(&conn, "t", None, None).unwrap();
+        let due = Utc.with_ymd_and_hms(2030, 1, 2, 10, 0, 0).unwrap();
+        let reminder = create_relative_reminder(&conn, task.id, 15, due).unwrap();
+        mark_reminders_as_sent(&conn, &[reminder.id]).unwrap();
+        let snoozed_to = due + Duration::hours(3);
+        snooze_reminder(&conn, reminder.id, snoozed_to).unwrap();
+
+        reschedule_relative_reminders(&conn, task.id, due + Duration::days(1)).unwrap();
+        let reminder = get_reminder_by_id(&conn, reminder.id).unwrap();
+        assert_eq!(reminder.remind_at, snoozed_to);
+        assert_eq!(reminder.snoozed_until, Some(snoozed_to));
+
+        // 推迟的提醒再次发出之后，恢复跟随截止日期
+        mark_reminders_as_sent(&conn, &[reminder.id]).unwrap();
+        let new_due = due + Duration::days(2);
+        reschedule_relative_reminders(&conn, task.id, new_due).unwrap();
+        let reminder = get_reminder_by_id(&conn, reminder.id).unwrap();
+        assert_eq!(reminder.remind_at, new_due - Duration::minutes(15));
+        assert_eq!(reminder.snoozed_until, None);
+        assert!(!reminder.is_sent);
+    }
+}
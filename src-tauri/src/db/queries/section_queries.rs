@@ -0,0 +1,91 @@
+use crate::domain::section::Section;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection, Result as SqliteResult, Row};
+
+/// 所有读取分组的查询共用的列
+const SECTION_COLUMNS: &str = "id, project_id, name, position, created_at, updated_at";
+
+/// 在项目的末尾创建一个新分组
+pub fn create_section(conn: &Connection, project_id: i64, name: &str) -> SqliteResult<Section> {
+    let sql = "INSERT INTO sections (project_id, name, position) 
+               VALUES (?1, ?2, (SELECT COALESCE(MAX(position), -1) + 1 FROM sections WHERE project_id = ?1))";
+    conn.execute(sql, params![project_id, name])?;
+    let id = conn.last_insert_rowid();
+    get_section_by_id(conn, id)
+}
+
+/// 获取项目下的所有分组，按顺序排列
+pub fn get_sections_for_project(conn: &Connection, project_id: i64) -> SqliteResult<Vec<Section>> {
+    let sql = format!(
+        "SELECT {SECTION_COLUMNS} FROM sections WHERE project_id = ? ORDER BY position ASC, id ASC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(params![project_id], row_to_section)?;
+    iter.collect()
+}
+
+/// 根据 ID 获取单个分组
+pub fn get_section_by_id(conn: &Connection, id: i64) -> SqliteResult<Section> {
+    let sql = format!("SELECT {SECTION_COLUMNS} FROM sections WHERE id = ?");
+    conn.query_row(&sql, params![id], row_to_section)
+}
+
+/// 重命名一个分组
+pub fn rename_section(conn: &Connection, id: i64, name: &str) -> SqliteResult<Section> {
+    let sql = "UPDATE sections SET name = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
+    conn.execute(sql, params![name, id])?;
+    get_section_by_id(conn, id)
+}
+
+/// 按给定的 ID 顺序重新排列项目内的分组
+pub fn reorder_sections(
+    conn: &Connection,
+    project_id: i64,
+    section_ids: &[i64],
+) -> SqliteResult<()> {
+    let sql = "UPDATE sections SET position = ?1 WHERE id = ?2 AND project_id = ?3";
+    for (position, id) in section_ids.iter().enumerate() {
+        conn.execute(sql, params![position as i64, id, project_id])?;
+    }
+    Ok(())
+}
+
+/// 删除一个分组，其中的任务会被移出分组（任务本身不会被删除）
+pub fn delete_section(conn: &Connection, id: i64) -> SqliteResult<usize> {
+    conn.execute(
+        "UPDATE tasks SET section_id = NULL WHERE section_id = ?",
+        params![id],
+    )?;
+    conn.execute("DELETE FROM sections WHERE id = ?", params![id])
+}
+
+/// 删除项目下的所有分组，其中的任务会被移出分组
+pub fn delete_sections_for_project(conn: &Connection, project_id: i64) -> SqliteResult<usize> {
+    conn.execute(
+        "UPDATE tasks SET section_id = NULL 
+         WHERE section_id IN (SELECT id FROM sections WHERE project_id = ?)",
+        params![project_id],
+    )?;
+    conn.execute(
+        "DELETE FROM sections WHERE project_id = ?",
+        params![project_id],
+    )
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 Section
+fn row_to_section(row: &Row) -> SqliteResult<Section> {
+    let created_at_str: String = row.get("created_at")?;
+    let updated_at_str: String = row.get("updated_at")?;
+    Ok(Section {
+        id: row.get("id")?,
+        project_id: row.get("project_id")?,
+        name: row.get("name")?,
+        position: row.get("position")?,
+        created_at: NaiveDateTime::parse_from_str(&created_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+        updated_at: NaiveDateTime::parse_from_str(&updated_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+    })
+}
@@ -0,0 +1,127 @@
+use crate::domain::tag::Tag;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult, Row};
+
+/// 所有读取标签的查询共用的列
+const TAG_COLUMNS: &str = "id, name, created_at, updated_at";
+
+/// 获取指定名称（不区分大小写）的标签，不存在时创建它
+pub fn get_or_create_tag(conn: &Connection, name: &str) -> SqliteResult<Tag> {
+    if let Some(tag) = get_tag_by_name(conn, name)? {
+        return Ok(tag);
+    }
+    conn.execute("INSERT INTO tags (name) VALUES (?)", params![name])?;
+    let id = conn.last_insert_rowid();
+    get_tag_by_id(conn, id)
+}
+
+/// 获取所有标签，按名称排列
+pub fn get_all_tags(conn: &Connection) -> SqliteResult<Vec<Tag>> {
+    let sql = format!("SELECT {TAG_COLUMNS} FROM tags ORDER BY name ASC, id ASC");
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map([], row_to_tag)?;
+    iter.collect()
+}
+
+/// 根据 ID 获取单个标签
+pub fn get_tag_by_id(conn: &Connection, id: i64) -> SqliteResult<Tag> {
+    let sql = format!("SELECT {TAG_COLUMNS} FROM tags WHERE id = ?");
+    conn.query_row(&sql, params![id], row_to_tag)
+}
+
+/// 根据名称（不区分大小写）获取标签
+pub fn get_tag_by_name(conn: &Connection, name: &str) -> SqliteResult<Option<Tag>> {
+    let sql = format!("SELECT {TAG_COLUMNS} FROM tags WHERE name = ?");
+    conn.query_row(&sql, params![name], row_to_tag).optional()
+}
+
+/// 重命名一个标签
+pub fn rename_tag(conn: &Connection, id: i64, name: &str) -> SqliteResult<Tag> {
+    let sql = "UPDATE tags SET name = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
+    conn.execute(sql, params![name, id])?;
+    get_tag_by_id(conn, id)
+}
+
+/// 删除一个标签，它与任务的关联会通过外键的级联删除一并删除
+pub fn delete_tag(conn: &Connection, id: i64) -> SqliteResult<usize> {
+    conn.execute("DELETE FROM tags WHERE id = ?", params![id])
+}
+
+/// 为任务添加标签，任务已经有该标签时不做任何修改
+pub fn add_tag_to_task(conn: &Connection, task_id: i64, tag_id: i64) -> SqliteResult<usize> {
+    let sql = "INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)";
+    conn.execute(sql, params![task_id, tag_id])
+}
+
+/// 移除任务的一个标签
+pub fn remove_tag_from_task(conn: &Connection, task_id: i64, tag_id: i64) -> SqliteResult<usize> {
+    let sql = "DELETE FROM task_tags WHERE task_id = ?1 AND tag_id = ?2";
+    conn.execute(sql, params![task_id, tag_id])
+}
+
+/// 获取任务的所有标签，按名称排列
+pub fn get_tags_for_task(conn: &Connection, task_id: i64) -> SqliteResult<Vec<Tag>> {
+    let sql = "SELECT g.id, g.name, g.created_at, g.updated_at
+               FROM tags g JOIN task_tags tt ON tt.tag_id = g.id
+               WHERE tt.task_id = ? ORDER BY g.name ASC, g.id ASC";
+    let mut stmt = conn.prepare(sql)?;
+    let iter = stmt.query_map(params![task_id], row_to_tag)?;
+    iter.collect()
+}
+
+/// 获取带有指定标签的所有任务的 ID
+pub fn get_task_ids_by_tag(conn: &Connection, tag_id: i64) -> SqliteResult<Vec<i64>> {
+    let mut stmt =
+        conn.prepare("SELECT task_id FROM task_tags WHERE tag_id = ? ORDER BY task_id ASC")?;
+    let iter = stmt.query_map(params![tag_id], |row| row.get(0))?;
+    iter.collect()
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 Tag
+fn row_to_tag(row: &Row) -> SqliteResult<Tag> {
+    let created_at_str: String = row.get("created_at")?;
+    let updated_at_str: String = row.get("updated_at")?;
+    Ok(Tag {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        created_at: NaiveDateTime::parse_from_str(&created_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+        updated_at: NaiveDateTime::parse_from_str(&updated_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::task_queries;
+    use crate::db::test_support::open_test_db;
+
+    #[test]
+    fn tag_names_are_case_insensitive() {
+        let conn = open_test_db();
+        let work = get_or_create_tag(&conn, "Work").unwrap();
+        assert_eq!(get_or_create_tag(&conn, "work").unwrap(), work);
+        assert_eq!(get_all_tags(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn adding_a_tag_twice_keeps_one_link() {
+        let conn = open_test_db();
+        let task = task_queries::create_task(&conn, "t", None, None).unwrap();
+        let tag = get_or_create_tag(&conn, "urgent").unwrap();
+
+        assert_eq!(add_tag_to_task(&conn, task.id, tag.id).unwrap(), 1);
+        assert_eq!(add_tag_to_task(&conn, task.id, tag.id).unwrap(), 0);
+        assert_eq!(
+            get_tags_for_task(&conn, task.id).unwrap(),
+            vec![tag.clone()]
+        );
+        assert_eq!(get_task_ids_by_tag(&conn, tag.id).unwrap(), [task.id]);
+
+        task_queries::delete_task(&conn, task.id).unwrap();
+        assert!(get_task_ids_by_tag(&conn, tag.id).unwrap().is_empty());
+    }
+}
@@ -1,7 +1,11 @@
+use crate::db::queries::project_queries::ARCHIVED_PROJECT_IDS;
+use crate::db::queries::workflow_status_queries::{self, OPEN_STATUS_IDS};
+use crate::domain::checklist::ChecklistProgress;
 use crate::domain::priority::Priority;
 use crate::domain::task::Task;
+use crate::domain::workflow_status::WorkflowStatus;
 use chrono::{DateTime, NaiveDateTime, Utc};
-use rusqlite::{params, Connection, Result as SqliteResult, ToSql};
+use rusqlite::{params, Connection, Result as SqliteResult, Row, ToSql};
 
 /// 创建一个新任务，并返回创建好的完整任务对象。
 pub fn create_task(
@@ -10,32 +14,74 @@ pub fn create_task(
     project_id: Option<i64>,
     parent_id: Option<i64>,
 ) -> SqliteResult<Task> {
+    // 新任务使用项目状态集合中的第一个未完成状态
+    let status_id = workflow_status_queries::get_default_status_id(conn, project_id, false)?;
     // SQL INSERT 语句现在也包含 project_id 字段
-    let sql = "INSERT INTO tasks (title, project_id, parent_id) VALUES (?1, ?2, ?3)";
+    let sql = "INSERT INTO tasks (title, project_id, parent_id, status_id) VALUES (?1, ?2, ?3, ?4)";
     // 将 title 和 project_id 作为参数传递
-    conn.execute(sql, params![title, project_id, parent_id])?;
+    conn.execute(sql, params![title, project_id, parent_id, status_id])?;
 
     let id = conn.last_insert_rowid();
     get_task_by_id(conn, id)
 }
 
+/// 所有读取任务的查询共用的 SELECT 子句（不含 WHERE）。
+/// `subtask_count`、`blocked_by`、`tracked_seconds`、`pomodoro_count`、`checklist_*` 和 `next_reminder_at` 不存在于表中，而是通过子查询动态计算。
+/// `descendant_stats` 汇总每个任务的所有后代任务（子任务、子任务的子任务……），只计算一次后按任务关联。
+/// 任务的状态通过子查询读取，而不是 JOIN，以免调用方追加的 WHERE 条件中出现有歧义的列名。
+const TASK_SELECT: &str = "
+    WITH RECURSIVE task_tree(root_id, id) AS (
+        SELECT parent_id, id FROM tasks WHERE parent_id IS NOT NULL
+        -- 使用 UNION 而不是 UNION ALL，即使数据中存在环也能正常结束
+        UNION
+        SELECT tt.root_id, c.id FROM tasks c JOIN task_tree tt ON c.parent_id = tt.id
+    ),
+    descendant_stats(root_id, total, completed, estimate_sum, open_estimate_sum) AS (
+        SELECT tt.root_id, COUNT(*), SUM(s.counts_as_done), COALESCE(SUM(d.estimate), 0),
+               COALESCE(SUM(CASE WHEN s.counts_as_done = 0 THEN d.estimate END), 0)
+        FROM task_tree tt JOIN tasks d ON d.id = tt.id
+        JOIN workflow_statuses s ON s.id = d.status_id
+        GROUP BY tt.root_id
+    )
+    SELECT
+        t.id, t.title, t.description, t.project_id, t.parent_id,
+        t.section_id, t.priority, t.due_date, t.created_at, t.updated_at, t.completed_at,
+        t.status_id, t.estimate,
+        (SELECT s.project_id FROM workflow_statuses s WHERE s.id = t.status_id) AS status_project_id,
+        (SELECT s.name FROM workflow_statuses s WHERE s.id = t.status_id) AS status_name,
+        (SELECT s.position FROM workflow_statuses s WHERE s.id = t.status_id) AS status_position,
+        (SELECT s.counts_as_done FROM workflow_statuses s WHERE s.id = t.status_id) AS status_counts_as_done,
+        (SELECT COUNT(*) FROM tasks AS st WHERE st.parent_id = t.id) AS subtask_count,
+        COALESCE(ds.total, 0) AS descendant_count,
+        COALESCE(ds.completed, 0) AS completed_descendant_count,
+        COALESCE(ds.estimate_sum, 0) AS descendant_estimate,
+        COALESCE(ds.open_estimate_sum, 0) AS open_descendant_estimate,
+        -- 阻塞该任务的、尚未完成的依赖任务 ID，以逗号分隔
+        (SELECT GROUP_CONCAT(d.depends_on_task_id) FROM task_dependencies d
+         JOIN tasks b ON b.id = d.depends_on_task_id
+         JOIN workflow_statuses bs ON bs.id = b.status_id
+         WHERE d.task_id = t.id AND bs.counts_as_done = 0) AS blocked_by,
+        -- 任务记录的总时长（秒），正在运行的计时器计算到当前时间
+        (SELECT COALESCE(SUM(CAST(strftime('%s', COALESCE(e.ended_at, datetime('now'))) AS INTEGER)
+                           - CAST(strftime('%s', e.started_at) AS INTEGER)), 0)
+         FROM time_entries e WHERE e.task_id = t.id) AS tracked_seconds,
+        (SELECT COUNT(*) FROM pomodoros pd WHERE pd.task_id = t.id) AS pomodoro_count,
+        (SELECT COUNT(*) FROM checklist_items ci WHERE ci.task_id = t.id) AS checklist_total,
+        (SELECT COALESCE(SUM(ci.is_checked), 0) FROM checklist_items ci WHERE ci.task_id = t.id)
+            AS checklist_checked,
+        -- 使用子查询找到每个任务的最近一个未发送的提醒时间
+        (SELECT MIN(remind_at) FROM reminders r WHERE r.task_id = t.id AND r.is_sent = 0 AND r.is_missed = 0) AS next_reminder_at
+    FROM tasks t
+    LEFT JOIN descendant_stats ds ON ds.root_id = t.id
+";
+
 /// 获取所有任务，按创建时间降序排列。
 pub fn get_tasks_by_parent(
     conn: &Connection,
     project_id: Option<i64>,
     parent_id: Option<i64>,
 ) -> SqliteResult<Vec<Task>> {
-    let mut sql =
-        "
-        SELECT
-            t.id, t.title, t.description, t.is_completed, t.project_id, t.parent_id,
-            t.priority, t.due_date, t.created_at, t.updated_at,
-            (SELECT COUNT(*) FROM tasks AS st WHERE st.parent_id = t.id) AS subtask_count,
-            -- 使用子查询找到每个任务的最近一个未发送的提醒时间
-            (SELECT MIN(remind_at) FROM reminders r WHERE r.task_id = t.id AND r.is_sent = 0) AS next_reminder_at
-        FROM tasks t
-        "
-            .to_string();
+    let mut sql = TASK_SELECT.to_string();
 
     let mut params_vec: Vec<Box<dyn ToSql>> = Vec::new();
 
@@ -65,48 +111,226 @@ pub fn get_tasks_by_parent(
     // 使用 `rusqlite::params_from_iter` 将 Vec 转换为 `rusqlite` 可接受的参数类型
     let params_slice = rusqlite::params_from_iter(params_vec.iter());
 
-    let task_iter = stmt.query_map(params_slice, |row| {
-        let created_at_str: String = row.get("created_at")?;
-        let updated_at_str: String = row.get("updated_at")?;
-        let priority_val: i64 = row.get("priority")?;
-        let due_date: Option<DateTime<Utc>> = row.get::<_, Option<String>>("due_date")?.map(|s| {
-            NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc()
-        });
-        let next_reminder_at: Option<DateTime<Utc>> =
-            row.get::<_, Option<String>>("next_reminder_at")?.map(|s| {
-                NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
-                    .unwrap()
-                    .and_utc()
-            });
-
-        Ok(Task {
-            id: row.get("id")?,
-            title: row.get("title")?,
-            description: row.get("description")?,
-            is_completed: row.get::<_, i32>("is_completed")? == 1,
-            project_id: row.get("project_id")?,
-            parent_id: row.get("parent_id")?,
-            subtask_count: row.get("subtask_count")?,
-            priority: priority_val.into(),
-            due_date,
-            next_reminder_at,
-            created_at: NaiveDateTime::parse_from_str(&created_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-            updated_at: NaiveDateTime::parse_from_str(&updated_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-        })
-    })?;
+    let task_iter = stmt.query_map(params_slice, row_to_task)?;
 
     task_iter.collect()
 }
+
+/// 获取所有未完成、且截止日期早于 `before` 或为高优先级的任务（用于每日概览）。
+/// 已归档项目中的任务不包含在内。
+/// 结果按截止日期升序排列，没有截止日期的任务排在最后。
+pub fn get_open_tasks_due_before_or_high_priority(
+    conn: &Connection,
+    before: DateTime<Utc>,
+) -> SqliteResult<Vec<Task>> {
+    let sql = format!(
+        "{TASK_SELECT}
+         WHERE t.status_id IN ({OPEN_STATUS_IDS}) AND (t.due_date < ?1 OR t.priority = ?2)
+           AND (t.project_id IS NULL OR t.project_id NOT IN ({ARCHIVED_PROJECT_IDS}))
+         ORDER BY t.due_date IS NULL, t.due_date ASC, t.priority DESC"
+    );
+    let before_str = before.format("%Y-%m-%d %H:%M:%S").to_string();
+    let high: i64 = Priority::High.into();
+
+    let mut stmt = conn.prepare(&sql)?;
+    let task_iter = stmt.query_map(params![before_str, high], row_to_task)?;
+    task_iter.collect()
+}
+
+/// 获取可以安排进每日计划的任务：尚未完成、没有被阻塞、没有未完成的子任务，且不在已归档的项目中。
+/// 结果按优先级降序、截止日期升序排列，没有截止日期的任务排在同一优先级的最后。
+pub fn get_plannable_tasks(conn: &Connection) -> SqliteResult<Vec<Task>> {
+    let sql = format!(
+        "{TASK_SELECT}
+         WHERE t.status_id IN ({OPEN_STATUS_IDS})
+           AND NOT EXISTS (SELECT 1 FROM tasks c
+                           WHERE c.parent_id = t.id AND c.status_id IN ({OPEN_STATUS_IDS}))
+           AND NOT EXISTS (SELECT 1 FROM task_dependencies d JOIN tasks b ON b.id = d.depends_on_task_id
+                           WHERE d.task_id = t.id AND b.status_id IN ({OPEN_STATUS_IDS}))
+           AND (t.project_id IS NULL OR t.project_id NOT IN ({ARCHIVED_PROJECT_IDS}))
+         ORDER BY t.priority DESC, t.due_date IS NULL, t.due_date ASC, t.created_at ASC, t.id ASC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let task_iter = stmt.query_map([], row_to_task)?;
+    task_iter.collect()
+}
+
+/// 将任务移动到指定的分组，`section_id` 为空时移出分组。
+pub fn update_task_section(
+    conn: &Connection,
+    id: i64,
+    section_id: Option<i64>,
+) -> SqliteResult<usize> {
+    let sql = "UPDATE tasks SET section_id = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
+    conn.execute(sql, params![section_id, id])
+}
+
+/// 更新任务的父任务，`parent_id` 为空时变为顶层任务
+pub fn update_task_parent(
+    conn: &Connection,
+    id: i64,
+    parent_id: Option<i64>,
+) -> SqliteResult<usize> {
+    let sql = "UPDATE tasks SET parent_id = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
+    conn.execute(sql, params![parent_id, id])
+}
+
+/// 获取属于指定项目的所有任务的 ID
+pub fn get_task_ids_by_project(conn: &Connection, project_id: i64) -> SqliteResult<Vec<i64>> {
+    let mut stmt = conn.prepare("SELECT id FROM tasks WHERE project_id = ?")?;
+    let iter = stmt.query_map(params![project_id], |row| row.get("id"))?;
+    iter.collect()
+}
+
+/// 获取指定任务及其所有后代任务（子任务、子任务的子任务……）的 ID
+pub fn get_subtree_task_ids(conn: &Connection, root_ids: &[i64]) -> SqliteResult<Vec<i64>> {
+    if root_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    // 使用 UNION 而不是 UNION ALL，即使数据中存在环也能正常结束
+    let sql = format!(
+        "WITH RECURSIVE subtree(id) AS (
+             SELECT id FROM tasks WHERE id IN ({})
+             UNION
+             SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+         )
+         SELECT id FROM subtree",
+        placeholders(root_ids.len())
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(rusqlite::params_from_iter(root_ids), |row| row.get("id"))?;
+    iter.collect()
+}
+
+/// 获取指定任务的所有未完成的后代任务的 ID
+pub fn get_open_descendant_ids(conn: &Connection, id: i64) -> SqliteResult<Vec<i64>> {
+    let sql = format!(
+        "
+        WITH RECURSIVE descendants(id, status_id) AS (
+            SELECT id, status_id FROM tasks WHERE parent_id = ?1
+            UNION
+            SELECT c.id, c.status_id FROM tasks c JOIN descendants d ON c.parent_id = d.id
+        )
+        SELECT id FROM descendants WHERE status_id IN ({OPEN_STATUS_IDS})"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(params![id], |row| row.get("id"))?;
+    iter.collect()
+}
+
+/// 获取指定任务的所有祖先任务的 ID，从直接父任务开始由近及远排列
+pub fn get_ancestor_ids(conn: &Connection, id: i64) -> SqliteResult<Vec<i64>> {
+    let sql = "
+        WITH RECURSIVE ancestors(id, parent_id, depth) AS (
+            SELECT p.id, p.parent_id, 1 FROM tasks c JOIN tasks p ON p.id = c.parent_id WHERE c.id = ?1
+            UNION ALL
+            SELECT t.id, t.parent_id, a.depth + 1 FROM tasks t JOIN ancestors a ON t.id = a.parent_id
+            -- 即使数据中存在环也能正常结束：回到起点或深度超过任务总数时停止
+            WHERE t.id <> ?1 AND a.depth < (SELECT COUNT(*) FROM tasks)
+        )
+        SELECT id FROM ancestors GROUP BY id ORDER BY MIN(depth) ASC";
+    let mut stmt = conn.prepare(sql)?;
+    let iter = stmt.query_map(params![id], |row| row.get("id"))?;
+    iter.collect()
+}
+
+/// 统计指定任务的直接子任务中尚未完成的数量
+pub fn count_open_subtasks(conn: &Connection, id: i64) -> SqliteResult<i64> {
+    let sql = format!(
+        "SELECT COUNT(*) FROM tasks WHERE parent_id = ? AND status_id IN ({OPEN_STATUS_IDS})"
+    );
+    conn.query_row(&sql, params![id], |row| row.get(0))
+}
+
+/// 根据 ID 列表批量获取任务
+pub fn get_tasks_by_ids(conn: &Connection, ids: &[i64]) -> SqliteResult<Vec<Task>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let sql = format!("{TASK_SELECT} WHERE t.id IN ({})", placeholders(ids.len()));
+    let mut stmt = conn.prepare(&sql)?;
+    let task_iter = stmt.query_map(rusqlite::params_from_iter(ids), row_to_task)?;
+    task_iter.collect()
+}
+
+/// 将指定的任务移动到另一个项目（为空时移动到收件箱），任务会被移出原来的分组，
+/// 并切换到目标项目状态集合中对应的状态
+pub fn move_tasks_to_project(
+    conn: &Connection,
+    ids: &[i64],
+    project_id: Option<i64>,
+) -> SqliteResult<usize> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let sql = format!(
+        "UPDATE tasks SET project_id = ?, section_id = NULL, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') 
+         WHERE id IN ({})",
+        placeholders(ids.len())
+    );
+    let mut params_vec: Vec<&dyn ToSql> = vec![&project_id];
+    params_vec.extend(ids.iter().map(|id| id as &dyn ToSql));
+    let moved = conn.execute(&sql, &params_vec[..])?;
+    // 目标项目可能使用另一组工作流状态
+    workflow_status_queries::remap_task_statuses(conn, ids)?;
+    Ok(moved)
+}
+
+/// 删除指定的任务。
+/// 它们的子任务、提醒和附件会通过外键的级联删除一并删除。
+pub fn delete_tasks_by_ids(conn: &Connection, ids: &[i64]) -> SqliteResult<usize> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let sql = format!(
+        "DELETE FROM tasks WHERE id IN ({})",
+        placeholders(ids.len())
+    );
+    conn.execute(&sql, rusqlite::params_from_iter(ids))
+}
+
+/// 内部辅助函数：生成 `IN (...)` 中使用的 `?, ?, ?` 占位符
+pub(crate) fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
 /// 更新指定 ID 任务的完成状态。
+/// 完成任务时记录完成时间，重新打开任务时清除完成时间。
+/// 完成标记改变时，任务会切换到项目状态集合中第一个对应的状态（例如“已完成”或“待办”）。
 pub fn update_task_status(conn: &Connection, id: i64, is_completed: bool) -> SqliteResult<usize> {
-    let sql = "UPDATE tasks SET is_completed = ?, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?";
-    conn.execute(sql, params![is_completed, id])
+    let project_id: Option<i64> = conn.query_row(
+        "SELECT project_id FROM tasks WHERE id = ?",
+        params![id],
+        |row| row.get(0),
+    )?;
+    let status_id = workflow_status_queries::get_default_status_id(conn, project_id, is_completed)?;
+    let sql = "UPDATE tasks 
+               SET completed_at = CASE 
+                       WHEN ?1 = 0 THEN NULL 
+                       WHEN completed_at IS NOT NULL THEN completed_at 
+                       ELSE strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') 
+                   END, 
+                   status_id = CASE
+                       WHEN (SELECT counts_as_done FROM workflow_statuses WHERE id = tasks.status_id) = ?1
+                       THEN status_id ELSE ?3
+                   END,
+                   updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') 
+               WHERE id = ?2";
+    conn.execute(sql, params![is_completed, id, status_id])
+}
+
+/// 将任务切换到指定的工作流状态，并同步更新完成标记和完成时间
+pub fn set_task_status(conn: &Connection, id: i64, status: &WorkflowStatus) -> SqliteResult<usize> {
+    let sql = "UPDATE tasks 
+               SET completed_at = CASE 
+                       WHEN ?1 = 0 THEN NULL 
+                       WHEN completed_at IS NOT NULL THEN completed_at 
+                       ELSE strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') 
+                   END, 
+                   status_id = ?2,
+                   updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') 
+               WHERE id = ?3";
+    conn.execute(sql, params![status.counts_as_done, status.id, id])
 }
 
 /// 根据 ID 删除一个任务。
@@ -115,54 +339,10 @@ pub fn delete_task(conn: &Connection, id: i64) -> SqliteResult<usize> {
     conn.execute(sql, params![id])
 }
 
-/// 这是一个内部辅助函数，不对外暴露，仅用于根据 ID 获取单个任务。
+/// 根据 ID 获取单个任务。
 pub fn get_task_by_id(conn: &Connection, id: i64) -> SqliteResult<Task> {
-    let sql =
-        "
-        SELECT
-            t.id, t.title, t.description, t.is_completed, t.project_id, t.parent_id,
-            t.priority, t.due_date, t.created_at, t.updated_at,
-            (SELECT COUNT(*) FROM tasks AS st WHERE st.parent_id = t.id) AS subtask_count,
-            (SELECT MIN(remind_at) FROM reminders r WHERE r.task_id = t.id AND r.is_sent = 0) AS next_reminder_at
-        FROM tasks t
-        WHERE t.id = ?
-        ";
-
-    conn.query_row(sql, params![id], |row| {
-        let created_at_str: String = row.get("created_at")?;
-        let updated_at_str: String = row.get("updated_at")?;
-        let priority_val: i64 = row.get("priority")?;
-        let due_date: Option<DateTime<Utc>> = row.get::<_, Option<String>>("due_date")?.map(|s| {
-            NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc()
-        });
-        let next_reminder_at: Option<DateTime<Utc>> =
-            row.get::<_, Option<String>>("next_reminder_at")?.map(|s| {
-                NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
-                    .unwrap()
-                    .and_utc()
-            });
-
-        Ok(Task {
-            id: row.get("id")?,
-            title: row.get("title")?,
-            description: row.get("description")?,
-            is_completed: row.get::<_, i32>("is_completed")? == 1,
-            project_id: row.get("project_id")?,
-            parent_id: row.get("parent_id")?,
-            subtask_count: row.get("subtask_count")?,
-            priority: priority_val.into(),
-            due_date,
-            next_reminder_at,
-            created_at: NaiveDateTime::parse_from_str(&created_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-            updated_at: NaiveDateTime::parse_from_str(&updated_at_str, "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .and_utc(),
-        })
-    })
+    let sql = format!("{TASK_SELECT} WHERE t.id = ?");
+    conn.query_row(&sql, params![id], row_to_task)
 }
 
 /// 用于更新任务优先级的函数
@@ -191,6 +371,16 @@ pub fn update_task_due_date(
     conn.execute(sql, params![due_date_str, id])
 }
 
+/// 更新任务的工作量估算，`estimate` 为空时清除估算
+pub fn update_task_estimate(
+    conn: &Connection,
+    id: i64,
+    estimate: Option<i64>,
+) -> SqliteResult<usize> {
+    let sql = "UPDATE tasks SET estimate = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
+    conn.execute(sql, params![estimate, id])
+}
+
 /// 更新任务描述的函数
 pub fn update_task_description(
     conn: &Connection,
@@ -200,3 +390,147 @@ pub fn update_task_description(
     let sql = "UPDATE tasks SET description = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
     conn.execute(sql, params![description, id])
 }
+
+/// 内部辅助函数：将查询结果的一行映射为 Task
+fn row_to_task(row: &Row) -> SqliteResult<Task> {
+    let created_at_str: String = row.get("created_at")?;
+    let updated_at_str: String = row.get("updated_at")?;
+    let priority_val: i64 = row.get("priority")?;
+    let due_date: Option<DateTime<Utc>> = row.get::<_, Option<String>>("due_date")?.map(|s| {
+        NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc()
+    });
+    let next_reminder_at: Option<DateTime<Utc>> =
+        row.get::<_, Option<String>>("next_reminder_at")?.map(|s| {
+            NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+                .unwrap()
+                .and_utc()
+        });
+
+    let completed_at: Option<DateTime<Utc>> =
+        row.get::<_, Option<String>>("completed_at")?.map(|s| {
+            NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+                .unwrap()
+                .and_utc()
+        });
+
+    let descendant_estimate: i64 = row.get("descendant_estimate")?;
+    let open_descendant_estimate: i64 = row.get("open_descendant_estimate")?;
+
+    let is_completed: bool = row.get("status_counts_as_done")?;
+    let estimate: Option<i64> = row.get("estimate")?;
+    let own_estimate = estimate.unwrap_or_default();
+
+    let blocked_by: Vec<i64> = row
+        .get::<_, Option<String>>("blocked_by")?
+        .map(|ids| ids.split(',').filter_map(|id| id.parse().ok()).collect())
+        .unwrap_or_default();
+
+    Ok(Task {
+        id: row.get("id")?,
+        title: row.get("title")?,
+        description: row.get("description")?,
+        is_completed,
+        status: WorkflowStatus {
+            id: row.get("status_id")?,
+            project_id: row.get("status_project_id")?,
+            name: workflow_status_queries::display_name(row.get("status_name")?),
+            position: row.get("status_position")?,
+            counts_as_done: row.get("status_counts_as_done")?,
+        },
+        project_id: row.get("project_id")?,
+        parent_id: row.get("parent_id")?,
+        section_id: row.get("section_id")?,
+        subtask_count: row.get("subtask_count")?,
+        descendant_count: row.get("descendant_count")?,
+        completed_descendant_count: row.get("completed_descendant_count")?,
+        is_blocked: !blocked_by.is_empty(),
+        blocked_by,
+        tracked_seconds: row.get("tracked_seconds")?,
+        pomodoro_count: row.get("pomodoro_count")?,
+        checklist_progress: ChecklistProgress {
+            total: row.get("checklist_total")?,
+            checked: row.get("checklist_checked")?,
+        },
+        estimate,
+        rolled_up_estimate: own_estimate + descendant_estimate,
+        remaining_estimate: if is_completed { 0 } else { own_estimate } + open_descendant_estimate,
+        priority: priority_val.into(),
+        due_date,
+        next_reminder_at,
+        created_at: NaiveDateTime::parse_from_str(&created_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+        updated_at: NaiveDateTime::parse_from_str(&updated_at_str, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc(),
+        completed_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::checklist_queries;
+    use crate::db::test_support::open_test_db;
+
+    #[test]
+    fn rolls_up_descendant_counts_and_estimates() {
+        let conn = open_test_db();
+        let root = create_task(&conn, "root", None, None).unwrap();
+        let child = create_task(&conn, "child", None, Some(root.id)).unwrap();
+        let done = create_task(&conn, "done", None, Some(root.id)).unwrap();
+        let grandchild = create_task(&conn, "grandchild", None, Some(child.id)).unwrap();
+        update_task_estimate(&conn, root.id, Some(10)).unwrap();
+        update_task_estimate(&conn, child.id, Some(20)).unwrap();
+        update_task_estimate(&conn, done.id, Some(30)).unwrap();
+        update_task_estimate(&conn, grandchild.id, Some(40)).unwrap();
+        update_task_status(&conn, done.id, true).unwrap();
+
+        let root = get_task_by_id(&conn, root.id).unwrap();
+        assert_eq!(root.subtask_count, 2);
+        assert_eq!(root.descendant_count, 3);
+        assert_eq!(root.completed_descendant_count, 1);
+        assert_eq!(root.rolled_up_estimate, 100);
+        assert_eq!(root.remaining_estimate, 70);
+
+        let leaf = get_task_by_id(&conn, grandchild.id).unwrap();
+        assert_eq!(leaf.descendant_count, 0);
+        assert_eq!(leaf.rolled_up_estimate, 40);
+        let done = get_task_by_id(&conn, done.id).unwrap();
+        assert_eq!(done.remaining_estimate, 0);
+    }
+
+    #[test]
+    fn reports_checklist_progress() {
+        let conn = open_test_db();
+        let task = create_task(&conn, "t", None, None).unwrap();
+        assert_eq!(task.checklist_progress.total, 0);
+        let item = checklist_queries::create_checklist_item(&conn, task.id, "a").unwrap();
+        checklist_queries::create_checklist_item(&conn, task.id, "b").unwrap();
+        checklist_queries::set_checklist_item_checked(&conn, item.id, true).unwrap();
+
+        let progress = get_task_by_id(&conn, task.id).unwrap().checklist_progress;
+        assert_eq!((progress.total, progress.checked), (2, 1));
+    }
+
+    #[test]
+    fn tree_queries_terminate_on_parent_cycles() {
+        let conn = open_test_db();
+        let a = create_task(&conn, "a", None, None).unwrap();
+        let b = create_task(&conn, "b", None, Some(a.id)).unwrap();
+        let c = create_task(&conn, "c", None, Some(b.id)).unwrap();
+        assert_eq!(get_ancestor_ids(&conn, c.id).unwrap(), vec![b.id, a.id]);
+
+        // 正常操作不会产生环，这里直接修改数据来模拟损坏的数据
+        conn.execute(
+            "UPDATE tasks SET parent_id = ?1 WHERE id = ?2",
+            params![c.id, a.id],
+        )
+        .unwrap();
+        assert_eq!(get_ancestor_ids(&conn, c.id).unwrap(), vec![b.id, a.id]);
+        assert_eq!(get_task_by_id(&conn, a.id).unwrap().descendant_count, 3);
+        assert_eq!(get_subtree_task_ids(&conn, &[a.id]).unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,276 @@
+use crate::db::queries::util::parse_datetime;
+use crate::domain::attachment::AttachmentType;
+use crate::domain::project::Project;
+use crate::domain::task::Task;
+use crate::domain::template::{
+    template_variables, Template, TemplateAttachment, TemplateKind, TemplateTask,
+};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, Result as SqliteResult, Row};
+use std::collections::BTreeSet;
+
+/// 所有读取模板的查询共用的 SELECT 语句，`task_count` 通过子查询动态计算
+const TEMPLATE_SELECT: &str = "
+    SELECT
+        tp.id, tp.name, tp.kind, tp.project_name, tp.project_color, tp.project_icon,
+        tp.project_description, tp.created_at, tp.updated_at,
+        (SELECT COUNT(*) FROM template_tasks tt WHERE tt.template_id = tp.id) AS task_count
+    FROM templates tp
+";
+
+/// 所有读取模板任务的查询共用的列
+const TEMPLATE_TASK_COLUMNS: &str =
+    "id, template_id, parent_id, position, title, description, priority, estimate, due_offset_minutes";
+
+/// 所有读取模板附件的查询共用的列
+const TEMPLATE_ATTACHMENT_COLUMNS: &str =
+    "id, template_task_id, type, payload, title, file_size, mime_type, original_name";
+
+/// 创建一个空模板，返回模板的 ID。`project` 只用于项目模板，保存项目的名称、颜色、图标和描述。
+pub fn create_template(
+    conn: &Connection,
+    name: &str,
+    kind: TemplateKind,
+    project: Option<&Project>,
+) -> SqliteResult<i64> {
+    let kind_str: &str = kind.into();
+    let sql = "INSERT INTO templates (name, kind, project_name, project_color, project_icon, project_description)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
+    conn.execute(
+        sql,
+        params![
+            name,
+            kind_str,
+            project.map(|p| &p.name),
+            project.and_then(|p| p.color.as_ref()),
+            project.and_then(|p| p.icon.as_ref()),
+            project.and_then(|p| p.description.as_ref()),
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// 将一个任务的标题、描述、优先级、估算、相对提醒和附件复制为模板任务，返回模板任务的 ID。
+/// `due_offset_minutes` 为截止日期相对于模板锚点的偏移量。
+pub fn add_template_task(
+    conn: &Connection,
+    template_id: i64,
+    parent_id: Option<i64>,
+    position: i64,
+    task: &Task,
+    due_offset_minutes: Option<i64>,
+) -> SqliteResult<i64> {
+    let priority: i64 = task.priority.into();
+    let sql = "INSERT INTO template_tasks (template_id, parent_id, position, title, description, priority, estimate, due_offset_minutes)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)";
+    conn.execute(
+        sql,
+        params![
+            template_id,
+            parent_id,
+            position,
+            task.title,
+            task.description,
+            priority,
+            task.estimate,
+            due_offset_minutes
+        ],
+    )?;
+    let template_task_id = conn.last_insert_rowid();
+
+    // 只保留相对提醒，绝对时间的提醒对新任务没有意义
+    conn.execute(
+        "INSERT OR IGNORE INTO template_task_reminders (template_task_id, offset_minutes)
+         SELECT ?1, offset_minutes FROM reminders WHERE task_id = ?2 AND offset_minutes IS NOT NULL",
+        params![template_task_id, task.id],
+    )?;
+    conn.execute(
+        "INSERT INTO template_attachments (template_task_id, type, payload, title, file_size, mime_type, original_name)
+         SELECT ?1, type, payload, title, file_size, mime_type, original_name FROM attachments
+         WHERE task_id = ?2 ORDER BY id ASC",
+        params![template_task_id, task.id],
+    )?;
+    Ok(template_task_id)
+}
+
+/// 获取所有模板，按名称排列
+pub fn get_all_templates(conn: &Connection) -> SqliteResult<Vec<Template>> {
+    let sql = format!("{TEMPLATE_SELECT} ORDER BY tp.name ASC, tp.id ASC");
+    let mut stmt = conn.prepare(&sql)?;
+    let templates = stmt
+        .query_map([], row_to_template)?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    templates
+        .into_iter()
+        .map(|template| with_variables(conn, template))
+        .collect()
+}
+
+/// 根据 ID 获取单个模板
+pub fn get_template_by_id(conn: &Connection, id: i64) -> SqliteResult<Template> {
+    let sql = format!("{TEMPLATE_SELECT} WHERE tp.id = ?");
+    let template = conn.query_row(&sql, params![id], row_to_template)?;
+    with_variables(conn, template)
+}
+
+/// 重命名模板
+pub fn rename_template(conn: &Connection, id: i64, name: &str) -> SqliteResult<Template> {
+    let sql = "UPDATE templates SET name = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2";
+    conn.execute(sql, params![name, id])?;
+    get_template_by_id(conn, id)
+}
+
+/// 删除模板，模板中的任务、提醒和附件会通过外键的级联删除一并删除
+pub fn delete_template(conn: &Connection, id: i64) -> SqliteResult<usize> {
+    conn.execute("DELETE FROM templates WHERE id = ?", params![id])
+}
+
+/// 获取模板中的所有任务（扁平列表，通过 `parent_id` 组成层级）。
+/// 按保存的顺序排列，父任务总是排在它的子任务之前。
+pub fn get_template_tasks(conn: &Connection, template_id: i64) -> SqliteResult<Vec<TemplateTask>> {
+    let sql = format!(
+        "SELECT {TEMPLATE_TASK_COLUMNS} FROM template_tasks WHERE template_id = ? ORDER BY id ASC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let tasks = stmt
+        .query_map(params![template_id], row_to_template_task)?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    let mut reminder_stmt = conn.prepare(
+        "SELECT offset_minutes FROM template_task_reminders
+         WHERE template_task_id = ? ORDER BY offset_minutes DESC",
+    )?;
+    let attachment_sql = format!(
+        "SELECT {TEMPLATE_ATTACHMENT_COLUMNS} FROM template_attachments WHERE template_task_id = ? ORDER BY id ASC"
+    );
+    let mut attachment_stmt = conn.prepare(&attachment_sql)?;
+    tasks
+        .into_iter()
+        .map(|mut task| {
+            task.reminder_offsets = reminder_stmt
+                .query_map(params![task.id], |row| row.get("offset_minutes"))?
+                .collect::<SqliteResult<_>>()?;
+            task.attachments = attachment_stmt
+                .query_map(params![task.id], row_to_template_attachment)?
+                .collect::<SqliteResult<_>>()?;
+            Ok(task)
+        })
+        .collect()
+}
+
+/// 内部辅助函数：收集模板中所有可以使用变量的文本，计算出模板用到的变量
+fn with_variables(conn: &Connection, mut template: Template) -> SqliteResult<Template> {
+    let note_str: &str = AttachmentType::Note.into();
+    let url_str: &str = AttachmentType::Url.into();
+    let sql = "SELECT tt.title AS text FROM template_tasks tt WHERE tt.template_id = ?1
+               UNION ALL SELECT tt.description FROM template_tasks tt WHERE tt.template_id = ?1
+               UNION ALL SELECT a.title FROM template_attachments a
+                   JOIN template_tasks tt ON tt.id = a.template_task_id WHERE tt.template_id = ?1
+               UNION ALL SELECT a.payload FROM template_attachments a
+                   JOIN template_tasks tt ON tt.id = a.template_task_id
+                   WHERE tt.template_id = ?1 AND a.type IN (?2, ?3)";
+    let mut stmt = conn.prepare(sql)?;
+    let mut texts = stmt
+        .query_map(params![template.id, note_str, url_str], |row| {
+            row.get::<_, Option<String>>("text")
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    texts.push(template.project_name.clone());
+    texts.push(template.project_description.clone());
+
+    let variables: BTreeSet<String> = texts
+        .iter()
+        .flatten()
+        .flat_map(|text| template_variables(text))
+        .map(str::to_string)
+        .collect();
+    template.variables = variables.into_iter().collect();
+    Ok(template)
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 Template，`variables` 由调用方填充
+fn row_to_template(row: &Row) -> SqliteResult<Template> {
+    let kind_str: String = row.get("kind")?;
+    let kind = TemplateKind::try_from(kind_str.as_str()).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(
+            row.as_ref().column_index("kind").unwrap_or(2),
+            Type::Text,
+            Box::new(e),
+        )
+    })?;
+    Ok(Template {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        kind,
+        project_name: row.get("project_name")?,
+        project_color: row.get("project_color")?,
+        project_icon: row.get("project_icon")?,
+        project_description: row.get("project_description")?,
+        task_count: row.get("task_count")?,
+        variables: Vec::new(),
+        created_at: parse_datetime(&row.get::<_, String>("created_at")?),
+        updated_at: parse_datetime(&row.get::<_, String>("updated_at")?),
+    })
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 TemplateTask，提醒和附件由调用方填充
+fn row_to_template_task(row: &Row) -> SqliteResult<TemplateTask> {
+    Ok(TemplateTask {
+        id: row.get("id")?,
+        template_id: row.get("template_id")?,
+        parent_id: row.get("parent_id")?,
+        position: row.get("position")?,
+        title: row.get("title")?,
+        description: row.get("description")?,
+        priority: row.get::<_, i64>("priority")?.into(),
+        estimate: row.get("estimate")?,
+        due_offset_minutes: row.get("due_offset_minutes")?,
+        reminder_offsets: Vec::new(),
+        attachments: Vec::new(),
+    })
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 TemplateAttachment
+fn row_to_template_attachment(row: &Row) -> SqliteResult<TemplateAttachment> {
+    let type_str: String = row.get("type")?;
+    let attachment_type = AttachmentType::try_from(type_str.as_str()).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(
+            row.as_ref().column_index("type").unwrap_or(2),
+            Type::Text,
+            Box::new(e),
+        )
+    })?;
+    Ok(TemplateAttachment {
+        id: row.get("id")?,
+        template_task_id: row.get("template_task_id")?,
+        attachment_type,
+        payload: row.get("payload")?,
+        title: row.get("title")?,
+        file_size: row.get("file_size")?,
+        mime_type: row.get("mime_type")?,
+        original_name: row.get("original_name")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::task_queries;
+    use crate::db::test_support::open_test_db;
+
+    #[test]
+    fn variables_are_deduplicated_and_sorted_by_name() {
+        let conn = open_test_db();
+        let template_id = create_template(&conn, "T", TemplateKind::Task, None).unwrap();
+        for (position, title) in ["Call {{ client }}", "Invoice {{client}} for {{amount}}"]
+            .into_iter()
+            .enumerate()
+        {
+            let task = task_queries::create_task(&conn, title, None, None).unwrap();
+            add_template_task(&conn, template_id, None, position as i64, &task, None).unwrap();
+        }
+
+        let template = get_template_by_id(&conn, template_id).unwrap();
+        assert_eq!(template.variables, ["amount", "client"]);
+    }
+}
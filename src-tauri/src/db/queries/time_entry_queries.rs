@@ -0,0 +1,255 @@
+use crate::db::queries::task_queries::placeholders;
+use crate::db::queries::util::{format_utc, parse_datetime};
+use crate::domain::time_entry::{TaskTrackedTime, TimeEntry};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult, Row, ToSql};
+
+/// 所有读取时间记录的查询共用的列
+const TIME_ENTRY_COLUMNS: &str = "id, task_id, started_at, ended_at, note, created_at, updated_at";
+
+/// 为任务启动一个计时器。
+/// 数据库的唯一索引保证同一时间只有一个正在运行的计时器，调用方应先检查 `get_running_entry`。
+pub fn start_timer(
+    conn: &Connection,
+    task_id: i64,
+    note: Option<&str>,
+    now: DateTime<Utc>,
+) -> SqliteResult<TimeEntry> {
+    let sql = "INSERT INTO time_entries (task_id, started_at, note) VALUES (?1, ?2, ?3)";
+    conn.execute(sql, params![task_id, format_utc(now), note])?;
+    let id = conn.last_insert_rowid();
+    get_time_entry_by_id(conn, id)
+}
+
+/// 获取正在运行的计时器（如果有的话）
+pub fn get_running_entry(conn: &Connection) -> SqliteResult<Option<TimeEntry>> {
+    let sql = format!("SELECT {TIME_ENTRY_COLUMNS} FROM time_entries WHERE ended_at IS NULL");
+    conn.query_row(&sql, [], row_to_time_entry).optional()
+}
+
+/// 停止正在运行的计时器，返回停止后的记录；没有运行中的计时器时返回 `None`
+pub fn stop_running_entry(
+    conn: &Connection,
+    now: DateTime<Utc>,
+) -> SqliteResult<Option<TimeEntry>> {
+    let Some(running) = get_running_entry(conn)? else {
+        return Ok(None);
+    };
+    // 结束时间不能早于开始时间（例如开始时间被手动改到了未来）
+    let ended_at = now.max(running.started_at);
+    conn.execute(
+        "UPDATE time_entries SET ended_at = ?1, updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime') WHERE id = ?2",
+        params![format_utc(ended_at), running.id],
+    )?;
+    get_time_entry_by_id(conn, running.id).map(Some)
+}
+
+/// 手动录入一条已经结束的时间记录
+pub fn create_time_entry(
+    conn: &Connection,
+    task_id: i64,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    note: Option<&str>,
+) -> SqliteResult<TimeEntry> {
+    let sql =
+        "INSERT INTO time_entries (task_id, started_at, ended_at, note) VALUES (?1, ?2, ?3, ?4)";
+    conn.execute(
+        sql,
+        params![task_id, format_utc(started_at), format_utc(ended_at), note],
+    )?;
+    let id = conn.last_insert_rowid();
+    get_time_entry_by_id(conn, id)
+}
+
+/// 修改一条时间记录的开始时间、结束时间和备注。
+/// `ended_at` 为空时记录保持（或变为）运行状态。
+pub fn update_time_entry(
+    conn: &Connection,
+    id: i64,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+    note: Option<&str>,
+) -> SqliteResult<TimeEntry> {
+    let sql = "UPDATE time_entries
+               SET started_at = ?1, ended_at = ?2, note = ?3,
+                   updated_at = strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')
+               WHERE id = ?4";
+    conn.execute(
+        sql,
+        params![format_utc(started_at), ended_at.map(format_utc), note, id],
+    )?;
+    get_time_entry_by_id(conn, id)
+}
+
+/// 查找与 `[started_at, ended_at)` 重叠的第一条时间记录，`exclude_id` 指定的记录除外。
+/// 正在运行的计时器视为持续到 `now`；`ended_at` 为空时，新的时间段同样持续到 `now`。
+pub fn find_overlapping_entry(
+    conn: &Connection,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+    exclude_id: Option<i64>,
+    now: DateTime<Utc>,
+) -> SqliteResult<Option<TimeEntry>> {
+    let sql = format!(
+        "SELECT {TIME_ENTRY_COLUMNS} FROM time_entries
+         WHERE started_at < ?2 AND COALESCE(ended_at, ?3) > ?1 AND id IS NOT ?4
+         ORDER BY started_at ASC LIMIT 1"
+    );
+    let ended_at = ended_at.unwrap_or(now.max(started_at));
+    conn.query_row(
+        &sql,
+        params![
+            format_utc(started_at),
+            format_utc(ended_at),
+            format_utc(now),
+            exclude_id
+        ],
+        row_to_time_entry,
+    )
+    .optional()
+}
+
+/// 删除一条时间记录
+pub fn delete_time_entry(conn: &Connection, id: i64) -> SqliteResult<usize> {
+    conn.execute("DELETE FROM time_entries WHERE id = ?", params![id])
+}
+
+/// 根据 ID 获取单条时间记录
+pub fn get_time_entry_by_id(conn: &Connection, id: i64) -> SqliteResult<TimeEntry> {
+    let sql = format!("SELECT {TIME_ENTRY_COLUMNS} FROM time_entries WHERE id = ?");
+    conn.query_row(&sql, params![id], row_to_time_entry)
+}
+
+/// 获取任务的所有时间记录，最近的记录在前
+pub fn get_time_entries_for_task(conn: &Connection, task_id: i64) -> SqliteResult<Vec<TimeEntry>> {
+    let sql = format!(
+        "SELECT {TIME_ENTRY_COLUMNS} FROM time_entries WHERE task_id = ? ORDER BY started_at DESC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(params![task_id], row_to_time_entry)?;
+    iter.collect()
+}
+
+/// 按任务汇总 `task_ids` 在 `[from, to)` 范围内记录的时长（秒）。
+/// 跨越范围边界的记录只计算范围内的部分，正在运行的计时器计算到 `now`。
+/// 只返回有记录的任务，按时长降序排列。
+pub fn get_tracked_time_by_task(
+    conn: &Connection,
+    task_ids: &[i64],
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> SqliteResult<Vec<TaskTrackedTime>> {
+    if task_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    // ?1 为当前时间，?2 和 ?3 为统计范围，之后的 ? 依次为任务 ID
+    let sql = format!(
+        "SELECT t.id AS task_id, t.title,
+                SUM(MAX(0,
+                    CAST(strftime('%s', MIN(COALESCE(e.ended_at, ?1), COALESCE(?3, '9999-12-31 23:59:59'))) AS INTEGER)
+                  - CAST(strftime('%s', MAX(e.started_at, COALESCE(?2, '0000-01-01 00:00:00'))) AS INTEGER)
+                )) AS total_seconds
+         FROM time_entries e JOIN tasks t ON t.id = e.task_id
+         WHERE e.task_id IN ({})
+         GROUP BY t.id
+         HAVING total_seconds > 0
+         ORDER BY total_seconds DESC, t.id ASC",
+        placeholders(task_ids.len())
+    );
+    let now_str = format_utc(now);
+    let from_str = from.map(format_utc);
+    let to_str = to.map(format_utc);
+    let mut params_vec: Vec<&dyn ToSql> = vec![&now_str, &from_str, &to_str];
+    params_vec.extend(task_ids.iter().map(|id| id as &dyn ToSql));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(&params_vec[..], |row| {
+        Ok(TaskTrackedTime {
+            task_id: row.get("task_id")?,
+            title: row.get("title")?,
+            total_seconds: row.get("total_seconds")?,
+        })
+    })?;
+    iter.collect()
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 TimeEntry
+fn row_to_time_entry(row: &Row) -> SqliteResult<TimeEntry> {
+    let started_at = parse_datetime(&row.get::<_, String>("started_at")?);
+    let ended_at = row
+        .get::<_, Option<String>>("ended_at")?
+        .map(|s| parse_datetime(&s));
+    let duration_seconds = (ended_at.unwrap_or_else(Utc::now) - started_at)
+        .num_seconds()
+        .max(0);
+    Ok(TimeEntry {
+        id: row.get("id")?,
+        task_id: row.get("task_id")?,
+        started_at,
+        ended_at,
+        note: row.get("note")?,
+        duration_seconds,
+        created_at: parse_datetime(&row.get::<_, String>("created_at")?),
+        updated_at: parse_datetime(&row.get::<_, String>("updated_at")?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::task_queries;
+    use crate::db::test_support::open_test_db;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2030, 1, 2, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn overlapping_entries_are_found() {
+        let conn = open_test_db();
+        let a = task_queries::create_task(&conn, "a", None, None).unwrap();
+        let entry = create_time_entry(&conn, a.id, at(9, 0), at(10, 0), None).unwrap();
+        let now = at(12, 0);
+
+        let found = find_overlapping_entry(&conn, at(9, 30), Some(at(11, 0)), None, now).unwrap();
+        assert_eq!(found.map(|e| e.id), Some(entry.id));
+        let found = find_overlapping_entry(&conn, at(8, 0), Some(at(11, 0)), None, now).unwrap();
+        assert_eq!(found.map(|e| e.id), Some(entry.id));
+        // 首尾相接不算重叠
+        assert!(
+            find_overlapping_entry(&conn, at(10, 0), Some(at(11, 0)), None, now)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            find_overlapping_entry(&conn, at(8, 0), Some(at(9, 0)), None, now)
+                .unwrap()
+                .is_none()
+        );
+        // 修改记录本身时不与自己比较
+        assert!(
+            find_overlapping_entry(&conn, at(9, 15), Some(at(9, 45)), Some(entry.id), now)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn a_running_timer_extends_to_now() {
+        let conn = open_test_db();
+        let task = task_queries::create_task(&conn, "t", None, None).unwrap();
+        let running = start_timer(&conn, task.id, None, at(9, 0)).unwrap();
+
+        let found =
+            find_overlapping_entry(&conn, at(10, 0), Some(at(10, 30)), None, at(11, 0)).unwrap();
+        assert_eq!(found.map(|e| e.id), Some(running.id));
+        assert!(
+            find_overlapping_entry(&conn, at(10, 0), Some(at(10, 30)), None, at(9, 30))
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+/// 数据库中 UTC 时间的存储格式
+pub(crate) const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// 将 UTC 时间格式化为数据库中的存储格式
+pub(crate) fn format_utc(time: DateTime<Utc>) -> String {
+    time.format(DATETIME_FORMAT).to_string()
+}
+
+/// 解析数据库中以 UTC 存储的时间字符串
+pub(crate) fn parse_datetime(s: &str) -> DateTime<Utc> {
+    NaiveDateTime::parse_from_str(s, DATETIME_FORMAT)
+        .unwrap()
+        .and_utc()
+}
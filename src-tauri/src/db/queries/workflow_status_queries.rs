@@ -0,0 +1,337 @@
+use crate::db::queries::task_queries::placeholders;
+use crate::domain::workflow_status::WorkflowStatus;
+use crate::t;
+use rusqlite::{
+    params, params_from_iter, Connection, OptionalExtension, Result as SqliteResult, Row,
+};
+
+/// 所有读取状态的查询共用的列
+const STATUS_COLUMNS: &str = "id, project_id, name, position, counts_as_done";
+
+/// 不视为已完成的状态的 ID，可以作为子查询嵌入到其他查询中，
+/// 例如 `WHERE status_id IN ({OPEN_STATUS_IDS})`。任务是否完成只由其状态决定。
+pub const OPEN_STATUS_IDS: &str = "SELECT id FROM workflow_statuses WHERE counts_as_done = 0";
+
+/// 内置状态在数据库中存储为本地化消息 ID，读取时按当前语言显示
+const BUILT_IN_STATUS_KEYS: [&str; 5] = [
+    "status-todo",
+    "status-in-progress",
+    "status-waiting",
+    "status-done",
+    "status-cancelled",
+];
+
+/// 将数据库中存储的状态名称转换为显示名称：内置状态按当前语言翻译，用户自定义的名称原样返回
+pub(crate) fn display_name(stored: String) -> String {
+    if BUILT_IN_STATUS_KEYS.contains(&stored.as_str()) {
+        t!(&stored)
+    } else {
+        stored
+    }
+}
+
+/// 获取状态集合的所有者：项目定义了自己的状态时返回该项目，否则返回 `None`（使用全局状态）
+pub fn get_status_owner(conn: &Connection, project_id: Option<i64>) -> SqliteResult<Option<i64>> {
+    let Some(project_id) = project_id else {
+        return Ok(None);
+    };
+    let has_own: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM workflow_statuses WHERE project_id = ?)",
+        params![project_id],
+        |row| row.get(0),
+    )?;
+    Ok(has_own.then_some(project_id))
+}
+
+/// 获取项目实际使用的状态（项目自己的状态，或全局状态），按顺序排列。
+/// `project_id` 为空时返回全局状态。
+pub fn get_statuses(
+    conn: &Connection,
+    project_id: Option<i64>,
+) -> SqliteResult<Vec<WorkflowStatus>> {
+    let owner = get_status_owner(conn, project_id)?;
+    let sql = format!(
+        "SELECT {STATUS_COLUMNS} FROM workflow_statuses WHERE project_id IS ? ORDER BY position ASC, id ASC"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let iter = stmt.query_map(params![owner], row_to_status)?;
+    iter.collect()
+}
+
+/// 根据 ID 获取单个状态
+pub fn get_status_by_id(conn: &Connection, id: i64) -> SqliteResult<WorkflowStatus> {
+    let sql = format!("SELECT {STATUS_COLUMNS} FROM workflow_statuses WHERE id = ?");
+    conn.query_row(&sql, params![id], row_to_status)
+}
+
+/// 获取项目中新任务（或完成状态切换时）使用的默认状态：
+/// 第一个 `counts_as_done` 与给定值相同的状态
+pub fn get_default_status_id(
+    conn: &Connection,
+    project_id: Option<i64>,
+    counts_as_done: bool,
+) -> SqliteResult<i64> {
+    let owner = get_status_owner(conn, project_id)?;
+    let sql = "SELECT id FROM workflow_statuses WHERE project_id IS ?1 AND counts_as_done = ?2
+               ORDER BY position ASC, id ASC LIMIT 1";
+    conn.query_row(sql, params![owner, counts_as_done], |row| row.get(0))
+}
+
+/// 在状态集合的末尾创建一个新状态，`project_id` 为空时创建全局状态
+pub fn create_status(
+    conn: &Connection,
+    project_id: Option<i64>,
+    name: &str,
+    counts_as_done: bool,
+) -> SqliteResult<WorkflowStatus> {
+    let sql = "INSERT INTO workflow_statuses (project_id, name, counts_as_done, position)
+               VALUES (?1, ?2, ?3, (SELECT COALESCE(MAX(position), -1) + 1 FROM workflow_statuses WHERE project_id IS ?1))";
+    conn.execute(sql, params![project_id, name, counts_as_done])?;
+    let id = conn.last_insert_rowid();
+    get_status_by_id(conn, id)
+}
+
+/// 将全局状态复制为项目自己的状态，并把项目中的任务切换到对应的新状态
+pub fn copy_global_statuses_to_project(conn: &Connection, project_id: i64) -> SqliteResult<()> {
+    conn.execute(
+        "INSERT INTO workflow_statuses (project_id, name, position, counts_as_done)
+         SELECT ?, name, position, counts_as_done FROM workflow_statuses WHERE project_id IS NULL",
+        params![project_id],
+    )?;
+    let task_ids = get_project_task_ids(conn, project_id)?;
+    remap_task_statuses(conn, &task_ids)
+}
+
+/// 更新状态的名称和“视为已完成”标记。
+/// 名称与当前显示名称相同时保留原来存储的值，内置状态因此仍会跟随界面语言。
+///
+/// 处于该状态的任务的完成标记随之改变，调用方需要再通过
+/// `task_completion::sync_tasks_in_status` 补做完成时间，并联动祖先任务和依赖任务。
+pub fn update_status(
+    conn: &Connection,
+    id: i64,
+    name: &str,
+    counts_as_done: bool,
+) -> SqliteResult<WorkflowStatus> {
+    let stored: String = conn.query_row(
+        "SELECT name FROM workflow_statuses WHERE id = ?",
+        params![id],
+        |row| row.get(0),
+    )?;
+    let name = if display_name(stored.clone()) == name {
+        stored
+    } else {
+        name.to_string()
+    };
+    conn.execute(
+        "UPDATE workflow_statuses SET name = ?1, counts_as_done = ?2 WHERE id = ?3",
+        params![name, counts_as_done, id],
+    )?;
+    get_status_by_id(conn, id)
+}
+
+/// 按给定的 ID 顺序重新排列状态集合，`project_id` 为空时调整全局状态
+pub fn reorder_statuses(
+    conn: &Connection,
+    project_id: Option<i64>,
+    status_ids: &[i64],
+) -> SqliteResult<()> {
+    let sql = "UPDATE workflow_statuses SET position = ?1 WHERE id = ?2 AND project_id IS ?3";
+    for (position, id) in status_ids.iter().enumerate() {
+        conn.execute(sql, params![position as i64, id, project_id])?;
+    }
+    Ok(())
+}
+
+/// 删除一个状态。
+/// 调用方需要先通过 `task_completion::sync_tasks_in_status` 把处于该状态的任务切换到其他状态。
+pub fn delete_status(conn: &Connection, id: i64) -> SqliteResult<usize> {
+    conn.execute("DELETE FROM workflow_statuses WHERE id = ?", params![id])
+}
+
+/// 获取处于指定状态的所有任务的 ID
+pub fn get_task_ids_in_status(conn: &Connection, status_id: i64) -> SqliteResult<Vec<i64>> {
+    let mut stmt = conn.prepare("SELECT id FROM tasks WHERE status_id = ? ORDER BY id ASC")?;
+    let iter = stmt.query_map(params![status_id], |row| row.get(0))?;
+    iter.collect()
+}
+
+/// 删除项目自己的所有状态，项目中的任务会切换到同名（或默认）的全局状态
+pub fn delete_statuses_for_project(conn: &Connection, project_id: i64) -> SqliteResult<usize> {
+    conn.execute(
+        "UPDATE tasks SET status_id = COALESCE(
+             (SELECT g.id FROM workflow_statuses g JOIN workflow_statuses s ON s.id = tasks.status_id
+              WHERE g.project_id IS NULL AND g.name = s.name AND g.counts_as_done = s.counts_as_done),
+             (SELECT g.id FROM workflow_statuses g JOIN workflow_statuses s ON s.id = tasks.status_id
+              WHERE g.project_id IS NULL AND g.counts_as_done = s.counts_as_done
+              ORDER BY g.position ASC, g.id ASC LIMIT 1)
+         )
+         WHERE status_id IN (SELECT id FROM workflow_statuses WHERE project_id = ?)",
+        params![project_id],
+    )?;
+    conn.execute(
+        "DELETE FROM workflow_statuses WHERE project_id = ?",
+        params![project_id],
+    )
+}
+
+/// 让任务的状态与其所在项目实际使用的状态集合保持一致。
+/// 任务被移动到使用另一组状态的项目后，会切换到新集合中同名、且完成标记相同的状态；
+/// 找不到时使用新集合中的默认状态。任务的完成标记保持不变。
+pub fn remap_task_statuses(conn: &Connection, task_ids: &[i64]) -> SqliteResult<()> {
+    if task_ids.is_empty() {
+        return Ok(());
+    }
+    let sql = format!(
+        "SELECT t.id, t.project_id, COALESCE(s.counts_as_done, 0) AS is_completed,
+                s.project_id AS status_project_id, s.name AS status_name
+         FROM tasks t LEFT JOIN workflow_statuses s ON s.id = t.status_id
+         WHERE t.id IN ({})",
+        placeholders(task_ids.len())
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map(params_from_iter(task_ids), |row| {
+            Ok((
+                row.get::<_, i64>("id")?,
+                row.get::<_, Option<i64>>("project_id")?,
+                row.get::<_, bool>("is_completed")?,
+                row.get::<_, Option<i64>>("status_project_id")?,
+                row.get::<_, Option<String>>("status_name")?,
+            ))
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    for (task_id, project_id, is_completed, status_project_id, status_name) in rows {
+        let owner = get_status_owner(conn, project_id)?;
+        if status_name.is_some() && status_project_id == owner {
+            continue;
+        }
+        let same_name: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM workflow_statuses WHERE project_id IS ?1 AND name = ?2 AND counts_as_done = ?3",
+                params![owner, status_name, is_completed],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let status_id = match same_name {
+            Some(id) => id,
+            None => get_default_status_id(conn, project_id, is_completed)?,
+        };
+        conn.execute(
+            "UPDATE tasks SET status_id = ? WHERE id = ?",
+            params![status_id, task_id],
+        )?;
+    }
+    Ok(())
+}
+
+/// 内部辅助函数：获取项目中所有任务的 ID
+fn get_project_task_ids(conn: &Connection, project_id: i64) -> SqliteResult<Vec<i64>> {
+    let mut stmt = conn.prepare("SELECT id FROM tasks WHERE project_id = ?")?;
+    let iter = stmt.query_map(params![project_id], |row| row.get(0))?;
+    iter.collect()
+}
+
+/// 内部辅助函数：将查询结果的一行映射为 WorkflowStatus
+fn row_to_status(row: &Row) -> SqliteResult<WorkflowStatus> {
+    Ok(WorkflowStatus {
+        id: row.get("id")?,
+        project_id: row.get("project_id")?,
+        name: display_name(row.get("name")?),
+        position: row.get("position")?,
+        counts_as_done: row.get("counts_as_done")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::{project_queries, task_queries};
+    use crate::db::test_support::open_test_db;
+
+    fn stored_name(conn: &Connection, id: i64) -> String {
+        conn.query_row(
+            "SELECT name FROM workflow_statuses WHERE id = ?",
+            params![id],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn built_in_statuses_are_stored_as_keys_and_localized_on_read() {
+        let conn = open_test_db();
+        let statuses = get_statuses(&conn, None).unwrap();
+        let names: Vec<_> = statuses.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Todo", "In Progress", "Waiting", "Done", "Cancelled"]
+        );
+        assert_eq!(stored_name(&conn, statuses[0].id), "status-todo");
+        assert_eq!(display_name("Review".to_string()), "Review");
+    }
+
+    #[test]
+    fn update_keeps_the_key_unless_the_name_changes() {
+        let conn = open_test_db();
+        let done = get_statuses(&conn, None).unwrap().remove(3);
+
+        update_status(&conn, done.id, "Done", true).unwrap();
+        assert_eq!(stored_name(&conn, done.id), "status-done");
+
+        let renamed = update_status(&conn, done.id, "Shipped", true).unwrap();
+        assert_eq!(renamed.name, "Shipped");
+        assert_eq!(stored_name(&conn, done.id), "Shipped");
+    }
+
+    #[test]
+    fn remap_moves_tasks_to_the_same_named_status_of_the_new_set() {
+        let conn = open_test_db();
+        let project = project_queries::create_project(&conn, "P", None).unwrap();
+        copy_global_statuses_to_project(&conn, project.id).unwrap();
+        let in_progress = get_statuses(&conn, None).unwrap().remove(1);
+
+        let task = task_queries::create_task(&conn, "t", None, None).unwrap();
+        task_queries::set_task_status(&conn, task.id, &in_progress).unwrap();
+        task_queries::move_tasks_to_project(&conn, &[task.id], Some(project.id)).unwrap();
+
+        let task = task_queries::get_task_by_id(&conn, task.id).unwrap();
+        assert_eq!(task.status.project_id, Some(project.id));
+        assert_eq!(task.status.name, "In Progress");
+    }
+
+    #[test]
+    fn remap_falls_back_to_the_default_status() {
+        let conn = open_test_db();
+        let project = project_queries::create_project(&conn, "P", None).unwrap();
+        let review = create_status(&conn, Some(project.id), "Review", false).unwrap();
+        create_status(&conn, Some(project.id), "Closed", true).unwrap();
+        let task = task_queries::create_task(&conn, "t", Some(project.id), None).unwrap();
+        task_queries::set_task_status(&conn, task.id, &review).unwrap();
+
+        task_queries::move_tasks_to_project(&conn, &[task.id], None).unwrap();
+        let task = task_queries::get_task_by_id(&conn, task.id).unwrap();
+        assert_eq!(
+            (task.status.project_id, task.status.name.as_str()),
+            (None, "Todo")
+        );
+    }
+
+    #[test]
+    fn resetting_a_project_returns_tasks_to_the_global_statuses() {
+        let conn = open_test_db();
+        let project = project_queries::create_project(&conn, "P", None).unwrap();
+        copy_global_statuses_to_project(&conn, project.id).unwrap();
+        let task = task_queries::create_task(&conn, "t", Some(project.id), None).unwrap();
+        task_queries::update_task_status(&conn, task.id, true).unwrap();
+
+        delete_statuses_for_project(&conn, project.id).unwrap();
+        let task = task_queries::get_task_by_id(&conn, task.id).unwrap();
+        assert_eq!(
+            (task.status.project_id, task.status.name.as_str()),
+            (None, "Done")
+        );
+        assert_eq!(get_status_owner(&conn, Some(project.id)).unwrap(), None);
+    }
+}
@@ -0,0 +1,11 @@
+use crate::app::setup;
+use rusqlite::Connection;
+use std::path::Path;
+
+/// 打开一个已执行全部迁移的内存数据库，供单元测试使用
+pub fn open_test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    let migration_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+    setup::migrate(&mut conn, &migration_dir).unwrap();
+    conn
+}
@@ -1,3 +1,5 @@
+use crate::t;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
@@ -7,6 +9,10 @@ use ts_rs::TS;
 pub enum AttachmentType {
     Url,       // 网络 Url
     LocalPath, // 本地文件/文件夹路径
+    VaultFile, // 复制进附件保管库的文件，payload 为文件内容的 SHA-256 哈希
+    Note,      // 内嵌的 Markdown 笔记，payload 为笔记正文
+    Email,     // `.eml` 邮件文件，payload 为 EmailPayload 的 JSON
+    CodeRef,   // 代码位置引用，payload 为 CodeRefPayload 的 JSON
 }
 
 /// @description 定义附件的核心数据结构。
@@ -17,17 +23,66 @@ pub struct Attachment {
     pub task_id: i64,
     pub attachment_type: AttachmentType,
     pub payload: String,
+    /// 显示标题，由用户填写或从网页获取
+    pub title: Option<String>,
+    /// 文件大小（字节）
+    pub file_size: Option<i64>,
+    /// 文件的 MIME 类型
+    pub mime_type: Option<String>,
+    /// 文件被添加时的原始文件名
+    pub original_name: Option<String>,
+    /// 本地路径在最近一次检查时是否存在，`None` 表示尚未检查过
+    pub exists: Option<bool>,
+    /// 最近一次健康检查的时间
+    pub last_checked_at: Option<DateTime<Utc>>,
+    /// 文件最近一次修改的时间
+    pub modified_at: Option<DateTime<Utc>>,
 }
 
+/// @description 邮件附件的内容，解析自 `.eml` 文件的邮件头。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct EmailPayload {
+    /// `.eml` 文件的路径
+    pub path: String,
+    pub subject: Option<String>,
+    pub from: Option<String>,
+    pub date: Option<DateTime<Utc>>,
+}
+
+/// @description 代码引用附件的内容：某个文件中的一行或一段连续的行。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct CodeRefPayload {
+    /// 源文件的路径
+    pub path: String,
+    /// 起始行号，从 1 开始
+    pub line: u32,
+    /// 结束行号（包含），为空时只引用起始行
+    pub end_line: Option<u32>,
+}
+
+/// 数据库中出现了无法识别的附件类型
+#[derive(Debug, thiserror::Error)]
+#[error("{}", t!("error-unknown-attachment-type", value = .0))]
+pub struct UnknownAttachmentType(pub String);
+
 // --- 类型转换：实现 Rust 代码 <-> 数据库 (String) 的转换 ---
 
-/// 将数据库中的字符串转换为 AttachmentType 枚举
-impl From<&str> for AttachmentType {
-    fn from(value: &str) -> Self {
+/// 将数据库中的字符串转换为 AttachmentType 枚举。
+/// 无法识别的类型会返回错误，而不是被当作某个已知类型处理。
+impl TryFrom<&str> for AttachmentType {
+    type Error = UnknownAttachmentType;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
-            "Url" => AttachmentType::Url,
-            "LocalPath" => AttachmentType::LocalPath,
-            _ => AttachmentType::Url, // 默认或备用情况
+            "Url" => Ok(AttachmentType::Url),
+            "LocalPath" => Ok(AttachmentType::LocalPath),
+            "VaultFile" => Ok(AttachmentType::VaultFile),
+            "Note" => Ok(AttachmentType::Note),
+            "Email" => Ok(AttachmentType::Email),
+            "CodeRef" => Ok(AttachmentType::CodeRef),
+            _ => Err(UnknownAttachmentType(value.to_string())),
         }
     }
 }
@@ -38,6 +93,10 @@ impl From<AttachmentType> for &str {
         match value {
             AttachmentType::Url => "Url",
             AttachmentType::LocalPath => "LocalPath",
+            AttachmentType::VaultFile => "VaultFile",
+            AttachmentType::Note => "Note",
+            AttachmentType::Email => "Email",
+            AttachmentType::CodeRef => "CodeRef",
         }
     }
 }
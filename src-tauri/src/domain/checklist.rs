@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use ts_rs::TS;
+
+/// @description 任务内的一个检查项。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ChecklistItem {
+    pub id: i64,
+    pub task_id: i64,
+    pub text: String,
+    pub is_checked: bool,
+    /// 检查项在任务内的排列顺序，从 0 开始
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// @description 任务中检查项的完成进度。
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Default, TS)]
+#[ts(export)]
+pub struct ChecklistProgress {
+    /// 检查项的总数
+    pub total: i64,
+    /// 已勾选的检查项数量
+    pub checked: i64,
+}
@@ -0,0 +1,36 @@
+use crate::domain::task::Task;
+use chrono::NaiveDate;
+use serde::Serialize;
+use ts_rs::TS;
+
+/// 每日概览：按项目汇总今天到期、已逾期和高优先级的未完成任务。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct DailyDigest {
+    /// 概览对应的本地日期
+    pub date: NaiveDate,
+    pub due_today_count: i64,
+    pub overdue_count: i64,
+    pub high_priority_count: i64,
+    /// 按项目分组的明细，收件箱中的任务归入 `project_id` 为 `None` 的分组
+    pub projects: Vec<DigestProjectGroup>,
+}
+
+/// 每日概览中单个项目的分组。
+/// 每个任务只会出现在一个列表中：逾期优先于今天到期，二者又优先于高优先级。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct DigestProjectGroup {
+    pub project_id: Option<i64>,
+    pub project_name: Option<String>,
+    pub due_today: Vec<Task>,
+    pub overdue: Vec<Task>,
+    pub high_priority: Vec<Task>,
+}
+
+impl DailyDigest {
+    /// 概览中是否没有任何需要关注的任务
+    pub fn is_empty(&self) -> bool {
+        self.due_today_count == 0 && self.overdue_count == 0 && self.high_priority_count == 0
+    }
+}
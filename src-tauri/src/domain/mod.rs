@@ -1,5 +1,16 @@
 pub mod attachment;
+pub mod checklist;
+pub mod digest;
+pub mod planning;
+pub mod pomodoro;
 pub mod priority;
 pub mod project;
+pub mod section;
+pub mod tag;
 pub mod task;
+pub mod template;
+pub mod time;
+pub mod time_entry;
 pub mod reminder;
+pub mod settings;
+pub mod workflow_status;
@@ -0,0 +1,23 @@
+use crate::domain::settings::EstimateUnit;
+use crate::domain::task::Task;
+use chrono::NaiveDate;
+use serde::Serialize;
+use ts_rs::TS;
+
+/// @description “规划我的一天”的结果：在每日容量内安排的任务，以及放不下的任务。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct DayPlan {
+    /// 计划对应的本地日期
+    pub date: NaiveDate,
+    /// 估算值的单位
+    pub unit: EstimateUnit,
+    /// 当天可以安排的工作量
+    pub capacity: i64,
+    /// 已安排任务的工作量之和，尚未估算的任务按默认估算计算
+    pub planned_estimate: i64,
+    /// 安排在当天的任务，按优先级和截止日期排列
+    pub planned: Vec<Task>,
+    /// 超出容量、没有安排进来的任务，顺序与 `planned` 相同
+    pub overflow: Vec<Task>,
+}
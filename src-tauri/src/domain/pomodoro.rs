@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use ts_rs::TS;
+
+/// @description 番茄钟的阶段
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export)]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+/// @description 正在进行的番茄钟的状态，由后端维护并通过 `pomodoro_tick` 事件推送给前端。
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct PomodoroState {
+    /// 关联的任务，为空时表示未关联任务
+    pub task_id: Option<i64>,
+    pub phase: PomodoroPhase,
+    /// 当前阶段开始的时间
+    pub phase_started_at: DateTime<Utc>,
+    /// 当前阶段预计结束的时间；暂停期间不会前进
+    pub phase_ends_at: DateTime<Utc>,
+    /// 当前阶段剩余的秒数
+    pub remaining_seconds: i64,
+    /// 当前阶段的总时长（秒）
+    pub phase_seconds: i64,
+    pub is_paused: bool,
+    /// 本轮已完成的专注时段数量，用于决定何时进入长休息
+    pub completed_work_sessions: u32,
+}
+
+/// @description 一个已完成的专注时段
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct Pomodoro {
+    pub id: i64,
+    pub task_id: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// 实际专注的时长（秒），不包含暂停的时间
+    pub duration_seconds: i64,
+}
@@ -1,5 +1,6 @@
-use chrono::{DateTime, Utc};
-use serde::Serialize;
+use crate::t;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
 /// 项目的领域模型，定义了一个“项目”或“分类”的核心属性。
@@ -8,6 +9,133 @@ use ts_rs::TS;
 pub struct Project {
     pub id: i64,
     pub name: String,
+    /// 颜色，格式为 `#RRGGBB`
+    pub color: Option<String>,
+    /// 图标，可以是 emoji 或前端图标的名称
+    pub icon: Option<String>,
+    pub description: Option<String>,
+    pub status: ProjectStatus,
+    /// 父项目的 ID，为空时是顶层项目（区域）
+    pub parent_id: Option<i64>,
+    /// 直接属于该项目的任务数量（包含子任务）
+    pub task_count: i64,
+    /// 直接属于该项目的未完成任务数量
+    pub open_task_count: i64,
+    /// 该项目及其所有后代项目中的任务数量
+    pub subtree_task_count: i64,
+    /// 该项目及其所有后代项目中的未完成任务数量
+    pub subtree_open_task_count: i64,
+    /// 该项目及其所有后代项目中所有任务的估算之和
+    pub subtree_estimate: i64,
+    /// 该项目及其所有后代项目中尚未完成的任务的估算之和
+    pub subtree_remaining_estimate: i64,
+    /// 项目的统计数据，只有在获取项目时要求包含统计数据才会填充
+    pub stats: Option<ProjectStats>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// @description 定义项目的状态。
+/// 已归档的项目及其子项目、任务不会出现在默认的列表中，但不会被删除。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export)]
+pub enum ProjectStatus {
+    Active,    // 进行中
+    OnHold,    // 暂停
+    Completed, // 已完成
+    Archived,  // 已归档
+}
+
+/// 数据库中出现了无法识别的项目状态
+#[derive(Debug, thiserror::Error)]
+#[error("{}", t!("error-unknown-project-status", value = .0))]
+pub struct UnknownProjectStatus(pub String);
+
+// --- 类型转换：实现 Rust 代码 <-> 数据库 (String) 的转换 ---
+
+/// 将数据库中的字符串转换为 ProjectStatus 枚举
+impl TryFrom<&str> for ProjectStatus {
+    type Error = UnknownProjectStatus;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "Active" => Ok(ProjectStatus::Active),
+            "OnHold" => Ok(ProjectStatus::OnHold),
+            "Completed" => Ok(ProjectStatus::Completed),
+            "Archived" => Ok(ProjectStatus::Archived),
+            _ => Err(UnknownProjectStatus(value.to_string())),
+        }
+    }
+}
+
+/// 将 ProjectStatus 枚举转换为字符串存入数据库
+impl From<ProjectStatus> for &str {
+    fn from(value: ProjectStatus) -> Self {
+        match value {
+            ProjectStatus::Active => "Active",
+            ProjectStatus::OnHold => "OnHold",
+            ProjectStatus::Completed => "Completed",
+            ProjectStatus::Archived => "Archived",
+        }
+    }
+}
+
+/// @description 删除项目时，如何处理项目中的任务。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export)]
+pub enum ProjectDeletionStrategy {
+    /// 将任务移动到收件箱
+    MoveToInbox,
+    /// 将任务移动到另一个项目，值为目标项目的 ID
+    MoveToProject(i64),
+    /// 删除任务及其所有子任务
+    DeleteTasks,
+}
+
+/// @description 删除项目后的结果汇总。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ProjectDeletionSummary {
+    pub project_id: i64,
+    /// 被移动的任务数量（包含子任务）
+    pub moved_task_count: i64,
+    /// 被删除的任务数量（包含子任务）
+    pub deleted_task_count: i64,
+    /// 被移动到上一级的子项目数量
+    pub reparented_project_count: i64,
+}
+
+/// @description 项目的进度与统计数据，统计范围包含项目中任务的各级子任务。
+#[derive(Debug, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ProjectStats {
+    pub project_id: i64,
+    pub total_count: i64,
+    pub completed_count: i64,
+    pub open_count: i64,
+    /// 已过截止日期但尚未完成的任务数量
+    pub overdue_count: i64,
+    /// 完成百分比（0 - 100），没有任务时为 0
+    pub completion_percentage: f64,
+    /// 最近几周每周完成的任务数量，按时间升序排列
+    pub completed_per_week: Vec<WeeklyCompletion>,
+    /// 从创建到完成的平均时长（小时），没有已完成的任务时为空
+    pub average_completion_hours: Option<f64>,
+    /// 为项目中的任务完成的番茄钟数量
+    pub pomodoro_count: i64,
+    /// 项目中各级任务记录的总时长（秒）
+    pub tracked_seconds: i64,
+    /// 项目中所有任务的估算之和，不含子项目；包含子项目的汇总见 `Project::subtree_estimate`
+    pub total_estimate: i64,
+    /// 项目中尚未完成的任务的估算之和，不含子项目
+    pub remaining_estimate: i64,
+}
+
+/// @description 某一周内完成的任务数量。
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct WeeklyCompletion {
+    /// 该周的第一天（周一）
+    pub week_start: NaiveDate,
+    pub completed_count: i64,
+}
@@ -1,7 +1,11 @@
-use chrono::{DateTime, Utc};
-use serde::Serialize;
+use crate::domain::time::local_to_utc;
+use chrono::{DateTime, Days, Duration, Local, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+/// “明天早上”稍后提醒所使用的本地时间（小时）
+const TOMORROW_MORNING_HOUR: u32 = 9;
+
 /// 提醒事项的领域模型
 #[derive(Debug, Serialize, Clone, TS)]
 #[ts(export)]
@@ -12,4 +16,45 @@ pub struct Reminder {
     pub remind_at: DateTime<Utc>,
     /// 是否已发送
     pub is_sent: bool,
+    /// 相对提醒：在截止日期前多少分钟提醒。
+    /// 为 `None` 时表示这是一个绝对时间的提醒。
+    pub offset_minutes: Option<i64>,
+    /// 提醒实际发出的时间
+    pub sent_at: Option<DateTime<Utc>>,
+    /// 用户是否已在收件箱中处理了该提醒
+    pub is_dismissed: bool,
+    /// 被推迟（snooze）的次数
+    pub snooze_count: i64,
+    /// 是否因应用关闭而错过（错过的提醒不会再单独发送）
+    pub is_missed: bool,
+    /// 因免打扰被推迟到的时间，在此之前不会发送
+    pub deferred_until: Option<DateTime<Utc>>,
+    /// 被推迟（snooze）到的时间。再次发出之前，截止日期的变化不会覆盖它
+    pub snoozed_until: Option<DateTime<Utc>>,
+}
+
+/// @description 稍后提醒的选项。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export)]
+pub enum SnoozeOption {
+    TenMinutes,            // 10 分钟后
+    OneHour,               // 1 小时后
+    TomorrowMorning,       // 明天早上（本地时间 9 点）
+    Custom(DateTime<Utc>), // 用户自定义的时间点
+}
+
+impl SnoozeOption {
+    /// 以 `now` 为基准，计算推迟后的提醒时间
+    pub fn resolve(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            SnoozeOption::TenMinutes => now + Duration::minutes(10),
+            SnoozeOption::OneHour => now + Duration::hours(1),
+            SnoozeOption::TomorrowMorning => {
+                let tomorrow = now.with_timezone(&Local).date_naive() + Days::new(1);
+                let morning = NaiveTime::from_hms_opt(TOMORROW_MORNING_HOUR, 0, 0).unwrap();
+                local_to_utc(tomorrow, morning)
+            }
+            SnoozeOption::Custom(at) => *at,
+        }
+    }
 }
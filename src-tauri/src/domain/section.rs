@@ -0,0 +1,26 @@
+use crate::domain::task::Task;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use ts_rs::TS;
+
+/// @description 项目内的分组，用于将一个项目中的任务划分为有序的几个部分。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct Section {
+    pub id: i64,
+    pub project_id: i64,
+    pub name: String,
+    /// 分组在项目内的排列顺序，从 0 开始
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// @description 按分组归类后的一组任务。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct SectionTasks {
+    /// 为空时表示不属于任何分组的任务
+    pub section: Option<Section>,
+    pub tasks: Vec<Task>,
+}
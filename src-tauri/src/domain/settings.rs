@@ -0,0 +1,292 @@
+use crate::domain::time::local_to_utc;
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 每日概览（晨间摘要）的设置，持久化在 `settings.json` 中。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct DigestSettings {
+    /// 是否启用每日概览
+    pub enabled: bool,
+    /// 每天发送概览的本地时间
+    pub time: NaiveTime,
+    /// 休息日，不发送概览。取值为 ISO 星期序号：1 = 周一 … 7 = 周日
+    pub days_off: Vec<u32>,
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        DigestSettings {
+            enabled: false,
+            time: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            days_off: Vec::new(),
+        }
+    }
+}
+
+impl DigestSettings {
+    /// 判断给定的日期是否为休息日
+    pub fn is_day_off(&self, date: NaiveDate) -> bool {
+        self.days_off.contains(&date.weekday().number_from_monday())
+    }
+}
+
+/// 免打扰设置：每天固定的安静时段，以及手动开启的“专注/勿扰直到某时”。
+/// 处于这些时段内的提醒会被推迟到时段结束后再发送。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct QuietHoursSettings {
+    /// 是否启用每天的安静时段
+    pub enabled: bool,
+    /// 安静时段开始的本地时间
+    pub start: NaiveTime,
+    /// 安静时段结束的本地时间。早于 `start` 时表示跨越午夜（如 22:00 - 07:00）
+    pub end: NaiveTime,
+    /// 是否允许高优先级任务的提醒无视免打扰立即发送
+    pub allow_high_priority: bool,
+    /// 手动开启的勿扰模式的结束时间，`None` 表示未开启
+    pub do_not_disturb_until: Option<DateTime<Utc>>,
+}
+
+impl Default for QuietHoursSettings {
+    fn default() -> Self {
+        QuietHoursSettings {
+            enabled: false,
+            start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+            allow_high_priority: true,
+            do_not_disturb_until: None,
+        }
+    }
+}
+
+impl QuietHoursSettings {
+    /// 如果 `now` 处于勿扰模式或安静时段内，返回该时段结束的时间；否则返回 `None`。
+    /// 勿扰模式结束时如果恰好落在安静时段内，则一直推迟到安静时段结束。
+    pub fn active_window_end(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut window_end = self.do_not_disturb_until.filter(|until| *until > now);
+
+        let check_at = window_end.unwrap_or(now).with_timezone(&Local);
+        if self.in_quiet_hours(check_at.time()) {
+            window_end = Some(self.next_quiet_end(check_at));
+        }
+        window_end
+    }
+
+    /// 判断本地时间是否处于每天的安静时段内
+    fn in_quiet_hours(&self, time: NaiveTime) -> bool {
+        if !self.enabled || self.start == self.end {
+            return false;
+        }
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            // 跨越午夜的时段
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// 计算 `after` 之后最近一次安静时段结束的时间
+    fn next_quiet_end(&self, after: DateTime<Local>) -> DateTime<Utc> {
+        let mut date = after.date_naive();
+        if after.time() >= self.end {
+            date = date + Days::new(1);
+        }
+        local_to_utc(date, self.end)
+    }
+}
+
+/// 完成一个任务时，如何处理它尚未完成的后代任务。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, TS)]
+#[ts(export)]
+pub enum OpenDescendantsPolicy {
+    /// 只完成该任务本身
+    #[default]
+    Ignore,
+    /// 一并完成所有未完成的后代任务
+    Complete,
+    /// 不完成任务，而是返回未完成的后代任务数量，由前端提示用户
+    Warn,
+}
+
+/// 任务完成状态联动的设置，持久化在 `settings.json` 中。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct TaskCompletionSettings {
+    /// 所有子任务都完成后，自动完成父任务；重新打开子任务时，也会重新打开已完成的父任务
+    pub auto_complete_parent: bool,
+    /// 完成任务时如何处理尚未完成的后代任务
+    pub open_descendants: OpenDescendantsPolicy,
+}
+
+/// 番茄钟设置，持久化在 `settings.json` 中。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct PomodoroSettings {
+    /// 专注时段的时长（分钟）
+    pub work_minutes: u32,
+    /// 短休息的时长（分钟）
+    pub short_break_minutes: u32,
+    /// 长休息的时长（分钟）
+    pub long_break_minutes: u32,
+    /// 每完成多少个专注时段后进入一次长休息
+    pub long_break_interval: u32,
+    /// 休息结束后是否自动开始下一个专注时段；否则休息结束时番茄钟停止
+    pub auto_start_work: bool,
+}
+
+impl Default for PomodoroSettings {
+    fn default() -> Self {
+        PomodoroSettings {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            long_break_interval: 4,
+            auto_start_work: false,
+        }
+    }
+}
+
+/// 工作量估算的单位。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, TS)]
+#[ts(export)]
+pub enum EstimateUnit {
+    /// 分钟
+    #[default]
+    Minutes,
+    /// 故事点
+    Points,
+}
+
+/// 工作量估算和每日计划的设置，持久化在 `settings.json` 中。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct PlanningSettings {
+    /// 所有估算值使用的单位
+    pub estimate_unit: EstimateUnit,
+    /// 每天可以安排的工作量，单位与估算相同
+    pub daily_capacity: u32,
+    /// 制定计划时，尚未估算的任务按这个工作量计算
+    pub default_estimate: u32,
+}
+
+impl Default for PlanningSettings {
+    fn default() -> Self {
+        PlanningSettings {
+            estimate_unit: EstimateUnit::Minutes,
+            daily_capacity: 480,
+            default_estimate: 30,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hm(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    /// 2030-01-15 这一天（避开夏令时切换）指定本地时间对应的 UTC 时间
+    fn at(day_offset: u64, hour: u32, minute: u32) -> DateTime<Utc> {
+        let date = NaiveDate::from_ymd_opt(2030, 1, 15).unwrap() + Days::new(day_offset);
+        local_to_utc(date, hm(hour, minute))
+    }
+
+    fn quiet(start: NaiveTime, end: NaiveTime) -> QuietHoursSettings {
+        QuietHoursSettings {
+            enabled: true,
+            start,
+            end,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn in_quiet_hours_handles_same_day_overnight_and_empty_windows() {
+        let cases = [
+            // (start, end, time, expected)
+            (hm(9, 0), hm(17, 0), hm(12, 0), true),
+            (hm(9, 0), hm(17, 0), hm(17, 0), false),
+            (hm(22, 0), hm(7, 0), hm(23, 0), true),
+            (hm(22, 0), hm(7, 0), hm(6, 59), true),
+            (hm(22, 0), hm(7, 0), hm(7, 0), false),
+            (hm(22, 0), hm(7, 0), hm(12, 0), false),
+            (hm(8, 0), hm(8, 0), hm(8, 0), false),
+            (hm(8, 0), hm(8, 0), hm(20, 0), false),
+        ];
+        for (start, end, time, expected) in cases {
+            assert_eq!(
+                quiet(start, end).in_quiet_hours(time),
+                expected,
+                "{start}-{end} at {time}"
+            );
+        }
+
+        let disabled = QuietHoursSettings {
+            enabled: false,
+            ..quiet(hm(22, 0), hm(7, 0))
+        };
+        assert!(!disabled.in_quiet_hours(hm(23, 0)));
+    }
+
+    #[test]
+    fn next_quiet_end_crosses_midnight_when_needed() {
+        let overnight = quiet(hm(22, 0), hm(7, 0));
+        let cases = [
+            // (now, expected end)
+            (at(0, 23, 0), at(1, 7, 0)),
+            (at(0, 2, 0), at(0, 7, 0)),
+            (at(0, 7, 0), at(1, 7, 0)),
+        ];
+        for (now, expected) in cases {
+            assert_eq!(
+                overnight.next_quiet_end(now.with_timezone(&Local)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn active_window_end_combines_quiet_hours_and_do_not_disturb() {
+        let overnight = quiet(hm(22, 0), hm(7, 0));
+        let with_dnd = |until| QuietHoursSettings {
+            do_not_disturb_until: Some(until),
+            ..overnight.clone()
+        };
+        let cases = [
+            // (settings, now, expected)
+            (overnight.clone(), at(0, 12, 0), None),
+            (overnight.clone(), at(0, 23, 0), Some(at(1, 7, 0))),
+            (overnight.clone(), at(1, 6, 0), Some(at(1, 7, 0))),
+            // 勿扰模式在安静时段之外单独生效
+            (with_dnd(at(0, 15, 0)), at(0, 12, 0), Some(at(0, 15, 0))),
+            // 勿扰模式结束时落在安静时段内，则推迟到安静时段结束
+            (with_dnd(at(0, 23, 0)), at(0, 12, 0), Some(at(1, 7, 0))),
+            // 已经过期的勿扰模式不再生效
+            (with_dnd(at(0, 11, 0)), at(0, 12, 0), None),
+            (
+                QuietHoursSettings {
+                    enabled: false,
+                    ..with_dnd(at(0, 23, 0))
+                },
+                at(0, 12, 0),
+                Some(at(0, 23, 0)),
+            ),
+        ];
+        for (settings, now, expected) in cases {
+            assert_eq!(
+                settings.active_window_end(now),
+                expected,
+                "{settings:?} at {now}"
+            );
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use crate::domain::checklist::ChecklistProgress;
 use crate::domain::priority::Priority;
+use crate::domain::workflow_status::WorkflowStatus;
 use chrono::{DateTime, Utc}; // 引入 `chrono` 库来处理与时区无关的时间。
-use serde::Serialize; // 引入 `serde` 的 `Serialize` Trait，用于将结构体序列化为 JSON。
+use serde::{Deserialize, Serialize}; // 引入 `serde` 的 Trait，用于在结构体和 JSON 之间转换。
 use ts_rs::TS;
 
 /// 核心业务模型：任务
@@ -18,8 +20,13 @@ pub struct Task {
     pub description: Option<String>,
 
     /// 任务是否已完成。
+    // 读取时由 `status.counts_as_done` 得出，数据库中不单独存储；
+    // 保留这个字段以兼容只关心“完成/未完成”的界面。
     pub is_completed: bool,
 
+    /// 任务当前所处的工作流状态。
+    pub status: WorkflowStatus,
+
     /// 任务所属项目的 ID。
     // 使用 `Option<i64>` 类型来表示这个字段。
     // - `Some(project_id)`: 代表任务属于某个项目。
@@ -32,10 +39,43 @@ pub struct Task {
     // - `None`: 代表这是一个顶层任务（父任务），对应数据库中的 NULL。
     pub parent_id: Option<i64>,
 
+    /// 任务在项目内所属分组的 ID，为空时不属于任何分组。
+    pub section_id: Option<i64>,
+
     // 子任务的数量。
     // 这个字段不存在于数据库表中，而是通过 SQL 查询动态计算出来的。
     pub subtask_count: i64,
 
+    /// 所有后代任务（子任务、子任务的子任务……）的数量。
+    pub descendant_count: i64,
+
+    /// 所有后代任务中已完成的数量。
+    pub completed_descendant_count: i64,
+
+    /// 任务是否被尚未完成的依赖任务阻塞。
+    pub is_blocked: bool,
+
+    /// 阻塞该任务的、尚未完成的依赖任务的 ID。
+    pub blocked_by: Vec<i64>,
+
+    /// 任务记录的总时长（秒），不包含子任务。
+    pub tracked_seconds: i64,
+
+    /// 为该任务完成的番茄钟（专注时段）数量。
+    pub pomodoro_count: i64,
+
+    /// 任务中检查项的完成进度。
+    pub checklist_progress: ChecklistProgress,
+
+    /// 任务本身的工作量估算，单位由计划设置决定（分钟或故事点）；为空时表示尚未估算。
+    pub estimate: Option<i64>,
+
+    /// 任务及其所有后代任务的估算之和。
+    pub rolled_up_estimate: i64,
+
+    /// 任务及其所有后代任务中，尚未完成部分的估算之和。
+    pub remaining_estimate: i64,
+
     /// 优先级
     pub priority: Priority,
 
@@ -49,4 +89,72 @@ pub struct Task {
     /// 这是处理时间的最佳实践，可以避免因用户本地时区不同而导致的数据混乱。
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+
+    /// 任务的完成时间，未完成时为空。
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// 更新任务完成状态的结果，包含因联动而一并改变的其他任务。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct TaskStatusChange {
+    /// 更新后的任务
+    pub task: Task,
+    /// 状态是否已经更新；在“提醒”策略下，存在未完成的后代任务时不会更新
+    pub applied: bool,
+    /// 完成任务时，尚未完成的后代任务数量
+    pub open_descendant_count: i64,
+    /// 随之被一并完成的后代任务
+    pub completed_descendant_ids: Vec<i64>,
+    /// 随之被自动完成（或重新打开）的祖先任务，由近及远排列
+    pub updated_ancestor_ids: Vec<i64>,
+    /// 因最后一个阻塞任务完成而解除阻塞的任务
+    pub unblocked_task_ids: Vec<i64>,
+}
+
+/// 复制任务时，副本放在哪里。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, TS)]
+#[ts(export)]
+pub enum DuplicateTarget {
+    /// 与原任务相同的项目和父任务下
+    #[default]
+    Original,
+    /// 作为指定任务的子任务，使用该任务所在的项目
+    Parent(i64),
+    /// 作为指定项目的顶层任务，为空时放入收件箱
+    Project(Option<i64>),
+}
+
+/// 复制任务时如何处理提醒。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, TS)]
+#[ts(export)]
+pub enum ReminderCopyMode {
+    /// 随截止日期一起平移：相对提醒按新的截止日期重新计算，绝对提醒平移相同的天数。
+    /// 平移后已经过去的提醒不会被复制。
+    #[default]
+    Shift,
+    /// 不复制提醒
+    Drop,
+}
+
+/// 批量操作中单个任务的结果。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct BulkTaskResult {
+    pub id: i64,
+    pub success: bool,
+    /// 失败时已经本地化的错误信息
+    pub error: Option<String>,
+}
+
+/// 批量操作的结果。所有任务在同一个事务中处理，失败的任务不会影响其他任务。
+#[derive(Debug, Serialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct BulkTaskOutcome {
+    /// 每个任务的结果，顺序与传入的 ID 相同
+    pub results: Vec<BulkTaskResult>,
+    pub succeeded_count: i64,
+    pub failed_count: i64,
+    /// 因此改变的所有任务，包括联动改变的后代任务、祖先任务和解除阻塞的任务
+    pub changed_task_ids: Vec<i64>,
 }
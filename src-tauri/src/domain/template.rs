@@ -0,0 +1,218 @@
+use crate::domain::attachment::AttachmentType;
+use crate::domain::priority::Priority;
+use crate::domain::project::Project;
+use crate::domain::task::Task;
+use crate::t;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ts_rs::TS;
+
+/// @description 模板的类型。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export)]
+pub enum TemplateKind {
+    /// 一个任务及其所有子任务
+    Task,
+    /// 一个项目及其中的所有任务
+    Project,
+}
+
+/// 数据库中出现了无法识别的模板类型
+#[derive(Debug, thiserror::Error)]
+#[error("{}", t!("error-unknown-template-kind", value = .0))]
+pub struct UnknownTemplateKind(pub String);
+
+// --- 类型转换：实现 Rust 代码 <-> 数据库 (String) 的转换 ---
+
+/// 将数据库中的字符串转换为 TemplateKind 枚举
+impl TryFrom<&str> for TemplateKind {
+    type Error = UnknownTemplateKind;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "Task" => Ok(TemplateKind::Task),
+            "Project" => Ok(TemplateKind::Project),
+            _ => Err(UnknownTemplateKind(value.to_string())),
+        }
+    }
+}
+
+/// 将 TemplateKind 枚举转换为字符串存入数据库
+impl From<TemplateKind> for &str {
+    fn from(value: TemplateKind) -> Self {
+        match value {
+            TemplateKind::Task => "Task",
+            TemplateKind::Project => "Project",
+        }
+    }
+}
+
+/// @description 从已有的任务或项目中保存下来的模板。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct Template {
+    pub id: i64,
+    pub name: String,
+    pub kind: TemplateKind,
+    /// 项目模板创建的项目名称，可以包含 `{{变量}}`；任务模板为空
+    pub project_name: Option<String>,
+    pub project_color: Option<String>,
+    pub project_icon: Option<String>,
+    pub project_description: Option<String>,
+    /// 模板中的任务数量（包含子任务）
+    pub task_count: i64,
+    /// 模板中用到的变量名，去重后按名称排序
+    pub variables: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// @description 模板中的一个任务。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct TemplateTask {
+    pub id: i64,
+    pub template_id: i64,
+    /// 模板中父任务的 ID，为空时是模板的顶层任务
+    pub parent_id: Option<i64>,
+    /// 在兄弟任务中的顺序，从 0 开始
+    pub position: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Priority,
+    pub estimate: Option<i64>,
+    /// 截止日期相对于锚点日期（本地时间零点）的偏移量（分钟），为空时没有截止日期
+    pub due_offset_minutes: Option<i64>,
+    /// 相对提醒：在截止日期前多少分钟提醒，按从远到近排序
+    pub reminder_offsets: Vec<i64>,
+    pub attachments: Vec<TemplateAttachment>,
+}
+
+/// @description 模板任务的附件，字段含义与 `Attachment` 相同。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct TemplateAttachment {
+    pub id: i64,
+    pub template_task_id: i64,
+    pub attachment_type: AttachmentType,
+    pub payload: String,
+    pub title: Option<String>,
+    pub file_size: Option<i64>,
+    pub mime_type: Option<String>,
+    pub original_name: Option<String>,
+}
+
+/// @description 使用模板创建的结果。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct TemplateInstance {
+    pub template_id: i64,
+    /// 项目模板创建的项目，任务模板为空
+    pub project: Option<Project>,
+    /// 创建的顶层任务
+    pub tasks: Vec<Task>,
+    /// 创建的任务总数（包含子任务）
+    pub created_task_count: i64,
+}
+
+/// 找出文本中所有的 `{{变量}}` 占位符，返回占位符在文本中的字节范围和（去掉首尾空白的）变量名。
+/// 每个 `}}` 与它之前最近的 `{{` 配对，多余的花括号（例如 `{{{name}}}` 外层的一对）作为普通文本保留；
+/// 没有闭合的 `{{` 和变量名为空的 `{{}}` 不是占位符。
+fn find_placeholders(text: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut placeholders = Vec::new();
+    let mut from = 0;
+    while let Some(close) = text[from..].find("}}") {
+        let close = from + close;
+        if let Some(open) = text[from..close].rfind("{{") {
+            let start = from + open;
+            let name = text[start + 2..close].trim();
+            if !name.is_empty() {
+                placeholders.push((start..close + 2, name));
+            }
+        }
+        from = close + 2;
+    }
+    placeholders
+}
+
+/// 返回文本中用到的所有变量名（可能重复）
+pub fn template_variables(text: &str) -> impl Iterator<Item = &str> {
+    find_placeholders(text).into_iter().map(|(_, name)| name)
+}
+
+/// 将文本中的 `{{变量}}` 替换为对应的值，没有提供值的占位符保持原样
+pub fn render_template_text(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(text.len());
+    let mut last = 0;
+    for (range, name) in find_placeholders(text) {
+        if let Some(value) = variables.get(name) {
+            rendered.push_str(&text[last..range.start]);
+            rendered.push_str(value);
+            last = range.end;
+        }
+    }
+    rendered.push_str(&text[last..]);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_variables_and_trims_names() {
+        let names: Vec<_> =
+            template_variables("Ship {{ version }} to {{client}} ({{version}})").collect();
+        assert_eq!(names, ["version", "client", "version"]);
+    }
+
+    #[test]
+    fn ignores_empty_and_unterminated_placeholders() {
+        assert_eq!(template_variables("{{}} {{   }} {{open").count(), 0);
+        assert_eq!(
+            template_variables("}} stray {{name}}").collect::<Vec<_>>(),
+            ["name"]
+        );
+    }
+
+    #[test]
+    fn renders_known_variables() {
+        let rendered = render_template_text(
+            "Release {{ version }} for {{client}}",
+            &values(&[("version", "1.2"), ("client", "ACME")]),
+        );
+        assert_eq!(rendered, "Release 1.2 for ACME");
+    }
+
+    #[test]
+    fn keeps_placeholders_without_a_value() {
+        let rendered = render_template_text("{{a}} and {{ b }}", &values(&[("a", "A")]));
+        assert_eq!(rendered, "A and {{ b }}");
+    }
+
+    #[test]
+    fn keeps_literal_braces() {
+        let variables = values(&[("name", "x")]);
+        assert_eq!(render_template_text("{{{name}}}", &variables), "{x}");
+        assert_eq!(
+            render_template_text("{ name } {{name", &variables),
+            "{ name } {{name"
+        );
+        assert_eq!(render_template_text("{{a {{name}}", &variables), "{{a x");
+        assert_eq!(render_template_text("{{}}", &variables), "{{}}");
+    }
+
+    #[test]
+    fn values_are_not_rendered_again() {
+        let variables = values(&[("a", "{{b}}"), ("b", "B")]);
+        assert_eq!(render_template_text("{{a}}", &variables), "{{b}}");
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+/// 将本地日期和时间转换为 UTC 时间。
+pub fn local_to_utc(date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+    let local = date.and_time(time);
+    earliest_or_utc(local.and_local_timezone(Local), local)
+}
+
+/// 内部辅助函数：本地时间对应两个时刻（如夏令时回拨）时取较早的一个；
+/// 本地时间不存在（如夏令时跳变）时，退化为按 UTC 计算。
+fn earliest_or_utc<Tz: TimeZone>(
+    result: LocalResult<DateTime<Tz>>,
+    local: NaiveDateTime,
+) -> DateTime<Utc> {
+    result
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| local.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn daylight_saving_transitions_resolve_to_a_single_instant() {
+        let local = NaiveDate::from_ymd_opt(2026, 3, 8)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        let winter = FixedOffset::west_opt(5 * 3600).unwrap();
+        let summer = FixedOffset::west_opt(4 * 3600).unwrap();
+
+        // 跳变时不存在的本地时间按 UTC 计算
+        assert_eq!(
+            earliest_or_utc::<FixedOffset>(LocalResult::None, local),
+            local.and_utc()
+        );
+
+        // 回拨时重复的本地时间取较早的时刻
+        let ambiguous = LocalResult::Ambiguous(
+            summer.from_local_datetime(&local).unwrap(),
+            winter.from_local_datetime(&local).unwrap(),
+        );
+        assert_eq!(
+            earliest_or_utc(ambiguous, local),
+            summer
+                .from_local_datetime(&local)
+                .unwrap()
+                .with_timezone(&Utc)
+        );
+    }
+}
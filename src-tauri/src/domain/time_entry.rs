@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use ts_rs::TS;
+
+/// @description 一条时间记录，由计时器产生或手动录入。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct TimeEntry {
+    pub id: i64,
+    pub task_id: i64,
+    pub started_at: DateTime<Utc>,
+    /// 为空时表示计时器正在运行
+    pub ended_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    /// 记录的时长（秒），正在运行的计时器计算到当前时间
+    pub duration_seconds: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// @description 项目在一段时间内的工时汇总，用于按小时结算。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ProjectTimeReport {
+    pub project_id: i64,
+    /// 统计范围的开始时间，为空时不限制
+    pub from: Option<DateTime<Utc>>,
+    /// 统计范围的结束时间，为空时不限制
+    pub to: Option<DateTime<Utc>>,
+    pub total_seconds: i64,
+    /// 按任务汇总的工时，只包含有记录的任务，按时长降序排列
+    pub tasks: Vec<TaskTrackedTime>,
+}
+
+/// @description 单个任务在统计范围内的工时。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct TaskTrackedTime {
+    pub task_id: i64,
+    pub title: String,
+    pub total_seconds: i64,
+}
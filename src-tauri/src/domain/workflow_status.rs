@@ -0,0 +1,26 @@
+use crate::domain::task::Task;
+use serde::Serialize;
+use ts_rs::TS;
+
+/// @description 任务的工作流状态，例如“待办 / 进行中 / 已完成”。
+/// 项目可以定义自己的一组状态，没有定义的项目使用全局状态。
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct WorkflowStatus {
+    pub id: i64,
+    /// 所属的项目，为空时是全局状态
+    pub project_id: Option<i64>,
+    pub name: String,
+    /// 状态在看板中的排列顺序，从 0 开始
+    pub position: i64,
+    /// 处于该状态的任务是否视为已完成
+    pub counts_as_done: bool,
+}
+
+/// @description 按工作流状态归类后的一组任务，用于看板视图中的一列。
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct StatusTasks {
+    pub status: WorkflowStatus,
+    pub tasks: Vec<Task>,
+}
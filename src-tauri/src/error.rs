@@ -1,3 +1,5 @@
+use crate::t;
+
 // 使用 `thiserror` 库可以方便地为自定义 Error 枚举派生标准的错误处理能力。
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -13,12 +15,39 @@ pub enum Error {
 
     #[error(transparent)]
     Store(#[from] tauri_plugin_store::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error), // 用于包装设置等 JSON 数据的序列化错误
+
+    // 业务规则校验失败时使用，携带已经本地化、可直接展示给用户的错误信息
+    #[error("{0}")]
+    Validation(String),
 }
 
 // `pub type Result<T>` 是一个贯穿整个项目的类型别名，
 // 它代表一个返回 `T` 或自定义 `Error` 的 `Result`，简化了函数签名。
 pub type Result<T> = std::result::Result<T, Error>;
 
+impl Error {
+    /// 返回按当前语言本地化后的、面向用户的错误信息
+    pub fn localized_message(&self) -> String {
+        match self {
+            Error::Sqlite(rusqlite::Error::QueryReturnedNoRows) => t!("error-not-found"),
+            Error::Sqlite(rusqlite::Error::SqliteFailure(e, _))
+                if e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE =>
+            {
+                t!("error-already-exists")
+            }
+            Error::Sqlite(e) => t!("error-database", detail = e),
+            Error::Io(e) => t!("error-io", detail = e),
+            Error::Store(e) => t!("error-settings", detail = e),
+            Error::Json(e) => t!("error-settings", detail = e),
+            Error::Tauri(e) => t!("error-internal", detail = e),
+            Error::Validation(message) => message.clone(),
+        }
+    }
+}
+
 // 为了让的自定义错误能被 Tauri 正确序列化并通过 `invoke` 的 `reject` 返回给前端，
 // 需要为它手动实现 `serde::Serialize` Trait。
 impl serde::Serialize for Error {
@@ -26,7 +55,7 @@ impl serde::Serialize for Error {
     where
         S: serde::Serializer,
     {
-        // 只将错误的文本信息序列化为字符串发给前端。
-        serializer.serialize_str(self.to_string().as_ref())
+        // 只将本地化后的错误文本序列化为字符串发给前端。
+        serializer.serialize_str(self.localized_message().as_ref())
     }
 }
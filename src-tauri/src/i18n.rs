@@ -0,0 +1,127 @@
+//! 后端本地化层。
+//!
+//! 所有由后端产生、会展示给用户的文本（通知、托盘菜单、错误信息）都通过这里获取。
+//! 文案存放在 `locales/*.ftl` 中，使用 Fluent 语法的简单消息子集，并在编译时嵌入二进制。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use ts_rs::TS;
+
+/// @description 后端支持的界面语言。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, TS)]
+#[ts(export)]
+pub enum Language {
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "zh-CN")]
+    Chinese,
+}
+
+impl Default for Language {
+    /// 未设置语言时，根据系统的 `LC_ALL` / `LANG` 环境变量推断，无法判断时使用英文
+    fn default() -> Self {
+        let locale = std::env::var("LC_ALL")
+            .or_else(|_| std::env::var("LANG"))
+            .unwrap_or_default();
+        if locale.starts_with("zh") {
+            Language::Chinese
+        } else {
+            Language::English
+        }
+    }
+}
+
+impl Language {
+    /// 该语言对应的消息目录源文件
+    fn catalog_source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::Chinese => include_str!("../locales/zh-CN.ftl"),
+        }
+    }
+
+    /// 获取（并在首次使用时解析）该语言的消息目录
+    fn catalog(self) -> &'static HashMap<&'static str, &'static str> {
+        static ENGLISH: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+        static CHINESE: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+        let cell = match self {
+            Language::English => &ENGLISH,
+            Language::Chinese => &CHINESE,
+        };
+        cell.get_or_init(|| parse_catalog(self.catalog_source()))
+    }
+}
+
+/// 当前使用的语言，由启动流程和 `set_language` 指令设置
+static CURRENT_LANGUAGE: RwLock<Language> = RwLock::new(Language::English);
+
+/// 切换当前语言
+pub fn set_language(language: Language) {
+    *CURRENT_LANGUAGE.write().unwrap() = language;
+}
+
+/// 获取当前语言
+pub fn current_language() -> Language {
+    *CURRENT_LANGUAGE.read().unwrap()
+}
+
+/// 按当前语言翻译一条消息，并用 `args` 替换其中的 `{ $name }` 变量。
+/// 当前语言缺少该消息时回退到英文，英文也没有时直接返回消息 ID。
+pub fn translate(key: &str, args: &[(&str, String)]) -> String {
+    let template = current_language()
+        .catalog()
+        .get(key)
+        .or_else(|| Language::English.catalog().get(key));
+    let Some(template) = template else {
+        return key.to_string();
+    };
+
+    let mut message = template.to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{ ${} }}", name), value);
+    }
+    message
+}
+
+/// 翻译一条消息的便捷宏：`t!("tray-show")` 或 `t!("notification-reminder-body", title = task.title)`
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+/// 内部辅助函数：解析 `key = value` 形式的消息目录，忽略空行和 `#` 注释
+fn parse_catalog(source: &'static str) -> HashMap<&'static str, &'static str> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn catalogs_define_the_same_messages() {
+        let english: BTreeSet<_> = Language::English.catalog().keys().collect();
+        let chinese: BTreeSet<_> = Language::Chinese.catalog().keys().collect();
+        assert_eq!(english, chinese);
+    }
+
+    #[test]
+    fn catalog_parsing_skips_comments_and_keeps_placeholders() {
+        let catalog = parse_catalog("# comment\n\ngreeting = Hello, { $name }!\n");
+        assert_eq!(catalog.len(), 1);
+        assert_eq!(catalog["greeting"], "Hello, { $name }!");
+    }
+}
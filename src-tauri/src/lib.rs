@@ -3,3 +3,4 @@ pub mod app;
 pub(crate) mod db;
 pub(crate) mod domain;
 pub mod error;
+pub mod i18n;
@@ -2,16 +2,18 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use momentum_lib::app::commands::{
-    attachment_commands, project_commands, reminder_commands, settings_commands, task_commands,
+    attachment_commands, checklist_commands, dependency_commands, pomodoro_commands,
+    project_commands, reminder_commands, section_commands, settings_commands, tag_commands,
+    task_commands, template_commands, time_entry_commands, workflow_status_commands,
 };
 use momentum_lib::{
     app::{self}, // 确保导入了 reminder_service
     error::Result,
+    i18n::{self, Language},
 };
 
 use tauri::include_image;
 use tauri::{
-    menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     Manager,
 };
@@ -74,15 +76,19 @@ fn main() -> Result<()> {
             let state = app::setup::init_database(app.handle())?;
             app.handle().manage(state);
 
+            // 加载界面语言设置，托盘菜单和通知文本都依赖它
+            let language: Language =
+                app::settings::load(app.handle(), app::settings::LANGUAGE_SETTINGS_KEY)?;
+            i18n::set_language(language);
+
             // 定义托盘右键菜单
-            let show_item = MenuItem::with_id(app, "show", "显示窗口", true, None::<&str>)?;
-            let exit_item = MenuItem::with_id(app, "exit", "退出", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_item, &exit_item])?;
+            let menu = app::tray::build_menu(app.handle())?;
 
             // 创建并配置托盘图标
-            TrayIconBuilder::new()
+            TrayIconBuilder::with_id(app::tray::TRAY_ID)
                 .icon(include_image!("icons/icon.png"))
-                .tooltip("Momentum 正在运行")
+                // 提示文字中会显示正在运行的计时器
+                .tooltip(app::tray::tooltip(app.handle()))
                 .menu(&menu)
                 .on_menu_event(|app, event| match event.id.as_ref() {
                     "show" => {
@@ -117,6 +123,15 @@ fn main() -> Result<()> {
                 app::reminder_service::start(app_handle);
             });
 
+            // 启动后台附件健康检查服务
+            app::attachment_health::start(app.handle().clone());
+
+            // 启动计时器的托盘刷新服务
+            app::time_tracking::start(app.handle().clone());
+
+            // 启动番茄钟服务，它在后台线程中计时，隐藏窗口后依然运行
+            app::pomodoro::start(app.handle().clone());
+
             app.global_shortcut().register(palette_shortcut)?;
 
             Ok(())
@@ -142,28 +157,137 @@ fn main() -> Result<()> {
             task_commands::create_task,
             task_commands::get_tasks_by_parent,
             task_commands::update_task_status,
+            task_commands::set_task_status,
             task_commands::delete_task,
             task_commands::get_task_by_id,
             task_commands::update_task_priority,
             task_commands::update_task_due_date,
             task_commands::update_task_description,
+            task_commands::update_task_estimate,
+            task_commands::duplicate_task,
+            task_commands::bulk_update_task_status,
+            task_commands::bulk_update_task_priority,
+            task_commands::bulk_update_task_due_date,
+            task_commands::bulk_add_tag_to_tasks,
+            task_commands::bulk_move_tasks_to_project,
+            task_commands::bulk_delete_tasks,
+            task_commands::plan_my_day,
             // 项目相关的指令
             project_commands::create_project,
             project_commands::get_all_projects,
             project_commands::update_project,
+            project_commands::get_project_stats,
+            project_commands::update_project_details,
+            project_commands::set_project_status,
+            project_commands::archive_project,
+            project_commands::move_project,
             project_commands::delete_project,
+            project_commands::get_project_default_reminders,
+            project_commands::set_project_default_reminders,
+            // 项目分组相关的指令
+            section_commands::create_section,
+            section_commands::get_sections_for_project,
+            section_commands::rename_section,
+            section_commands::reorder_sections,
+            section_commands::delete_section,
+            section_commands::move_task_to_section,
+            section_commands::get_tasks_grouped_by_section,
+            // 工作流状态相关的指令
+            workflow_status_commands::get_workflow_statuses,
+            workflow_status_commands::create_workflow_status,
+            workflow_status_commands::update_workflow_status,
+            workflow_status_commands::reorder_workflow_statuses,
+            workflow_status_commands::delete_workflow_status,
+            workflow_status_commands::reset_project_workflow_statuses,
+            workflow_status_commands::get_tasks_grouped_by_status,
+            // 时间记录相关的指令
+            time_entry_commands::start_timer,
+            time_entry_commands::stop_timer,
+            time_entry_commands::get_running_timer,
+            time_entry_commands::get_time_entries_for_task,
+            time_entry_commands::create_time_entry,
+            time_entry_commands::update_time_entry,
+            time_entry_commands::delete_time_entry,
+            time_entry_commands::get_project_time_report,
+            // 番茄钟相关的指令
+            pomodoro_commands::start_pomodoro,
+            pomodoro_commands::get_pomodoro_state,
+            pomodoro_commands::pause_pomodoro,
+            pomodoro_commands::resume_pomodoro,
+            pomodoro_commands::skip_pomodoro_phase,
+            pomodoro_commands::stop_pomodoro,
+            pomodoro_commands::get_pomodoros_for_task,
+            // 模板相关的指令
+            template_commands::create_task_template,
+            template_commands::create_project_template,
+            template_commands::get_templates,
+            template_commands::get_template_tasks,
+            template_commands::rename_template,
+            template_commands::delete_template,
+            template_commands::instantiate_template,
+            // 标签相关的指令
+            tag_commands::get_all_tags,
+            tag_commands::get_tags_for_task,
+            tag_commands::get_tasks_by_tag,
+            tag_commands::add_tag_to_task,
+            tag_commands::remove_tag_from_task,
+            tag_commands::rename_tag,
+            tag_commands::delete_tag,
+            // 检查项相关的指令
+            checklist_commands::create_checklist_item,
+            checklist_commands::get_checklist_items,
+            checklist_commands::update_checklist_item,
+            checklist_commands::set_checklist_item_checked,
+            checklist_commands::reorder_checklist_items,
+            checklist_commands::delete_checklist_item,
+            checklist_commands::promote_checklist_item,
+            // 任务依赖相关的指令
+            dependency_commands::add_task_dependency,
+            dependency_commands::remove_task_dependency,
+            dependency_commands::get_task_dependencies,
+            dependency_commands::get_task_dependents,
             // 附件相关的指令
             attachment_commands::create_url_attachment,
+            attachment_commands::update_attachment_title,
             attachment_commands::get_attachments_for_task,
             attachment_commands::delete_attachment,
             attachment_commands::create_local_path_attachment,
+            attachment_commands::create_note_attachment,
+            attachment_commands::update_note_attachment,
+            attachment_commands::create_email_attachment,
+            attachment_commands::create_code_ref_attachment,
+            attachment_commands::get_attachment_file_path,
+            attachment_commands::collect_vault_garbage,
+            attachment_commands::check_attachments,
+            attachment_commands::relink_attachments,
             // 提醒相关的指令
             reminder_commands::create_reminder,
+            reminder_commands::create_relative_reminder,
             reminder_commands::get_reminders_for_task,
             reminder_commands::delete_reminder,
+            reminder_commands::get_reminder_inbox,
+            reminder_commands::get_missed_reminders,
+            reminder_commands::snooze_reminder,
+            reminder_commands::dismiss_reminder,
+            reminder_commands::complete_reminder_task,
+            reminder_commands::open_reminder_task,
+            reminder_commands::get_daily_digest,
             // 设置相关的指令
             settings_commands::get_data_path,
-            settings_commands::set_data_path
+            settings_commands::set_data_path,
+            settings_commands::get_digest_settings,
+            settings_commands::set_digest_settings,
+            settings_commands::get_quiet_hours_settings,
+            settings_commands::set_quiet_hours_settings,
+            settings_commands::set_do_not_disturb,
+            settings_commands::get_task_completion_settings,
+            settings_commands::set_task_completion_settings,
+            settings_commands::get_pomodoro_settings,
+            settings_commands::set_pomodoro_settings,
+            settings_commands::get_planning_settings,
+            settings_commands::set_planning_settings,
+            settings_commands::get_language,
+            settings_commands::set_language
         ])
         // 启动应用
         .run(tauri::generate_context!())